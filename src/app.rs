@@ -1,4 +1,19 @@
-use crate::model::{ApiSpec, Endpoint};
+use std::collections::BTreeMap;
+
+use crate::auth::{Auth, TokenRequest};
+use crate::builder::RequestForm;
+use crate::clipboard::Backend;
+use crate::config::Config;
+use crate::environment::{self, Environment};
+use crate::http::{Exchange, Response};
+use crate::model::{ApiSpec, Endpoint, EndpointKind, Link, Server};
+use crate::snippet::{Body, Language, Request};
+use crate::theme::Theme;
+use crate::validate;
+
+pub(crate) const MIN_SPLIT_PERCENT: u16 = 20;
+pub(crate) const MAX_SPLIT_PERCENT: u16 = 80;
+const SPLIT_STEP: u16 = 5;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Focus {
@@ -6,37 +21,263 @@ pub enum Focus {
     Detail,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    /// Endpoint list and detail panes.
+    Browse,
+    /// API metadata and statistics.
+    Overview,
+    /// Form filling in a request for the selected endpoint.
+    Request,
+    /// A response received this session, see `App::response_index`.
+    Response,
+}
+
+/// A single input of a popup form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormField {
+    pub name: String,
+    pub value: String,
+    /// Allowed values; when non-empty the value is picked rather than typed.
+    pub options: Vec<String>,
+    pub description: Option<String>,
+}
+
+impl FormField {
+    pub fn push_char(&mut self, c: char) {
+        if self.options.is_empty() {
+            self.value.push(c);
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        if self.options.is_empty() {
+            self.value.pop();
+        }
+    }
+
+    pub fn cycle_option(&mut self, forward: bool) {
+        let len = self.options.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.options.iter().position(|o| o == &self.value);
+        let next = match (current, forward) {
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => i.checked_sub(1).unwrap_or(len - 1),
+            (None, _) => 0,
+        };
+        self.value = self.options[next].clone();
+    }
+}
+
+/// Something from the selected endpoint that can be copied to the clipboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyItem {
+    pub label: String,
+    pub text: String,
+}
+
+impl CopyItem {
+    fn new(label: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            text: text.into(),
+        }
+    }
+}
+
+/// A transient message shown at the bottom of the screen until the next key press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusMessage {
+    pub text: String,
+    pub error: bool,
+}
+
+/// Modal overlays drawn on top of the current screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Popup {
+    /// Choosing the active server among `App::selected_servers`.
+    ServerPicker { selected: usize },
+    /// Filling in the variables of `App::selected_servers()[server]`.
+    ServerVariables {
+        server: usize,
+        fields: Vec<FormField>,
+        selected: usize,
+    },
+    /// Following one of the response links of the selected endpoint.
+    Links { selected: usize },
+    /// Jumping to one of the components the detail pane refers to.
+    Definitions { selected: usize },
+    /// Code calling the selected endpoint in `App::snippet_language`,
+    /// scrolled by `scroll` lines.
+    Snippet { scroll: u16 },
+    /// Choosing what to copy among `App::copy_items`.
+    Copy { selected: usize },
+    /// Re-opening a sent request, most recent first.
+    History { selected: usize },
+    /// Choosing the active environment; 0 is none, then `App::environments`.
+    Environments { selected: usize },
+}
+
+/// Where the browser was before a jump, so that it can be returned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// Index into `spec.endpoints` of the selected endpoint.
+    endpoint: Option<usize>,
+    definition: Option<usize>,
+    detail_scroll: u16,
+    focus: Focus,
+}
+
+/// Jumps remembered in each direction.
+const HISTORY_LIMIT: usize = 100;
+
 pub struct App {
     pub spec: ApiSpec,
     pub selected_index: usize,
     pub should_quit: bool,
     pub focus: Focus,
+    pub screen: Screen,
+    pub overview_scroll: u16,
     pub detail_scroll: u16,
+    /// Which example to show where an endpoint has several; each group of
+    /// examples shows this index modulo its length.
+    pub example_index: usize,
+    /// Leaves optional properties out of payloads generated from schemas.
+    pub required_only: bool,
+    /// Expands the Extensions section of the detail pane.
+    pub show_extensions: bool,
+    /// Largest useful `detail_scroll`, recomputed from the pane size on every draw.
+    pub detail_max_scroll: u16,
+    /// First visible row of the endpoint list, kept across draws.
+    pub list_offset: usize,
     pub search_mode: bool,
     pub search_query: String,
     pub filtered_indices: Vec<usize>,
+    /// Leaves deprecated operations out of the endpoint list.
+    pub hide_deprecated: bool,
+    /// Share of the screen given to the endpoint list, in percent.
+    pub split_percent: u16,
+    /// Terminal width below which the panes are stacked vertically.
+    pub stack_below: u16,
+    /// When set, only the focused pane is drawn.
+    pub zoomed: bool,
+    pub theme: Theme,
+    pub popup: Option<Popup>,
+    /// Index into `spec.components` shown in the detail pane instead of the
+    /// selected endpoint.
+    pub definition: Option<usize>,
+    /// Locations to return to with Ctrl-o, most recent last.
+    back_stack: Vec<Location>,
+    /// Locations left with Ctrl-o, to go forward to again with Tab.
+    forward_stack: Vec<Location>,
+    /// Client the snippet popup generates code for, kept between popups.
+    pub snippet_language: Language,
+    /// Clipboard backend from the config, see `clipboard::copy`.
+    pub clipboard: Option<String>,
+    /// Request being built, kept while the same endpoint stays selected.
+    pub request_form: Option<RequestForm>,
+    /// Request waiting to be sent by the event loop, with the index of its
+    /// endpoint.
+    pub pending_send: Option<(usize, Request)>,
+    /// Requests sent this session, oldest first.
+    pub history: Vec<Exchange>,
+    /// Index into `history` shown on the response screen.
+    pub response_index: usize,
+    pub response_scroll: u16,
+    /// Text waiting to be copied by the event loop, which owns the terminal.
+    pub pending_copy: Option<CopyItem>,
+    pub status: Option<StatusMessage>,
+    /// Index into `spec.servers` used to build request URLs.
+    pub active_server: usize,
+    /// The server chosen for endpoints with their own `servers`, keyed by the
+    /// URLs of those servers so that the operations of a path share it.
+    pub override_servers: BTreeMap<Vec<String>, usize>,
+    /// User-provided variable values, by server URL template.
+    pub server_values: BTreeMap<String, BTreeMap<String, String>>,
+    pub environments: Vec<Environment>,
+    /// Index into `environments` of the one requests are built with.
+    pub active_environment: Option<usize>,
+    /// Credentials applied to requests, shared with the threads sending them.
+    pub auth: Auth,
 }
 
 impl App {
+    #[cfg(test)]
     pub fn new(spec: ApiSpec) -> Self {
+        Self::with_config(spec, &Config::default(), Theme::dark())
+    }
+
+    pub fn with_config(spec: ApiSpec, config: &Config, theme: Theme) -> Self {
         let endpoint_count = spec.endpoints.len();
+        let environments = environment::from_config(&config.environments);
+        let active_environment = config
+            .environment
+            .as_ref()
+            .and_then(|name| environments.iter().position(|env| &env.name == name));
         Self {
             spec,
             selected_index: 0,
             should_quit: false,
             focus: Focus::List,
+            screen: if config.overview_on_startup {
+                Screen::Overview
+            } else {
+                Screen::Browse
+            },
+            overview_scroll: 0,
             detail_scroll: 0,
+            example_index: 0,
+            required_only: false,
+            show_extensions: false,
+            detail_max_scroll: u16::MAX,
+            list_offset: 0,
             search_mode: false,
             search_query: String::new(),
             filtered_indices: (0..endpoint_count).collect(),
+            hide_deprecated: false,
+            split_percent: config
+                .layout
+                .split_percent
+                .clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT),
+            stack_below: config.layout.stack_below,
+            zoomed: false,
+            theme,
+            popup: None,
+            definition: None,
+            back_stack: Vec::new(),
+            forward_stack: Vec::new(),
+            snippet_language: Language::default(),
+            clipboard: config.clipboard.clone(),
+            request_form: None,
+            pending_send: None,
+            history: Vec::new(),
+            response_index: 0,
+            response_scroll: 0,
+            pending_copy: None,
+            status: None,
+            active_server: 0,
+            override_servers: BTreeMap::new(),
+            server_values: BTreeMap::new(),
+            environments,
+            active_environment,
+            auth: Auth::new(config.auth.clone()),
         }
     }
 
+    /// Whether key presses should be treated as text rather than shortcuts.
+    pub fn text_input(&self) -> bool {
+        self.search_mode
+            || self.screen == Screen::Request
+            || matches!(self.popup, Some(Popup::ServerVariables { .. }))
+    }
+
     pub fn select_next(&mut self) {
         let len = self.filtered_indices.len();
         if len > 0 {
             self.selected_index = (self.selected_index + 1) % len;
-            self.detail_scroll = 0;
+            self.reset_detail();
         }
     }
 
@@ -44,7 +285,7 @@ impl App {
         let len = self.filtered_indices.len();
         if len > 0 {
             self.selected_index = self.selected_index.checked_sub(1).unwrap_or(len - 1);
-            self.detail_scroll = 0;
+            self.reset_detail();
         }
     }
 
@@ -93,9 +334,8 @@ impl App {
             .endpoints
             .iter()
             .enumerate()
-            .filter(|(_, ep)| {
-                query_lower.is_empty() || ep.path.to_lowercase().contains(&query_lower)
-            })
+            .filter(|(_, ep)| !(self.hide_deprecated && ep.deprecated))
+            .filter(|(_, ep)| matches_query(ep, &query_lower))
             .map(|(i, _)| i)
             .collect();
 
@@ -104,6 +344,67 @@ impl App {
         }
     }
 
+    /// Shows or hides deprecated operations, keeping the selected endpoint
+    /// selected when it stays visible.
+    pub fn toggle_hide_deprecated(&mut self) {
+        let selected = self.filtered_indices.get(self.selected_index).copied();
+        self.hide_deprecated = !self.hide_deprecated;
+        self.update_filtered_indices();
+        if let Some(position) =
+            selected.and_then(|idx| self.filtered_indices.iter().position(|&i| i == idx))
+        {
+            self.selected_index = position;
+        }
+        self.reset_detail();
+    }
+
+    /// Scrolls the detail pane back to the top and shows the first examples,
+    /// for when another endpoint gets selected.
+    fn reset_detail(&mut self) {
+        self.detail_scroll = 0;
+        self.example_index = 0;
+        self.definition = None;
+    }
+
+    /// Largest number of examples in any single group of the selected endpoint.
+    fn example_count(&self) -> usize {
+        let Some(endpoint) = self.selected_endpoint() else {
+            return 0;
+        };
+        let params = endpoint.parameters.iter().flat_map(|p| {
+            std::iter::once(p.examples.len()).chain(p.content.iter().map(|m| m.examples.len()))
+        });
+        let bodies = endpoint
+            .request_body
+            .iter()
+            .flat_map(|b| &b.content)
+            .chain(endpoint.responses.values().flat_map(|r| &r.content))
+            .map(|m| m.examples.len());
+        params.chain(bodies).max().unwrap_or(0)
+    }
+
+    pub fn next_example(&mut self) {
+        let count = self.example_count();
+        if count > 1 {
+            self.example_index = (self.example_index + 1) % count;
+        }
+    }
+
+    pub fn previous_example(&mut self) {
+        let count = self.example_count();
+        if count > 1 {
+            self.example_index = self.example_index.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    pub fn toggle_required_only(&mut self) {
+        self.required_only = !self.required_only;
+    }
+
+    pub fn toggle_extensions(&mut self) {
+        self.show_extensions = !self.show_extensions;
+    }
+
     pub fn quit(&mut self) {
         self.should_quit = true;
     }
@@ -117,18 +418,789 @@ impl App {
     }
 
     pub fn scroll_down(&mut self) {
-        self.detail_scroll = self.detail_scroll.saturating_add(1);
+        self.detail_scroll = self
+            .detail_scroll
+            .saturating_add(1)
+            .min(self.detail_max_scroll);
     }
 
     pub fn scroll_up(&mut self) {
         self.detail_scroll = self.detail_scroll.saturating_sub(1);
     }
+
+    /// Records how far the detail pane can scroll and clamps the current offset,
+    /// e.g. after the terminal was resized.
+    pub fn set_detail_max_scroll(&mut self, max_scroll: u16) {
+        self.detail_max_scroll = max_scroll;
+        self.detail_scroll = self.detail_scroll.min(max_scroll);
+    }
+
+    pub fn toggle_overview(&mut self) {
+        self.screen = match self.screen {
+            Screen::Overview => Screen::Browse,
+            Screen::Browse | Screen::Request | Screen::Response => Screen::Overview,
+        };
+        self.overview_scroll = 0;
+    }
+
+    pub fn close_overview(&mut self) {
+        self.screen = Screen::Browse;
+    }
+
+    /// Scrolling past the end is clamped when the overview is drawn.
+    pub fn overview_scroll_down(&mut self) {
+        self.overview_scroll = self.overview_scroll.saturating_add(1);
+    }
+
+    pub fn overview_scroll_up(&mut self) {
+        self.overview_scroll = self.overview_scroll.saturating_sub(1);
+    }
+
+    /// The servers the selected endpoint is served from: its own path- or
+    /// operation-level `servers`, or else the API's.
+    pub fn selected_servers(&self) -> &[Server] {
+        self.servers_of(self.selected_endpoint())
+    }
+
+    fn servers_of<'a>(&'a self, endpoint: Option<&'a Endpoint>) -> &'a [Server] {
+        match endpoint {
+            Some(endpoint) if !endpoint.servers.is_empty() => &endpoint.servers,
+            _ => &self.spec.servers,
+        }
+    }
+
+    /// Index into the endpoint's servers (see `selected_servers`) of the one
+    /// requests go to.
+    pub fn chosen_server(&self, endpoint: Option<&Endpoint>) -> usize {
+        match endpoint {
+            Some(endpoint) if !endpoint.servers.is_empty() => self
+                .override_servers
+                .get(&server_urls(&endpoint.servers))
+                .copied()
+                .unwrap_or(0),
+            _ => self.active_server,
+        }
+    }
+
+    /// Makes `index` into `selected_servers` the server requests go to.
+    fn choose_server(&mut self, index: usize) {
+        match self.selected_endpoint() {
+            Some(endpoint) if !endpoint.servers.is_empty() => {
+                let key = server_urls(&endpoint.servers);
+                self.override_servers.insert(key, index);
+            }
+            _ => self.active_server = index,
+        }
+    }
+
+    /// The URL of `server` with the variables filled in by the user.
+    pub fn resolve_server(&self, server: &Server) -> String {
+        match self.server_values.get(&server.url) {
+            Some(values) => server.resolve_url(values),
+            None => server.resolve_url(&BTreeMap::new()),
+        }
+    }
+
+    /// Resolved base URL for an endpoint: the active environment's `base_url`,
+    /// or else the server chosen among the endpoint's own `servers` or the
+    /// API's.
+    pub fn server_url(&self, endpoint: &Endpoint) -> Option<String> {
+        // Callbacks and webhooks are sent to the API's clients, not its servers
+        if endpoint.kind != EndpointKind::Operation {
+            return None;
+        }
+        if let Some(environment) = self.environment() {
+            if let Some(base_url) = &environment.base_url {
+                return Some(environment.substitute(base_url));
+            }
+        }
+        let servers = self.servers_of(Some(endpoint));
+        let server = servers.get(self.chosen_server(Some(endpoint)))?;
+        Some(self.resolve_server(server))
+    }
+
+    /// Full URL of an endpoint, with path parameters left as placeholders.
+    pub fn endpoint_url(&self, endpoint: &Endpoint) -> String {
+        match self.server_url(endpoint) {
+            Some(base) => format!("{}{}", base.trim_end_matches('/'), endpoint.path),
+            None => endpoint.path.clone(),
+        }
+    }
+
+    pub fn open_server_picker(&mut self) {
+        if !self.selected_servers().is_empty() {
+            self.popup = Some(Popup::ServerPicker {
+                selected: self.chosen_server(self.selected_endpoint()),
+            });
+        }
+    }
+
+    /// Response links of the selected endpoint, in response order.
+    pub fn selected_links(&self) -> Vec<&Link> {
+        self.selected_endpoint()
+            .map(|ep| ep.responses.values().flat_map(|r| &r.links).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn open_links(&mut self) {
+        if !self.selected_links().is_empty() {
+            self.popup = Some(Popup::Links { selected: 0 });
+        }
+    }
+
+    /// The request for the selected endpoint: as filled in the request builder,
+    /// or else generated from the spec with the example and field selection of
+    /// the detail pane. Either way it goes to the endpoint's server and carries
+    /// the active environment's variables and the configured credentials.
+    pub fn selected_request(&self) -> Option<Request> {
+        let endpoint = self.selected_endpoint()?;
+        let base_url = self.server_url(endpoint);
+        let environment = self.environment();
+        let mut request = match self.selected_form() {
+            Some(form) => form.request(endpoint, &self.spec, base_url.as_deref(), environment),
+            None => {
+                let request = Request::new(
+                    endpoint,
+                    &self.spec,
+                    base_url.as_deref(),
+                    self.example_index,
+                    self.required_only,
+                );
+                match environment {
+                    Some(environment) => environment.apply(&request),
+                    None => request,
+                }
+            }
+        };
+        self.auth
+            .apply(&mut request, endpoint, &self.spec, environment);
+        Some(request)
+    }
+
+    /// OAuth2 tokens to fetch before sending a request for `endpoint`.
+    pub fn token_requests(&self, endpoint: usize) -> Vec<TokenRequest> {
+        match self.spec.endpoints.get(endpoint) {
+            Some(endpoint) => self
+                .auth
+                .token_requests(endpoint, &self.spec, self.environment()),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn environment(&self) -> Option<&Environment> {
+        self.environments.get(self.active_environment?)
+    }
+
+    /// `text` with the secrets of the active environment, the configured
+    /// credentials and the fetched tokens hidden, for anything shown or copied.
+    pub fn mask(&self, text: &str) -> String {
+        self.with_secrets(|secrets| environment::mask(text, secrets))
+    }
+
+    /// `request` with the secrets hidden (see `mask`) before they get
+    /// percent-encoded or escaped in a URL or snippet.
+    pub fn mask_request(&self, request: &Request) -> Request {
+        self.with_secrets(|secrets| environment::mask_request(request, secrets))
+    }
+
+    /// The full URL of `request` with the secrets hidden.
+    pub fn masked_url(&self, request: &Request) -> String {
+        self.mask_request(request).full_url()
+    }
+
+    fn with_secrets<T>(&self, f: impl FnOnce(&[&str]) -> T) -> T {
+        let environment = self.environment();
+        let credentials = self.auth.secrets(environment);
+        let mut secrets: Vec<&str> = credentials.iter().map(String::as_str).collect();
+        if let Some(environment) = environment {
+            secrets.extend(environment.secret_values());
+        }
+        f(&secrets)
+    }
+
+    pub fn open_environments(&mut self) {
+        if self.environments.is_empty() {
+            self.status = Some(StatusMessage {
+                text: "No environments configured".to_string(),
+                error: true,
+            });
+        } else {
+            self.popup = Some(Popup::Environments {
+                selected: self.active_environment.map_or(0, |i| i + 1),
+            });
+        }
+    }
+
+    /// The request builder, if it was opened for the selected endpoint.
+    pub fn selected_form(&self) -> Option<&RequestForm> {
+        let index = *self.filtered_indices.get(self.selected_index)?;
+        self.request_form
+            .as_ref()
+            .filter(|form| form.endpoint == index)
+    }
+
+    /// Opens the request builder, keeping the values entered earlier for the
+    /// same endpoint.
+    pub fn open_request_builder(&mut self) {
+        let Some(&index) = self.filtered_indices.get(self.selected_index) else {
+            return;
+        };
+        if self.selected_form().is_none() {
+            let endpoint = &self.spec.endpoints[index];
+            self.request_form = Some(RequestForm::new(
+                index,
+                endpoint,
+                self.example_index,
+                self.required_only,
+            ));
+        }
+        self.screen = Screen::Request;
+    }
+
+    pub fn close_request_builder(&mut self) {
+        self.screen = Screen::Browse;
+    }
+
+    /// Queues the selected request for sending, unless the request builder
+    /// reports a problem with it or a placeholder is left in it.
+    pub fn send_request(&mut self) {
+        let Some(&index) = self.filtered_indices.get(self.selected_index) else {
+            return;
+        };
+        let problem = self
+            .selected_form()
+            .and_then(|form| form.errors(self.environment()).into_iter().next());
+        if let Some(problem) = problem {
+            self.status = Some(StatusMessage {
+                text: format!("Not sent: {}", problem),
+                error: true,
+            });
+            return;
+        }
+        let Some(request) = self.selected_request() else {
+            return;
+        };
+        if let Some(placeholder) = request.placeholders().first() {
+            self.status = Some(StatusMessage {
+                text: format!("Not sent: {} has no value", placeholder),
+                error: true,
+            });
+            return;
+        }
+        self.queue_send(index, request);
+    }
+
+    /// Sends the request shown on the response screen again.
+    pub fn resend(&mut self) {
+        if let Some(exchange) = self.history.get(self.response_index) {
+            let (endpoint, request) = (exchange.endpoint, exchange.request.clone());
+            self.queue_send(endpoint, request);
+        }
+    }
+
+    fn queue_send(&mut self, endpoint: usize, request: Request) {
+        self.status = Some(StatusMessage {
+            text: format!("Sending {} {}…", request.method, self.masked_url(&request)),
+            error: false,
+        });
+        self.pending_send = Some((endpoint, request));
+    }
+
+    /// Records the outcome of a request sent by the event loop and shows it.
+    pub fn received(
+        &mut self,
+        endpoint: usize,
+        request: Request,
+        result: anyhow::Result<Response>,
+    ) {
+        let validation = match (&result, self.spec.endpoints.get(endpoint)) {
+            (Ok(response), Some(declared)) => Some(validate::check(declared, &self.spec, response)),
+            _ => None,
+        };
+        self.history.push(Exchange {
+            endpoint,
+            request,
+            response: result.map_err(|err| format!("{:#}", err)),
+            validation,
+        });
+        self.clear_status();
+        self.open_response(self.history.len() - 1);
+    }
+
+    pub fn open_response(&mut self, index: usize) {
+        self.response_index = index;
+        self.response_scroll = 0;
+        self.screen = Screen::Response;
+    }
+
+    pub fn close_response(&mut self) {
+        self.screen = Screen::Browse;
+    }
+
+    /// Scrolling past the end is clamped when the response is drawn.
+    pub fn response_scroll_down(&mut self) {
+        self.response_scroll = self.response_scroll.saturating_add(1);
+    }
+
+    pub fn response_scroll_up(&mut self) {
+        self.response_scroll = self.response_scroll.saturating_sub(1);
+    }
+
+    pub fn open_history(&mut self) {
+        if self.history.is_empty() {
+            self.status = Some(StatusMessage {
+                text: "No requests sent yet".to_string(),
+                error: false,
+            });
+        } else {
+            self.popup = Some(Popup::History { selected: 0 });
+        }
+    }
+
+    /// Code sending the selected request in the chosen language.
+    pub fn snippet(&self) -> Option<String> {
+        self.selected_request()
+            .map(|request| self.snippet_language.generate(&self.mask_request(&request)))
+    }
+
+    pub fn open_snippet(&mut self) {
+        if self.selected_endpoint().is_some() {
+            self.popup = Some(Popup::Snippet { scroll: 0 });
+        }
+    }
+
+    /// What can be copied from the selected endpoint: its path, operation ID,
+    /// request snippet, and the example payload and schema of the request
+    /// body and of the first response with content.
+    pub fn copy_items(&self) -> Vec<CopyItem> {
+        let Some(endpoint) = self.selected_endpoint() else {
+            return Vec::new();
+        };
+        let mut items = vec![
+            CopyItem::new("Path", endpoint.path.clone()),
+            CopyItem::new(
+                "Method and path",
+                format!("{} {}", endpoint.method, endpoint.path),
+            ),
+        ];
+        if let Some(id) = &endpoint.operation_id {
+            items.push(CopyItem::new("Operation ID", id.clone()));
+        }
+        if let Some(snippet) = self.snippet() {
+            items.push(CopyItem::new(
+                format!("{} snippet", self.snippet_language),
+                snippet,
+            ));
+        }
+
+        let request = endpoint
+            .request_body
+            .as_ref()
+            .and_then(|body| body.content.first());
+        let response = endpoint.responses.values().find_map(|r| r.content.first());
+        for (name, media) in [("Request", request), ("Response", response)] {
+            let Some(media) = media else {
+                continue;
+            };
+            if let Some(value) = media.example_value(self.example_index, self.required_only) {
+                let body = Body {
+                    content_type: media.content_type.clone(),
+                    value,
+                };
+                items.push(CopyItem::new(format!("{} example", name), body.text()));
+            }
+            if let Some(schema) = &media.schema_json {
+                let text = serde_json::to_string_pretty(schema).unwrap_or_default();
+                items.push(CopyItem::new(format!("{} schema", name), text));
+            }
+        }
+        for item in &mut items {
+            item.text = self.mask(&item.text);
+        }
+        items
+    }
+
+    pub fn open_copy(&mut self) {
+        if !self.copy_items().is_empty() {
+            self.popup = Some(Popup::Copy { selected: 0 });
+        }
+    }
+
+    /// Copies the snippet straight from the snippet popup.
+    pub fn copy_snippet(&mut self) {
+        if let Some(snippet) = self.snippet() {
+            let label = format!("{} snippet", self.snippet_language);
+            self.pending_copy = Some(CopyItem::new(label, snippet));
+        }
+    }
+
+    /// Reports the outcome of copying `item` in the status line.
+    pub fn copied(&mut self, item: &CopyItem, result: anyhow::Result<Backend>) {
+        self.status = Some(match result {
+            Ok(backend) => StatusMessage {
+                text: format!("Copied {} ({})", item.label.to_lowercase(), backend),
+                error: false,
+            },
+            Err(err) => StatusMessage {
+                text: format!("Copy failed: {:#}", err),
+                error: true,
+            },
+        });
+    }
+
+    pub fn clear_status(&mut self) {
+        self.status = None;
+    }
+
+    /// Indices into `spec.components` of the definitions the detail pane
+    /// refers to, skipping references that don't resolve.
+    pub fn definition_targets(&self) -> Vec<usize> {
+        let references = match self.definition {
+            Some(index) => self.spec.components[index].references(),
+            None => self
+                .selected_endpoint()
+                .map(Endpoint::references)
+                .unwrap_or_default(),
+        };
+        references
+            .iter()
+            .filter_map(|reference| self.spec.find_component(reference))
+            .collect()
+    }
+
+    pub fn open_definitions(&mut self) {
+        if !self.definition_targets().is_empty() {
+            self.popup = Some(Popup::Definitions { selected: 0 });
+        }
+    }
+
+    /// Shows the component at `index` in the detail pane, remembering where
+    /// we came from.
+    pub fn open_definition(&mut self, index: usize) {
+        self.record_jump();
+        self.definition = Some(index);
+        self.detail_scroll = 0;
+        self.focus = Focus::Detail;
+    }
+
+    fn location(&self) -> Location {
+        Location {
+            endpoint: self.filtered_indices.get(self.selected_index).copied(),
+            definition: self.definition,
+            detail_scroll: self.detail_scroll,
+            focus: self.focus,
+        }
+    }
+
+    fn restore(&mut self, location: Location) {
+        if let Some(endpoint) = location.endpoint {
+            self.jump_to_endpoint(endpoint);
+        }
+        self.definition = location.definition;
+        self.detail_scroll = location.detail_scroll;
+        self.focus = location.focus;
+    }
+
+    fn record_jump(&mut self) {
+        let location = self.location();
+        push_limited(&mut self.back_stack, location);
+        self.forward_stack.clear();
+    }
+
+    /// Returns to where the last jump was made from.
+    pub fn jump_back(&mut self) {
+        if let Some(location) = self.back_stack.pop() {
+            let current = self.location();
+            push_limited(&mut self.forward_stack, current);
+            self.restore(location);
+        }
+    }
+
+    /// Redoes a jump undone with `jump_back`.
+    pub fn jump_forward(&mut self) {
+        if let Some(location) = self.forward_stack.pop() {
+            let current = self.location();
+            push_limited(&mut self.back_stack, current);
+            self.restore(location);
+        }
+    }
+
+    /// Selects the endpoint at `index` into `spec.endpoints`, clearing any
+    /// filter that hides it.
+    pub fn jump_to_endpoint(&mut self, index: usize) {
+        if !self.filtered_indices.contains(&index) {
+            self.search_query.clear();
+            if self.spec.endpoints[index].deprecated {
+                self.hide_deprecated = false;
+            }
+            self.update_filtered_indices();
+        }
+        if let Some(position) = self.filtered_indices.iter().position(|&i| i == index) {
+            self.selected_index = position;
+            self.reset_detail();
+        }
+    }
+
+    pub fn close_popup(&mut self) {
+        self.popup = None;
+    }
+
+    pub fn popup_next(&mut self) {
+        let server_count = self.selected_servers().len();
+        let link_count = self.selected_links().len();
+        let definition_count = self.definition_targets().len();
+        let snippet_lines = match self.popup {
+            Some(Popup::Snippet { .. }) => self.snippet().map_or(0, |code| code.lines().count()),
+            _ => 0,
+        };
+        let copy_count = self.copy_items().len();
+        let history_count = self.history.len();
+        let environment_count = self.environments.len() + 1;
+        match &mut self.popup {
+            Some(Popup::ServerPicker { selected }) => {
+                *selected = (*selected + 1) % server_count;
+            }
+            Some(Popup::ServerVariables {
+                fields, selected, ..
+            }) => {
+                *selected = (*selected + 1) % fields.len();
+            }
+            Some(Popup::Links { selected }) => {
+                *selected = (*selected + 1) % link_count;
+            }
+            Some(Popup::Definitions { selected }) => {
+                *selected = (*selected + 1) % definition_count;
+            }
+            Some(Popup::Snippet { scroll }) if usize::from(*scroll) + 1 < snippet_lines => {
+                *scroll += 1;
+            }
+            Some(Popup::Copy { selected }) => {
+                *selected = (*selected + 1) % copy_count;
+            }
+            Some(Popup::History { selected }) => {
+                *selected = (*selected + 1) % history_count;
+            }
+            Some(Popup::Environments { selected }) => {
+                *selected = (*selected + 1) % environment_count;
+            }
+            Some(Popup::Snippet { .. }) | None => {}
+        }
+    }
+
+    pub fn popup_previous(&mut self) {
+        let server_count = self.selected_servers().len();
+        let link_count = self.selected_links().len();
+        let definition_count = self.definition_targets().len();
+        let copy_count = self.copy_items().len();
+        let history_count = self.history.len();
+        let environment_count = self.environments.len() + 1;
+        match &mut self.popup {
+            Some(Popup::ServerPicker { selected }) => {
+                *selected = selected.checked_sub(1).unwrap_or(server_count - 1);
+            }
+            Some(Popup::ServerVariables {
+                fields, selected, ..
+            }) => {
+                *selected = selected.checked_sub(1).unwrap_or(fields.len() - 1);
+            }
+            Some(Popup::Links { selected }) => {
+                *selected = selected.checked_sub(1).unwrap_or(link_count - 1);
+            }
+            Some(Popup::Definitions { selected }) => {
+                *selected = selected.checked_sub(1).unwrap_or(definition_count - 1);
+            }
+            Some(Popup::Snippet { scroll }) => {
+                *scroll = scroll.saturating_sub(1);
+            }
+            Some(Popup::Copy { selected }) => {
+                *selected = selected.checked_sub(1).unwrap_or(copy_count - 1);
+            }
+            Some(Popup::History { selected }) => {
+                *selected = selected.checked_sub(1).unwrap_or(history_count - 1);
+            }
+            Some(Popup::Environments { selected }) => {
+                *selected = selected.checked_sub(1).unwrap_or(environment_count - 1);
+            }
+            None => {}
+        }
+    }
+
+    /// Activates the highlighted server, applies the edited variables,
+    /// follows the highlighted link or definition, copies the highlighted
+    /// item, re-opens the highlighted response, or switches environments.
+    pub fn popup_confirm(&mut self) {
+        match self.popup.take() {
+            Some(Popup::ServerPicker { selected }) => self.choose_server(selected),
+            Some(Popup::ServerVariables { server, fields, .. }) => {
+                let url = self.selected_servers()[server].url.clone();
+                let values = fields
+                    .into_iter()
+                    .map(|field| (field.name, field.value))
+                    .collect();
+                self.server_values.insert(url, values);
+                self.choose_server(server);
+            }
+            Some(Popup::Links { selected }) => {
+                let target = self
+                    .selected_links()
+                    .get(selected)
+                    .and_then(|link| self.spec.find_link_target(&link.target));
+                if let Some(index) = target {
+                    self.record_jump();
+                    self.jump_to_endpoint(index);
+                }
+            }
+            Some(Popup::Definitions { selected }) => {
+                if let Some(&index) = self.definition_targets().get(selected) {
+                    self.open_definition(index);
+                }
+            }
+            Some(Popup::Copy { selected }) => {
+                self.pending_copy = self.copy_items().into_iter().nth(selected);
+            }
+            Some(Popup::History { selected }) => {
+                if let Some(index) = self.history.len().checked_sub(selected + 1) {
+                    self.open_response(index);
+                }
+            }
+            Some(Popup::Environments { selected }) => {
+                self.active_environment = selected.checked_sub(1);
+                let text = match self.environment() {
+                    Some(environment) => format!("Environment: {}", environment.name),
+                    None => "No environment".to_string(),
+                };
+                self.status = Some(StatusMessage { text, error: false });
+            }
+            Some(Popup::Snippet { .. }) | None => {}
+        }
+    }
+
+    /// Opens the variable form for the server highlighted in the picker.
+    pub fn edit_server_variables(&mut self) {
+        let Some(Popup::ServerPicker { selected }) = self.popup else {
+            return;
+        };
+        let server = &self.selected_servers()[selected];
+        if server.variables.is_empty() {
+            return;
+        }
+
+        let values = self.server_values.get(&server.url);
+        let fields = server
+            .variables
+            .iter()
+            .map(|variable| FormField {
+                name: variable.name.clone(),
+                value: values
+                    .and_then(|values| values.get(&variable.name))
+                    .unwrap_or(&variable.default)
+                    .clone(),
+                options: variable.enum_values.clone(),
+                description: variable.description.clone(),
+            })
+            .collect();
+
+        self.popup = Some(Popup::ServerVariables {
+            server: selected,
+            fields,
+            selected: 0,
+        });
+    }
+
+    fn selected_form_field(&mut self) -> Option<&mut FormField> {
+        match &mut self.popup {
+            Some(Popup::ServerVariables {
+                fields, selected, ..
+            }) => fields.get_mut(*selected),
+            _ => None,
+        }
+    }
+
+    pub fn form_push_char(&mut self, c: char) {
+        if let Some(field) = self.selected_form_field() {
+            field.push_char(c);
+        }
+    }
+
+    pub fn form_pop_char(&mut self) {
+        if let Some(field) = self.selected_form_field() {
+            field.pop_char();
+        }
+    }
+
+    /// Cycles the options of the selected form field, or the snippet language.
+    pub fn popup_cycle(&mut self, forward: bool) {
+        if let Some(Popup::Snippet { scroll }) = &mut self.popup {
+            *scroll = 0;
+            self.snippet_language = self.snippet_language.cycle(forward);
+        } else if let Some(field) = self.selected_form_field() {
+            field.cycle_option(forward);
+        }
+    }
+
+    pub fn grow_list(&mut self) {
+        self.split_percent = (self.split_percent + SPLIT_STEP).min(MAX_SPLIT_PERCENT);
+    }
+
+    pub fn shrink_list(&mut self) {
+        self.split_percent = self
+            .split_percent
+            .saturating_sub(SPLIT_STEP)
+            .max(MIN_SPLIT_PERCENT);
+    }
+
+    pub fn toggle_zoom(&mut self) {
+        self.zoomed = !self.zoomed;
+    }
+}
+
+/// Identifies a list of path- or operation-level servers.
+fn server_urls(servers: &[Server]) -> Vec<String> {
+    servers.iter().map(|server| server.url.clone()).collect()
+}
+
+fn push_limited(stack: &mut Vec<Location>, location: Location) {
+    if stack.len() == HISTORY_LIMIT {
+        stack.remove(0);
+    }
+    stack.push(location);
+}
+
+/// Matches an endpoint against a lowercase search query. Every whitespace
+/// separated term must match: `scope:<name>` and `auth:<scheme>` (or
+/// `auth:none`) filter on security requirements, `x-<name>` or
+/// `x-<name>=<value>` on extensions (`!` in front excludes instead), anything
+/// else on the path.
+fn matches_query(endpoint: &Endpoint, query: &str) -> bool {
+    query.split_whitespace().all(|term| {
+        if let Some(scope) = term.strip_prefix("scope:") {
+            endpoint.requires_scope(scope)
+        } else if let Some(scheme) = term.strip_prefix("auth:") {
+            if scheme == "none" {
+                endpoint.allows_anonymous()
+            } else {
+                endpoint.uses_scheme(scheme)
+            }
+        } else if term.starts_with("x-") || term.starts_with("!x-") {
+            let (negate, term) = match term.strip_prefix('!') {
+                Some(term) => (true, term),
+                None => (false, term),
+            };
+            let (name, value) = match term.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (term, None),
+            };
+            endpoint.has_extension(name, value) != negate
+        } else {
+            endpoint.path.to_lowercase().contains(term)
+        }
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{Endpoint, HttpMethod};
+    use crate::model::{Endpoint, HttpMethod, SchemeRequirement, ServerVariable};
     use std::collections::BTreeMap;
 
     fn create_test_spec(endpoint_count: usize) -> ApiSpec {
@@ -143,6 +1215,7 @@ mod tests {
                 parameters: vec![],
                 request_body: None,
                 responses: BTreeMap::new(),
+                ..Default::default()
             })
             .collect();
 
@@ -151,6 +1224,7 @@ mod tests {
             version: "1.0.0".to_string(),
             description: None,
             endpoints,
+            ..Default::default()
         }
     }
 
@@ -268,6 +1342,30 @@ mod tests {
         assert_eq!(app.detail_scroll, 0); // should not underflow
     }
 
+    #[test]
+    fn test_scroll_down_stops_at_max() {
+        let spec = create_test_spec(1);
+        let mut app = App::new(spec);
+
+        app.set_detail_max_scroll(1);
+        app.scroll_down();
+        app.scroll_down();
+        assert_eq!(app.detail_scroll, 1);
+    }
+
+    #[test]
+    fn test_set_detail_max_scroll_clamps_offset() {
+        let spec = create_test_spec(1);
+        let mut app = App::new(spec);
+
+        app.detail_scroll = 30;
+        app.set_detail_max_scroll(12);
+        assert_eq!(app.detail_scroll, 12);
+
+        app.set_detail_max_scroll(40);
+        assert_eq!(app.detail_scroll, 12);
+    }
+
     #[test]
     fn test_select_resets_scroll() {
         let spec = create_test_spec(3);
@@ -329,6 +1427,7 @@ mod tests {
             parameters: vec![],
             request_body: None,
             responses: BTreeMap::new(),
+            ..Default::default()
         }
     }
 
@@ -343,6 +1442,7 @@ mod tests {
                 create_endpoint_with_path("/users/{id}"),
                 create_endpoint_with_path("/posts"),
             ],
+            ..Default::default()
         };
         let mut app = App::new(spec);
 
@@ -355,6 +1455,581 @@ mod tests {
         assert_eq!(app.filtered_indices, vec![0, 1]);
     }
 
+    #[test]
+    fn test_search_filters_by_security() {
+        let secured = |path: &str, scheme: &str, scopes: &[&str]| Endpoint {
+            security: vec![vec![SchemeRequirement {
+                scheme: scheme.to_string(),
+                scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            }]],
+            ..create_endpoint_with_path(path)
+        };
+        let spec = ApiSpec {
+            endpoints: vec![
+                secured("/admin/users", "oauth", &["admin:write"]),
+                secured("/users", "oauth", &["users:read"]),
+                secured("/admin/stats", "api_key", &[]),
+                create_endpoint_with_path("/health"),
+            ],
+            ..Default::default()
+        };
+        let mut app = App::new(spec);
+
+        for c in "scope:admin:write".chars() {
+            app.search_push_char(c);
+        }
+        assert_eq!(app.filtered_indices, vec![0]);
+
+        app.clear_search();
+        for c in "auth:oauth".chars() {
+            app.search_push_char(c);
+        }
+        assert_eq!(app.filtered_indices, vec![0, 1]);
+
+        app.clear_search();
+        for c in "auth:none".chars() {
+            app.search_push_char(c);
+        }
+        assert_eq!(app.filtered_indices, vec![3]);
+
+        app.clear_search();
+        for c in "admin auth:api_key".chars() {
+            app.search_push_char(c);
+        }
+        assert_eq!(app.filtered_indices, vec![2]);
+    }
+
+    #[test]
+    fn test_search_filters_by_extension() {
+        let spec = crate::parser::parse_file("tests/fixtures/extensions.yaml").unwrap();
+        let mut app = App::new(spec);
+        let mut search = |query: &str| {
+            app.clear_search();
+            for c in query.chars() {
+                app.search_push_char(c);
+            }
+            app.filtered_indices.clone()
+        };
+
+        assert_eq!(search("x-internal"), vec![0, 2]);
+        assert_eq!(search("x-internal=true"), vec![2]);
+        assert_eq!(search("!x-internal"), vec![1]);
+        assert_eq!(search("!x-internal=true"), vec![0, 1]);
+        // Path-level extensions count for every operation of the path
+        assert_eq!(search("x-gateway=EDGE"), vec![1, 2]);
+        assert_eq!(search("x-rate-limit users"), vec![1]);
+    }
+
+    #[test]
+    fn test_go_to_definition_and_back() {
+        let spec = crate::parser::parse_file("tests/fixtures/components.yaml").unwrap();
+        let mut app = App::new(spec);
+        let names = |app: &App| -> Vec<String> {
+            app.definition_targets()
+                .iter()
+                .map(|&i| app.spec.components[i].name.clone())
+                .collect()
+        };
+
+        app.select_next();
+        app.detail_scroll = 3;
+        assert_eq!(names(&app), vec!["Pet", "NotFound", "Error"]);
+
+        app.open_definitions();
+        app.popup_confirm();
+        assert_eq!(app.definition, Some(0));
+        assert_eq!(app.detail_scroll, 0);
+        assert_eq!(app.focus, Focus::Detail);
+        // References are now those of the opened component
+        assert_eq!(names(&app), vec!["Owner", "Pet"]);
+
+        app.open_definition(1);
+        app.jump_back();
+        assert_eq!(app.definition, Some(0));
+        app.jump_back();
+        assert_eq!(app.definition, None);
+        assert_eq!(app.selected_index, 1);
+        assert_eq!(app.detail_scroll, 3);
+        assert_eq!(app.focus, Focus::List);
+        // Nothing further back
+        app.jump_back();
+        assert_eq!(app.selected_index, 1);
+
+        app.jump_forward();
+        assert_eq!(app.definition, Some(0));
+        app.jump_forward();
+        assert_eq!(app.definition, Some(1));
+
+        // Selecting another endpoint leaves the definition
+        app.select_previous();
+        assert_eq!(app.definition, None);
+    }
+
+    #[test]
+    fn test_jump_back_restores_selection_before_following_link() {
+        let spec = crate::parser::parse_file("tests/fixtures/responses.yaml").unwrap();
+        let mut app = App::new(spec);
+        let create = app
+            .spec
+            .endpoints
+            .iter()
+            .position(|e| e.method == HttpMethod::Post)
+            .unwrap();
+        app.jump_to_endpoint(create);
+
+        app.open_links();
+        app.popup_confirm();
+        assert_ne!(app.selected_index, create);
+
+        app.jump_back();
+        assert_eq!(app.selected_index, create);
+    }
+
+    #[test]
+    fn test_snippet_popup() {
+        let spec = crate::parser::parse_file("tests/fixtures/snippets.yaml").unwrap();
+        let mut app = App::new(spec);
+        let create = app
+            .spec
+            .endpoints
+            .iter()
+            .position(|e| e.path == "/pets" && e.method == HttpMethod::Post)
+            .unwrap();
+        app.jump_to_endpoint(create);
+
+        let request = app.selected_request().unwrap();
+        assert_eq!(request.url, "https://api.example.com/v1/pets");
+
+        app.open_snippet();
+        assert_eq!(app.popup, Some(Popup::Snippet { scroll: 0 }));
+        // The command has seven lines, so scrolling stops at the last one
+        for _ in 0..10 {
+            app.popup_next();
+        }
+        assert_eq!(app.popup, Some(Popup::Snippet { scroll: 6 }));
+        app.popup_previous();
+        assert_eq!(app.popup, Some(Popup::Snippet { scroll: 5 }));
+
+        // Switching languages starts at the top again
+        app.popup_cycle(false);
+        assert_eq!(app.snippet_language, Language::Rust);
+        assert_eq!(app.popup, Some(Popup::Snippet { scroll: 0 }));
+        assert!(app
+            .snippet()
+            .unwrap()
+            .contains("reqwest::blocking::Client::new()"));
+
+        app.popup_confirm();
+        assert_eq!(app.popup, None);
+    }
+
+    #[test]
+    fn test_copy_items() {
+        let spec = crate::parser::parse_file("tests/fixtures/snippets.yaml").unwrap();
+        let mut app = App::new(spec);
+        let create = app
+            .spec
+            .endpoints
+            .iter()
+            .position(|e| e.path == "/pets" && e.method == HttpMethod::Post)
+            .unwrap();
+        app.jump_to_endpoint(create);
+
+        let items = app.copy_items();
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Path",
+                "Method and path",
+                "curl snippet",
+                "Request example",
+                "Request schema"
+            ]
+        );
+        assert_eq!(items[1].text, "POST /pets");
+        assert_eq!(
+            items[3].text,
+            "{\n  \"name\": \"Rex\",\n  \"tag\": \"dog\"\n}"
+        );
+        // The $ref to the Pet schema is resolved
+        let schema: serde_json::Value = serde_json::from_str(&items[4].text).unwrap();
+        assert_eq!(schema["required"], serde_json::json!(["name"]));
+
+        app.open_copy();
+        app.popup_previous();
+        app.popup_confirm();
+        assert_eq!(app.popup, None);
+        assert_eq!(app.pending_copy.as_ref(), Some(&items[4]));
+
+        let item = app.pending_copy.take().unwrap();
+        app.copied(&item, Ok(Backend::Osc52));
+        assert_eq!(
+            app.status,
+            Some(StatusMessage {
+                text: "Copied request schema (OSC 52)".to_string(),
+                error: false,
+            })
+        );
+        app.copied(&item, Err(anyhow::anyhow!("xclip exited with 1")));
+        assert_eq!(
+            app.status.as_ref().map(|s| (s.text.as_str(), s.error)),
+            Some(("Copy failed: xclip exited with 1", true))
+        );
+    }
+
+    #[test]
+    fn test_copy_snippet_from_popup() {
+        let spec = crate::parser::parse_file("tests/fixtures/snippets.yaml").unwrap();
+        let mut app = App::new(spec);
+        app.open_snippet();
+        app.popup_cycle(true);
+        app.copy_snippet();
+
+        let item = app.pending_copy.unwrap();
+        assert_eq!(item.label, "HTTPie snippet");
+        assert!(item.text.starts_with("http "));
+        // Copying leaves the snippet open
+        assert!(matches!(app.popup, Some(Popup::Snippet { .. })));
+    }
+
+    #[test]
+    fn test_request_builder() {
+        let spec = crate::parser::parse_file("tests/fixtures/builder.yaml").unwrap();
+        let mut app = App::new(spec);
+        app.open_request_builder();
+        assert_eq!(app.screen, Screen::Request);
+        assert!(app.text_input());
+
+        let form = app.request_form.as_mut().unwrap();
+        form.pop_char();
+        form.push_char('5');
+        assert!(app.snippet().unwrap().contains("/pets?limit=25'"));
+
+        // Values survive closing and reopening the builder
+        app.close_request_builder();
+        assert_eq!(app.screen, Screen::Browse);
+        app.open_request_builder();
+        assert_eq!(app.selected_request().unwrap().query[0].1, "25");
+
+        // Other endpoints get a request generated from the spec
+        app.close_request_builder();
+        app.select_next();
+        assert!(app.selected_form().is_none());
+        assert_eq!(app.selected_request().unwrap().url, "/pets/<petId>");
+        app.open_request_builder();
+        assert_eq!(app.request_form.as_ref().unwrap().endpoint, 1);
+    }
+
+    #[test]
+    fn test_send_request_needs_values() {
+        let spec = crate::parser::parse_file("tests/fixtures/snippets.yaml").unwrap();
+        let mut app = App::new(spec);
+        let replace = app
+            .spec
+            .endpoints
+            .iter()
+            .position(|e| e.path == "/pets/{petId}")
+            .unwrap();
+
+        app.jump_to_endpoint(replace);
+        app.send_request();
+        assert_eq!(app.pending_send, None);
+        assert_eq!(
+            app.status.as_ref().unwrap().text,
+            "Not sent: <petId> has no value"
+        );
+
+        // Placeholder credentials don't go out either
+        app.open_request_builder();
+        app.request_form.as_mut().unwrap().push_char('7');
+        app.send_request();
+        assert_eq!(app.pending_send, None);
+        assert_eq!(
+            app.status.as_ref().unwrap().text,
+            "Not sent: <api-key> has no value"
+        );
+    }
+
+    #[test]
+    fn test_send_request_and_history() {
+        let spec = crate::parser::parse_file("tests/fixtures/builder.yaml").unwrap();
+        let mut app = App::new(spec);
+        app.open_history();
+        assert_eq!(app.popup, None);
+        assert_eq!(app.status.as_ref().unwrap().text, "No requests sent yet");
+
+        // Problems found by the request builder keep the request from going out
+        app.open_request_builder();
+        app.request_form.as_mut().unwrap().pop_char();
+        app.request_form.as_mut().unwrap().pop_char();
+        app.send_request();
+        assert_eq!(app.pending_send, None);
+        assert_eq!(
+            app.status.as_ref().unwrap().text,
+            "Not sent: limit is required"
+        );
+
+        app.request_form.as_mut().unwrap().push_char('5');
+        app.send_request();
+        let (endpoint, request) = app.pending_send.take().unwrap();
+        assert_eq!(
+            (endpoint, request.full_url().as_str()),
+            (0, "/pets?limit=5")
+        );
+
+        let response = Response {
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: vec![],
+            body: "[]".to_string(),
+            elapsed: std::time::Duration::from_millis(12),
+        };
+        app.received(endpoint, request.clone(), Ok(response));
+        app.received(
+            endpoint,
+            request,
+            Err(anyhow::anyhow!("Connection refused")),
+        );
+        assert_eq!(app.screen, Screen::Response);
+        assert_eq!(app.response_index, 1);
+        assert_eq!(
+            app.history[1].response,
+            Err("Connection refused".to_string())
+        );
+        // Only responses are checked against the spec
+        assert!(app.history[0].validation.as_ref().unwrap().is_valid());
+        assert_eq!(app.history[1].validation, None);
+
+        // Sending again queues the exact same request
+        app.resend();
+        assert_eq!(app.pending_send.as_ref().unwrap().1, app.history[1].request);
+
+        // The history lists the most recent request first
+        app.open_history();
+        app.popup_next();
+        app.popup_confirm();
+        assert_eq!(app.response_index, 0);
+        assert_eq!(app.response_scroll, 0);
+    }
+
+    #[test]
+    fn test_configured_credentials() {
+        let spec = crate::parser::parse_file("tests/fixtures/security.yaml").unwrap();
+        let config = Config {
+            auth: BTreeMap::from([(
+                "api_key".to_string(),
+                crate::auth::Credentials {
+                    api_key: Some("k-123".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let mut app = App::with_config(spec, &config, Theme::dark());
+        let list = app
+            .spec
+            .endpoints
+            .iter()
+            .position(|e| e.path == "/pets" && e.method == crate::model::HttpMethod::Get)
+            .unwrap();
+        app.selected_index = list;
+        let request = app.selected_request().unwrap();
+        assert_eq!(
+            request.headers,
+            vec![("X-API-Key".to_string(), "k-123".to_string())]
+        );
+        assert!(app.snippet().unwrap().contains("X-API-Key: ••••••"));
+        assert!(app.token_requests(list).is_empty());
+    }
+
+    #[test]
+    fn test_configured_credentials_are_masked() {
+        let spec = crate::parser::parse_file("tests/fixtures/snippets.yaml").unwrap();
+        let config = Config {
+            auth: BTreeMap::from([(
+                "bearerAuth".to_string(),
+                crate::auth::Credentials {
+                    token: Some("tok-123".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let mut app = App::with_config(spec, &config, Theme::dark());
+        app.selected_index = app
+            .spec
+            .endpoints
+            .iter()
+            .position(|e| e.path == "/pets" && e.method == crate::model::HttpMethod::Get)
+            .unwrap();
+
+        let request = app.selected_request().unwrap();
+        assert!(request
+            .headers
+            .contains(&("Authorization".to_string(), "Bearer tok-123".to_string())));
+        let snippet = app.snippet().unwrap();
+        assert!(snippet.contains("Authorization: Bearer ••••••"));
+        assert!(!snippet.contains("tok-123"));
+        assert!(app
+            .copy_items()
+            .iter()
+            .all(|item| !item.text.contains("tok-123")));
+    }
+
+    #[test]
+    fn test_environments() {
+        let spec = crate::parser::parse_file("tests/fixtures/builder.yaml").unwrap();
+        let mut app = App::new(spec);
+        app.open_environments();
+        assert_eq!(app.popup, None);
+        assert_eq!(
+            app.status.as_ref().unwrap().text,
+            "No environments configured"
+        );
+
+        app.environments = vec![Environment {
+            name: "staging".to_string(),
+            base_url: Some("https://{{host}}/v1".to_string()),
+            variables: BTreeMap::from([
+                ("host".to_string(), "staging.example.com".to_string()),
+                ("trace".to_string(), "t0k3n-123".to_string()),
+            ]),
+            secrets: vec!["trace".to_string()],
+        }];
+        app.open_environments();
+        app.popup_next();
+        app.popup_confirm();
+        assert_eq!(app.active_environment, Some(0));
+        assert_eq!(app.status.as_ref().unwrap().text, "Environment: staging");
+
+        app.open_request_builder();
+        let form = app.request_form.as_mut().unwrap();
+        form.selected = 3;
+        "{{trace}}".chars().for_each(|c| form.push_char(c));
+        app.send_request();
+        let (_, request) = app.pending_send.take().unwrap();
+        assert_eq!(
+            request.full_url(),
+            "https://staging.example.com/v1/pets?limit=20"
+        );
+        assert_eq!(
+            request.headers,
+            vec![("X-Trace".to_string(), "t0k3n-123".to_string())]
+        );
+
+        // Secrets never make it into what is shown or copied
+        let snippet = app.snippet().unwrap();
+        assert!(snippet.contains("X-Trace: ••••••"));
+        assert!(!snippet.contains("t0k3n-123"));
+        assert!(app
+            .copy_items()
+            .iter()
+            .all(|item| !item.text.contains("t0k3n-123")));
+
+        app.open_environments();
+        app.popup_previous();
+        app.popup_confirm();
+        assert_eq!(app.active_environment, None);
+        assert_eq!(app.status.as_ref().unwrap().text, "No environment");
+    }
+
+    #[test]
+    fn test_toggle_hide_deprecated() {
+        let deprecated = |path: &str| Endpoint {
+            deprecated: true,
+            ..create_endpoint_with_path(path)
+        };
+        let spec = ApiSpec {
+            endpoints: vec![
+                deprecated("/v1/users"),
+                create_endpoint_with_path("/v2/users"),
+                deprecated("/v1/posts"),
+                create_endpoint_with_path("/v2/posts"),
+            ],
+            ..Default::default()
+        };
+        let mut app = App::new(spec);
+        app.selected_index = 3;
+
+        app.toggle_hide_deprecated();
+        assert!(app.hide_deprecated);
+        assert_eq!(app.filtered_indices, vec![1, 3]);
+        assert_eq!(app.selected_endpoint().unwrap().path, "/v2/posts");
+
+        // Combines with the search query
+        for c in "users".chars() {
+            app.search_push_char(c);
+        }
+        assert_eq!(app.filtered_indices, vec![1]);
+
+        app.toggle_hide_deprecated();
+        assert_eq!(app.filtered_indices, vec![0, 1]);
+        assert_eq!(app.selected_endpoint().unwrap().path, "/v2/users");
+    }
+
+    #[test]
+    fn test_follow_link_jumps_to_operation() {
+        let spec = crate::parser::parse_file("tests/fixtures/responses.yaml").unwrap();
+        let mut app = App::new(spec);
+
+        // GET /reports/{id} has no links
+        app.selected_index = 1;
+        app.open_links();
+        assert_eq!(app.popup, None);
+
+        // POST /reports links to three operations
+        app.selected_index = 0;
+        app.open_links();
+        assert_eq!(app.popup, Some(Popup::Links { selected: 0 }));
+        assert_eq!(app.selected_links().len(), 3);
+
+        app.popup_previous();
+        assert_eq!(app.popup, Some(Popup::Links { selected: 2 }));
+        app.popup_next();
+        assert_eq!(app.popup, Some(Popup::Links { selected: 0 }));
+
+        app.popup_confirm();
+        assert_eq!(app.popup, None);
+        let ep = app.selected_endpoint().unwrap();
+        assert_eq!(ep.method, HttpMethod::Get);
+        assert_eq!(ep.path, "/reports/{id}");
+    }
+
+    #[test]
+    fn test_jump_to_endpoint_clears_hiding_search() {
+        let spec = crate::parser::parse_file("tests/fixtures/responses.yaml").unwrap();
+        let mut app = App::new(spec);
+
+        for c in "download".chars() {
+            app.search_push_char(c);
+        }
+        assert_eq!(app.filtered_indices, vec![3]);
+
+        app.jump_to_endpoint(2);
+        assert!(app.search_query.is_empty());
+        assert_eq!(app.selected_endpoint().unwrap().method, HttpMethod::Delete);
+    }
+
+    #[test]
+    fn test_cycle_examples() {
+        let spec = crate::parser::parse_file("tests/fixtures/examples.yaml").unwrap();
+        let mut app = App::new(spec);
+
+        // The response has four examples, more than any other group
+        app.next_example();
+        assert_eq!(app.example_index, 1);
+        app.previous_example();
+        app.previous_example();
+        assert_eq!(app.example_index, 3);
+        app.next_example();
+        assert_eq!(app.example_index, 0);
+
+        app.next_example();
+        app.select_next();
+        assert_eq!(app.example_index, 0);
+    }
+
     #[test]
     fn test_search_case_insensitive() {
         let spec = ApiSpec {
@@ -365,6 +2040,7 @@ mod tests {
                 create_endpoint_with_path("/Users"),
                 create_endpoint_with_path("/ADMIN"),
             ],
+            ..Default::default()
         };
         let mut app = App::new(spec);
 
@@ -386,6 +2062,7 @@ mod tests {
                 create_endpoint_with_path("/b"),
                 create_endpoint_with_path("/c"),
             ],
+            ..Default::default()
         };
         let mut app = App::new(spec);
 
@@ -407,6 +2084,7 @@ mod tests {
                 create_endpoint_with_path("/bbb"),
                 create_endpoint_with_path("/ccc"),
             ],
+            ..Default::default()
         };
         let mut app = App::new(spec);
 
@@ -427,6 +2105,7 @@ mod tests {
                 create_endpoint_with_path("/b"),
                 create_endpoint_with_path("/ab"),
             ],
+            ..Default::default()
         };
         let mut app = App::new(spec);
 
@@ -453,6 +2132,279 @@ mod tests {
         assert_eq!(app.search_query, "a");
     }
 
+    #[test]
+    fn test_grow_and_shrink_list() {
+        let spec = create_test_spec(1);
+        let mut app = App::new(spec);
+
+        assert_eq!(app.split_percent, 40);
+        app.grow_list();
+        assert_eq!(app.split_percent, 45);
+        app.shrink_list();
+        app.shrink_list();
+        assert_eq!(app.split_percent, 35);
+    }
+
+    #[test]
+    fn test_split_percent_is_bounded() {
+        let spec = create_test_spec(1);
+        let mut app = App::new(spec);
+
+        for _ in 0..20 {
+            app.grow_list();
+        }
+        assert_eq!(app.split_percent, MAX_SPLIT_PERCENT);
+
+        for _ in 0..20 {
+            app.shrink_list();
+        }
+        assert_eq!(app.split_percent, MIN_SPLIT_PERCENT);
+    }
+
+    #[test]
+    fn test_with_config_clamps_split_percent() {
+        let mut config = Config::default();
+        config.layout.split_percent = 95;
+        config.layout.stack_below = 60;
+        let app = App::with_config(create_test_spec(1), &config, Theme::dark());
+
+        assert_eq!(app.split_percent, MAX_SPLIT_PERCENT);
+        assert_eq!(app.stack_below, 60);
+    }
+
+    #[test]
+    fn test_toggle_zoom() {
+        let spec = create_test_spec(1);
+        let mut app = App::new(spec);
+
+        assert!(!app.zoomed);
+        app.toggle_zoom();
+        assert!(app.zoomed);
+        app.toggle_zoom();
+        assert!(!app.zoomed);
+    }
+
+    #[test]
+    fn test_toggle_overview() {
+        let spec = create_test_spec(1);
+        let mut app = App::new(spec);
+
+        assert_eq!(app.screen, Screen::Browse);
+        app.toggle_overview();
+        assert_eq!(app.screen, Screen::Overview);
+        app.overview_scroll_down();
+        assert_eq!(app.overview_scroll, 1);
+        app.toggle_overview();
+        assert_eq!(app.screen, Screen::Browse);
+
+        app.toggle_overview();
+        assert_eq!(app.overview_scroll, 0);
+        app.close_overview();
+        assert_eq!(app.screen, Screen::Browse);
+    }
+
+    #[test]
+    fn test_overview_on_startup() {
+        let config = Config {
+            overview_on_startup: true,
+            ..Default::default()
+        };
+        let app = App::with_config(create_test_spec(1), &config, Theme::dark());
+
+        assert_eq!(app.screen, Screen::Overview);
+    }
+
+    fn create_server_spec() -> ApiSpec {
+        ApiSpec {
+            servers: vec![
+                Server {
+                    url: "https://{env}.example.com/{version}".to_string(),
+                    description: None,
+                    variables: vec![
+                        ServerVariable {
+                            name: "env".to_string(),
+                            default: "api".to_string(),
+                            enum_values: vec!["api".to_string(), "staging".to_string()],
+                            description: None,
+                        },
+                        ServerVariable {
+                            name: "version".to_string(),
+                            default: "v1".to_string(),
+                            enum_values: vec![],
+                            description: None,
+                        },
+                    ],
+                },
+                Server {
+                    url: "http://localhost:8080/".to_string(),
+                    ..Default::default()
+                },
+            ],
+            endpoints: vec![create_endpoint_with_path("/users")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_endpoint_url_uses_active_server() {
+        let mut app = App::new(create_server_spec());
+        let endpoint = app.spec.endpoints[0].clone();
+
+        assert_eq!(
+            app.endpoint_url(&endpoint),
+            "https://api.example.com/v1/users"
+        );
+
+        app.open_server_picker();
+        app.popup_next();
+        app.popup_confirm();
+        assert_eq!(app.active_server, 1);
+        assert!(app.popup.is_none());
+        assert_eq!(app.endpoint_url(&endpoint), "http://localhost:8080/users");
+    }
+
+    #[test]
+    fn test_endpoint_url_without_servers() {
+        let app = App::new(create_test_spec(1));
+        let endpoint = app.spec.endpoints[0].clone();
+        assert_eq!(app.endpoint_url(&endpoint), "/test/0");
+    }
+
+    #[test]
+    fn test_endpoint_url_prefers_endpoint_servers() {
+        let mut spec = create_server_spec();
+        spec.endpoints[0].servers = vec![
+            Server {
+                url: "https://files.example.com".to_string(),
+                ..Default::default()
+            },
+            Server {
+                url: "https://{region}.files.example.com".to_string(),
+                variables: vec![ServerVariable {
+                    name: "region".to_string(),
+                    default: "eu".to_string(),
+                    enum_values: vec![],
+                    description: None,
+                }],
+                ..Default::default()
+            },
+        ];
+        let mut app = App::new(spec);
+        let endpoint = app.spec.endpoints[0].clone();
+
+        assert_eq!(
+            app.endpoint_url(&endpoint),
+            "https://files.example.com/users"
+        );
+
+        // The picker and variable form work on the endpoint's own servers
+        app.open_server_picker();
+        app.popup_next();
+        app.edit_server_variables();
+        app.form_pop_char();
+        app.form_pop_char();
+        "us".chars().for_each(|c| app.form_push_char(c));
+        app.popup_confirm();
+        assert_eq!(app.active_server, 0);
+        assert_eq!(
+            app.endpoint_url(&endpoint),
+            "https://us.files.example.com/users"
+        );
+
+        // An active environment's base URL wins over any server
+        app.environments = vec![Environment {
+            name: "local".to_string(),
+            base_url: Some("http://localhost:3000".to_string()),
+            ..Default::default()
+        }];
+        app.active_environment = Some(0);
+        assert_eq!(app.endpoint_url(&endpoint), "http://localhost:3000/users");
+    }
+
+    #[test]
+    fn test_endpoint_url_ignores_servers_for_callbacks() {
+        let mut spec = create_server_spec();
+        spec.endpoints[0].kind = EndpointKind::Webhook;
+        spec.endpoints[0].path = "newUser".to_string();
+        let app = App::new(spec);
+        let endpoint = app.spec.endpoints[0].clone();
+
+        assert_eq!(app.server_url(&endpoint), None);
+        assert_eq!(app.endpoint_url(&endpoint), "newUser");
+    }
+
+    #[test]
+    fn test_server_picker_wraps() {
+        let mut app = App::new(create_server_spec());
+
+        app.open_server_picker();
+        app.popup_previous();
+        assert_eq!(app.popup, Some(Popup::ServerPicker { selected: 1 }));
+        app.popup_next();
+        assert_eq!(app.popup, Some(Popup::ServerPicker { selected: 0 }));
+        app.close_popup();
+        assert!(app.popup.is_none());
+    }
+
+    #[test]
+    fn test_server_picker_requires_servers() {
+        let mut app = App::new(create_test_spec(1));
+        app.open_server_picker();
+        assert!(app.popup.is_none());
+    }
+
+    #[test]
+    fn test_edit_server_variables() {
+        let mut app = App::new(create_server_spec());
+        let endpoint = app.spec.endpoints[0].clone();
+
+        app.open_server_picker();
+        app.edit_server_variables();
+        assert!(app.text_input());
+
+        // Enum variable: typing is ignored, options are cycled
+        app.form_push_char('x');
+        app.popup_cycle(true);
+
+        // Free-text variable
+        app.popup_next();
+        app.form_pop_char();
+        app.form_push_char('2');
+        app.popup_confirm();
+
+        assert!(app.popup.is_none());
+        assert!(!app.text_input());
+        assert_eq!(
+            app.endpoint_url(&endpoint),
+            "https://staging.example.com/v2/users"
+        );
+    }
+
+    #[test]
+    fn test_edit_server_variables_needs_variables() {
+        let mut app = App::new(create_server_spec());
+
+        app.open_server_picker();
+        app.popup_next();
+        app.edit_server_variables();
+        assert_eq!(app.popup, Some(Popup::ServerPicker { selected: 1 }));
+    }
+
+    #[test]
+    fn test_form_field_cycle_option() {
+        let mut field = FormField {
+            name: "env".to_string(),
+            value: "b".to_string(),
+            options: vec!["a".to_string(), "b".to_string()],
+            description: None,
+        };
+
+        field.cycle_option(true);
+        assert_eq!(field.value, "a");
+        field.cycle_option(false);
+        assert_eq!(field.value, "b");
+    }
+
     #[test]
     fn test_clear_search_shows_all() {
         let spec = ApiSpec {
@@ -463,6 +2415,7 @@ mod tests {
                 create_endpoint_with_path("/a"),
                 create_endpoint_with_path("/b"),
             ],
+            ..Default::default()
         };
         let mut app = App::new(spec);
 
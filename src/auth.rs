@@ -0,0 +1,562 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::environment::{self, Environment};
+use crate::model::{
+    ApiSpec, Endpoint, OAuthFlowKind, ParameterLocation, SchemeRequirement, SecurityScheme,
+    SecuritySchemeKind,
+};
+use crate::snippet::Request;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+/// Tokens are fetched again this long before they expire, so that they don't
+/// run out on the way to the server.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// Credentials for a security scheme, as written under `[auth.<scheme>]`.
+/// Which fields are used depends on the scheme's type.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Credentials {
+    /// For `apiKey` schemes.
+    pub api_key: Option<String>,
+    /// For HTTP `basic` schemes.
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// For other HTTP schemes like `bearer`, or a fixed OAuth2 or OpenID
+    /// Connect access token.
+    pub token: Option<String>,
+    /// For OAuth2 client credentials flows.
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// Scopes to ask for instead of the ones the operation requires.
+    pub scopes: Option<Vec<String>>,
+}
+
+/// What to send to an OAuth2 token endpoint for a client credentials grant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenRequest {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub scopes: Vec<String>,
+}
+
+impl TokenRequest {
+    /// Tokens are only reused for the same client asking for the same scopes.
+    fn key(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.token_url,
+            self.client_id,
+            self.scopes.join(" ")
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    access_token: String,
+    /// `None` when the token endpoint didn't say.
+    expires_at: Option<Instant>,
+}
+
+impl Token {
+    fn is_fresh(&self) -> bool {
+        self.expires_at
+            .is_none_or(|at| Instant::now() + EXPIRY_MARGIN < at)
+    }
+}
+
+/// The configured credentials, and the OAuth2 tokens fetched with them. Clones
+/// share the tokens, so that requests sent on other threads can fetch them.
+#[derive(Debug, Clone, Default)]
+pub struct Auth {
+    credentials: BTreeMap<String, Credentials>,
+    tokens: Arc<Mutex<BTreeMap<String, Token>>>,
+}
+
+impl Auth {
+    pub fn new(credentials: BTreeMap<String, Credentials>) -> Self {
+        Self {
+            credentials,
+            tokens: Arc::default(),
+        }
+    }
+
+    /// Replaces the placeholder credentials `Request` puts in for the
+    /// endpoint's first security requirement with the configured ones, with
+    /// the environment's variables substituted. OAuth2 client credentials
+    /// flows use the last token fetched while it is fresh.
+    pub fn apply(
+        &self,
+        request: &mut Request,
+        endpoint: &Endpoint,
+        spec: &ApiSpec,
+        environment: Option<&Environment>,
+    ) {
+        for (scheme, needed, credentials) in self.schemes(endpoint, spec) {
+            match &scheme.kind {
+                SecuritySchemeKind::ApiKey { name, location } => {
+                    let Some(key) = &credentials.api_key else {
+                        continue;
+                    };
+                    let entries = match location {
+                        ParameterLocation::Query => &mut request.query,
+                        ParameterLocation::Cookie => &mut request.cookies,
+                        ParameterLocation::Header | ParameterLocation::Path => &mut request.headers,
+                    };
+                    set(entries, name, environment::resolve(key, environment));
+                }
+                SecuritySchemeKind::Http { scheme, .. } if scheme.eq_ignore_ascii_case("basic") => {
+                    if let Some(username) = &credentials.username {
+                        let password = credentials.password.as_deref().unwrap_or_default();
+                        request.basic_auth = Some((
+                            environment::resolve(username, environment),
+                            environment::resolve(password, environment),
+                        ));
+                    }
+                }
+                SecuritySchemeKind::Http { scheme, .. } => {
+                    if let Some(token) = &credentials.token {
+                        let scheme = if scheme.eq_ignore_ascii_case("bearer") {
+                            "Bearer"
+                        } else {
+                            scheme
+                        };
+                        let token = environment::resolve(token, environment);
+                        let value = format!("{} {}", scheme, token);
+                        set(&mut request.headers, "Authorization", value);
+                    }
+                }
+                SecuritySchemeKind::OAuth2 { .. } | SecuritySchemeKind::OpenIdConnect { .. } => {
+                    let token = match &credentials.token {
+                        Some(token) => Some(environment::resolve(token, environment)),
+                        None => token_request(scheme, needed, credentials, environment)
+                            .and_then(|request| self.cached(&request)),
+                    };
+                    if let Some(token) = token {
+                        set(&mut request.headers, "Authorization", bearer(&token));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Tokens the endpoint's request needs that haven't been fetched yet, or
+    /// have expired.
+    pub fn token_requests(
+        &self,
+        endpoint: &Endpoint,
+        spec: &ApiSpec,
+        environment: Option<&Environment>,
+    ) -> Vec<TokenRequest> {
+        self.schemes(endpoint, spec)
+            .into_iter()
+            .filter(|(_, _, credentials)| credentials.token.is_none())
+            .filter_map(|(scheme, needed, credentials)| {
+                token_request(scheme, needed, credentials, environment)
+            })
+            .filter(|request| self.cached(request).is_none())
+            .collect()
+    }
+
+    /// Fetches the tokens of `token_requests` and puts them in the request's
+    /// `Authorization` header.
+    pub fn authorize(&self, request: &mut Request, token_requests: &[TokenRequest]) -> Result<()> {
+        for token_request in token_requests {
+            let token = self.fetch(token_request)?;
+            set(&mut request.headers, "Authorization", bearer(&token));
+        }
+        Ok(())
+    }
+
+    /// Gets a token from the token endpoint and keeps it for later requests.
+    pub fn fetch(&self, request: &TokenRequest) -> Result<String> {
+        let token = fetch_token(request)?;
+        let access_token = token.access_token.clone();
+        self.lock().insert(request.key(), token);
+        Ok(access_token)
+    }
+
+    /// The configured secrets, as given and with the environment's variables
+    /// substituted, and every token fetched so far.
+    pub fn secrets(&self, environment: Option<&Environment>) -> Vec<String> {
+        let mut secrets: Vec<String> = self
+            .credentials
+            .values()
+            .flat_map(|credentials| {
+                [
+                    &credentials.api_key,
+                    &credentials.password,
+                    &credentials.token,
+                    &credentials.client_secret,
+                ]
+            })
+            .flatten()
+            .flat_map(|value| {
+                let resolved = environment.map(|environment| environment.substitute(value));
+                std::iter::once(value.clone()).chain(resolved)
+            })
+            .collect();
+        secrets.extend(self.lock().values().map(|token| token.access_token.clone()));
+        secrets
+    }
+
+    fn cached(&self, request: &TokenRequest) -> Option<String> {
+        self.lock()
+            .get(&request.key())
+            .filter(|token| token.is_fresh())
+            .map(|token| token.access_token.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Token>> {
+        // A thread panicking while holding the lock can't leave a half-written token
+        self.tokens.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// The declared schemes of the endpoint's first security requirement that
+    /// have credentials configured.
+    fn schemes<'a>(
+        &'a self,
+        endpoint: &'a Endpoint,
+        spec: &'a ApiSpec,
+    ) -> Vec<(&'a SecurityScheme, &'a SchemeRequirement, &'a Credentials)> {
+        let Some(requirement) = endpoint.security.first() else {
+            return Vec::new();
+        };
+        requirement
+            .iter()
+            .filter_map(|needed| {
+                let scheme = spec
+                    .security_schemes
+                    .iter()
+                    .find(|s| s.name == needed.scheme)?;
+                let credentials = self.credentials.get(&needed.scheme)?;
+                Some((scheme, needed, credentials))
+            })
+            .collect()
+    }
+}
+
+/// The token request for a scheme with a client credentials flow, when a
+/// client ID is configured for it.
+fn token_request(
+    scheme: &SecurityScheme,
+    needed: &SchemeRequirement,
+    credentials: &Credentials,
+    environment: Option<&Environment>,
+) -> Option<TokenRequest> {
+    let SecuritySchemeKind::OAuth2 { flows } = &scheme.kind else {
+        return None;
+    };
+    let token_url = flows
+        .iter()
+        .find(|flow| flow.kind == OAuthFlowKind::ClientCredentials)?
+        .token_url
+        .as_ref()?;
+    Some(TokenRequest {
+        token_url: token_url.clone(),
+        client_id: environment::resolve(credentials.client_id.as_ref()?, environment),
+        client_secret: environment::resolve(
+            credentials.client_secret.as_deref().unwrap_or_default(),
+            environment,
+        ),
+        scopes: credentials
+            .scopes
+            .clone()
+            .unwrap_or_else(|| needed.scopes.clone()),
+    })
+}
+
+/// Posts a client credentials grant, authenticating the client with HTTP Basic
+/// as RFC 6749 recommends.
+fn fetch_token(request: &TokenRequest) -> Result<Token> {
+    let credentials = STANDARD.encode(format!("{}:{}", request.client_id, request.client_secret));
+    let scope = request.scopes.join(" ");
+    let mut form = vec![("grant_type", "client_credentials")];
+    if !scope.is_empty() {
+        form.push(("scope", &scope));
+    }
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+    let result = agent
+        .post(&request.token_url)
+        .set("Authorization", &format!("Basic {}", credentials))
+        .set("Accept", "application/json")
+        .send_form(&form);
+    let response = match result {
+        Ok(response) => response,
+        Err(ureq::Error::Status(status, response)) => {
+            let body: Value = response
+                .into_string()
+                .ok()
+                .and_then(|body| serde_json::from_str(&body).ok())
+                .unwrap_or_default();
+            let reason = body
+                .get("error_description")
+                .or_else(|| body.get("error"))
+                .and_then(Value::as_str)
+                .map(|reason| format!(": {}", reason))
+                .unwrap_or_default();
+            bail!(
+                "The token request to {} failed with {}{}",
+                request.token_url,
+                status,
+                reason
+            );
+        }
+        Err(ureq::Error::Transport(err)) => return Err(anyhow!(err)),
+    };
+
+    let body: Value = serde_json::from_str(&response.into_string()?)?;
+    let access_token = body
+        .get("access_token")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("No access_token in the response of {}", request.token_url))?;
+    let expires_at = body
+        .get("expires_in")
+        .and_then(Value::as_u64)
+        // Too far in the future to represent means it doesn't expire
+        .and_then(|seconds| Instant::now().checked_add(Duration::from_secs(seconds)));
+    Ok(Token {
+        access_token: access_token.to_string(),
+        expires_at,
+    })
+}
+
+/// Sets the entry called `name`, adding it if there is none.
+fn set(entries: &mut Vec<(String, String)>, name: &str, value: String) {
+    match entries
+        .iter_mut()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+    {
+        Some(entry) => entry.1 = value,
+        None => entries.push((name.to_string(), value)),
+    }
+}
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::HttpMethod;
+    use crate::parser::parse_file;
+    use std::thread;
+
+    fn endpoint(spec: &ApiSpec, method: HttpMethod, path: &str) -> Endpoint {
+        spec.endpoints
+            .iter()
+            .find(|e| e.method == method && e.path == path)
+            .unwrap()
+            .clone()
+    }
+
+    fn credentials(entries: &[(&str, Credentials)]) -> Auth {
+        Auth::new(
+            entries
+                .iter()
+                .map(|(name, credentials)| (name.to_string(), credentials.clone()))
+                .collect(),
+        )
+    }
+
+    /// Answers each token request with the next of `bodies`, handing the
+    /// `Authorization` headers and bodies received back through the join handle.
+    fn token_server(
+        bodies: Vec<&'static str>,
+    ) -> (String, thread::JoinHandle<Vec<(String, String)>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/token", server.server_addr().to_ip().unwrap());
+        let handle = thread::spawn(move || {
+            let mut received = Vec::new();
+            for body in bodies {
+                let mut request = server.recv().unwrap();
+                let mut form = String::new();
+                request.as_reader().read_to_string(&mut form).unwrap();
+                let authorization = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Authorization"))
+                    .map(|h| h.value.to_string())
+                    .unwrap_or_default();
+                received.push((authorization, form));
+                let status = if body.contains("error") { 401 } else { 200 };
+                let response = tiny_http::Response::from_string(body).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+            received
+        });
+        (url, handle)
+    }
+
+    fn with_token_url(spec: &mut ApiSpec, url: &str) {
+        for scheme in &mut spec.security_schemes {
+            if let SecuritySchemeKind::OAuth2 { flows } = &mut scheme.kind {
+                flows[0].token_url = Some(url.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_credentials() {
+        let spec = parse_file("tests/fixtures/security.yaml").unwrap();
+        let list = endpoint(&spec, HttpMethod::Get, "/pets");
+        let environment = Environment {
+            variables: BTreeMap::from([("key".to_string(), "k-123".to_string())]),
+            ..Default::default()
+        };
+        let auth = credentials(&[(
+            "api_key",
+            Credentials {
+                api_key: Some("{{key}}".to_string()),
+                ..Default::default()
+            },
+        )]);
+        let mut request = Request::new(&list, &spec, None, 0, false);
+        auth.apply(&mut request, &list, &spec, Some(&environment));
+        assert_eq!(
+            request.headers,
+            vec![("X-API-Key".to_string(), "k-123".to_string())]
+        );
+
+        // Schemes without credentials keep their placeholders
+        let create = endpoint(&spec, HttpMethod::Post, "/pets");
+        let mut request = Request::new(&create, &spec, None, 0, false);
+        auth.apply(&mut request, &create, &spec, None);
+        assert_eq!(request.headers[0].1, "Bearer <access-token>");
+
+        let auth = credentials(&[(
+            "oauth",
+            Credentials {
+                token: Some("fixed".to_string()),
+                ..Default::default()
+            },
+        )]);
+        auth.apply(&mut request, &create, &spec, None);
+        assert_eq!(
+            request.headers,
+            vec![("Authorization".to_string(), "Bearer fixed".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_client_credentials() {
+        let (url, server) = token_server(vec![
+            r#"{"access_token": "first", "token_type": "Bearer", "expires_in": 3600}"#,
+        ]);
+        let mut spec = parse_file("tests/fixtures/security.yaml").unwrap();
+        with_token_url(&mut spec, &url);
+        let create = endpoint(&spec, HttpMethod::Post, "/pets");
+        let auth = credentials(&[(
+            "oauth",
+            Credentials {
+                client_id: Some("app".to_string()),
+                client_secret: Some("shh".to_string()),
+                ..Default::default()
+            },
+        )]);
+
+        let mut request = Request::new(&create, &spec, None, 0, false);
+        auth.apply(&mut request, &create, &spec, None);
+        assert_eq!(request.headers[0].1, "Bearer <access-token>");
+        let token_requests = auth.token_requests(&create, &spec, None);
+        assert_eq!(
+            token_requests,
+            vec![TokenRequest {
+                token_url: url.clone(),
+                client_id: "app".to_string(),
+                client_secret: "shh".to_string(),
+                scopes: vec!["admin:write".to_string()],
+            }]
+        );
+
+        auth.authorize(&mut request, &token_requests).unwrap();
+        assert_eq!(request.headers[0].1, "Bearer first");
+        assert_eq!(
+            server.join().unwrap(),
+            vec![(
+                "Basic YXBwOnNoaA==".to_string(),
+                "grant_type=client_credentials&scope=admin%3Awrite".to_string()
+            )]
+        );
+
+        // The token is reused while it is fresh, by clones too
+        assert!(auth.token_requests(&create, &spec, None).is_empty());
+        let mut request = Request::new(&create, &spec, None, 0, false);
+        auth.clone().apply(&mut request, &create, &spec, None);
+        assert_eq!(request.headers[0].1, "Bearer first");
+        assert_eq!(auth.secrets(None), vec!["shh", "first"]);
+    }
+
+    #[test]
+    fn test_client_credentials_refresh() {
+        let (url, server) = token_server(vec![
+            r#"{"access_token": "short", "expires_in": 5}"#,
+            r#"{"access_token": "second", "expires_in": 3600}"#,
+            r#"{"error": "invalid_client", "error_description": "Unknown client"}"#,
+        ]);
+        let mut spec = parse_file("tests/fixtures/security.yaml").unwrap();
+        with_token_url(&mut spec, &url);
+        let create = endpoint(&spec, HttpMethod::Post, "/pets");
+        let auth = credentials(&[(
+            "oauth",
+            Credentials {
+                client_id: Some("app".to_string()),
+                scopes: Some(vec![]),
+                ..Default::default()
+            },
+        )]);
+
+        // Tokens about to expire are fetched again
+        let mut request = Request::new(&create, &spec, None, 0, false);
+        auth.authorize(&mut request, &auth.token_requests(&create, &spec, None))
+            .unwrap();
+        assert_eq!(request.headers[0].1, "Bearer short");
+        let token_requests = auth.token_requests(&create, &spec, None);
+        assert_eq!(token_requests.len(), 1);
+        auth.authorize(&mut request, &token_requests).unwrap();
+        assert_eq!(request.headers[0].1, "Bearer second");
+
+        let err = auth.fetch(&token_requests[0]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "The token request to {} failed with 401: Unknown client",
+                url
+            )
+        );
+        let received = server.join().unwrap();
+        assert_eq!(received[0].1, "grant_type=client_credentials");
+    }
+
+    #[test]
+    fn test_client_credentials_huge_expiry() {
+        let (url, server) = token_server(vec![
+            r#"{"access_token": "forever", "expires_in": 18446744073709551615}"#,
+        ]);
+        let mut spec = parse_file("tests/fixtures/security.yaml").unwrap();
+        with_token_url(&mut spec, &url);
+        let create = endpoint(&spec, HttpMethod::Post, "/pets");
+        let auth = credentials(&[(
+            "oauth",
+            Credentials {
+                client_id: Some("app".to_string()),
+                ..Default::default()
+            },
+        )]);
+
+        let token_requests = auth.token_requests(&create, &spec, None);
+        assert_eq!(auth.fetch(&token_requests[0]).unwrap(), "forever");
+        assert!(auth.token_requests(&create, &spec, None).is_empty());
+        server.join().unwrap();
+    }
+}
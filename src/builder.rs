@@ -0,0 +1,592 @@
+use serde_json::Value;
+
+use crate::app::FormField;
+use crate::environment::{self, Environment};
+use crate::model::{ApiSpec, Bound, Constraints, Endpoint, Parameter, ParameterLocation};
+use crate::snippet::{self, Body, Request};
+
+/// What a parameter input accepts, from its schema type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Text,
+    Integer,
+    Number,
+    Boolean,
+}
+
+/// A parameter of the endpoint being filled in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterInput {
+    pub field: FormField,
+    pub location: ParameterLocation,
+    pub required: bool,
+    pub kind: InputKind,
+    constraints: Constraints,
+}
+
+impl ParameterInput {
+    fn new(param: &Parameter, example_index: usize) -> Self {
+        let schema_type = param.schema_type.as_deref().unwrap_or_default();
+        let kind = match schema_type {
+            "integer" => InputKind::Integer,
+            "number" => InputKind::Number,
+            "boolean" => InputKind::Boolean,
+            _ => InputKind::Text,
+        };
+        let mut options = match kind {
+            InputKind::Boolean => vec!["true".to_string(), "false".to_string()],
+            _ => param.constraints.enum_values.clone(),
+        };
+        // Optional choices can be left out of the request
+        if !options.is_empty() && !param.required {
+            options.insert(0, String::new());
+        }
+        // Optional parameters start out left out unless the spec shows a value
+        let shown = param.required || param.examples.iter().any(|e| e.value.is_some());
+        let value = shown
+            .then(|| snippet::parameter_example(param, example_index))
+            .flatten()
+            .filter(|value| options.is_empty() || options.contains(value))
+            .or_else(|| options.first().cloned())
+            .unwrap_or_default();
+
+        Self {
+            field: FormField {
+                name: param.name.clone(),
+                value,
+                options,
+                description: param.description.clone(),
+            },
+            location: param.location.clone(),
+            required: param.required,
+            kind,
+            constraints: param.constraints.clone(),
+        }
+    }
+
+    /// What is wrong with the current value once the variables of
+    /// `environment` are substituted, if anything.
+    pub fn error(&self, environment: Option<&Environment>) -> Option<String> {
+        let name = &self.field.name;
+        let value = environment::resolve(&self.field.value, environment);
+        let value = value.as_str();
+        if value.is_empty() {
+            return self.required.then(|| format!("{} is required", name));
+        }
+        if let Some(variable) = environment::variables(value).first() {
+            return Some(format!(
+                "{} uses the unknown variable {{{{{}}}}}",
+                name, variable
+            ));
+        }
+        let number = match self.kind {
+            InputKind::Integer => match value.parse::<i64>() {
+                Ok(n) => Some(n as f64),
+                Err(_) => return Some(format!("{} must be an integer", name)),
+            },
+            InputKind::Number => match value.parse::<f64>() {
+                Ok(n) => Some(n),
+                Err(_) => return Some(format!("{} must be a number", name)),
+            },
+            InputKind::Text | InputKind::Boolean => None,
+        };
+        if let Some(number) = number {
+            if let Some(bound) = self.constraints.minimum.as_ref() {
+                if violates(bound, |limit| {
+                    number < limit || bound.exclusive && number == limit
+                }) {
+                    let op = if bound.exclusive { ">" } else { "≥" };
+                    return Some(format!("{} must be {} {}", name, op, bound.value));
+                }
+            }
+            if let Some(bound) = self.constraints.maximum.as_ref() {
+                if violates(bound, |limit| {
+                    number > limit || bound.exclusive && number == limit
+                }) {
+                    let op = if bound.exclusive { "<" } else { "≤" };
+                    return Some(format!("{} must be {} {}", name, op, bound.value));
+                }
+            }
+        }
+        let length = value.chars().count();
+        if let Some(min) = self.constraints.min_length.filter(|&min| length < min) {
+            return Some(format!("{} must be at least {} characters", name, min));
+        }
+        if let Some(max) = self.constraints.max_length.filter(|&max| length > max) {
+            return Some(format!("{} must be at most {} characters", name, max));
+        }
+        None
+    }
+}
+
+fn violates(bound: &Bound, check: impl Fn(f64) -> bool) -> bool {
+    bound.value.parse::<f64>().is_ok_and(check)
+}
+
+/// A small multi-line editor for the request body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BodyEditor {
+    pub content_type: String,
+    pub required: bool,
+    pub lines: Vec<String>,
+    /// Cursor position, as a line and a character offset into it.
+    pub row: usize,
+    pub col: usize,
+    /// Whether keys go to the editor rather than moving between fields.
+    pub editing: bool,
+}
+
+impl BodyEditor {
+    fn new(content_type: String, required: bool, text: &str) -> Self {
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        Self {
+            content_type,
+            required,
+            lines,
+            row: 0,
+            col: 0,
+            editing: false,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    fn byte_offset(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .nth(self.col)
+            .map_or(line.len(), |(offset, _)| offset)
+    }
+
+    fn line_len(&self) -> usize {
+        self.lines[self.row].chars().count()
+    }
+
+    fn insert(&mut self, c: char) {
+        let offset = self.byte_offset();
+        self.lines[self.row].insert(offset, c);
+        self.col += 1;
+    }
+
+    fn newline(&mut self) {
+        let offset = self.byte_offset();
+        let rest = self.lines[self.row].split_off(offset);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let offset = self.byte_offset();
+            self.lines[self.row].remove(offset);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn up(&mut self) {
+        self.row = self.row.saturating_sub(1);
+        self.col = self.col.min(self.line_len());
+    }
+
+    fn down(&mut self) {
+        self.row = (self.row + 1).min(self.lines.len() - 1);
+        self.col = self.col.min(self.line_len());
+    }
+
+    fn left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len();
+        }
+    }
+
+    fn right(&mut self) {
+        if self.col < self.line_len() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    /// The body to send with the variables of `environment` substituted,
+    /// parsed according to its content type. `None` when left empty.
+    fn body(&self, environment: Option<&Environment>) -> Result<Option<Body>, String> {
+        let text = environment::resolve(&self.text(), environment);
+        if text.trim().is_empty() {
+            return match self.required {
+                true => Err("The body is required".to_string()),
+                false => Ok(None),
+            };
+        }
+        if let Some(variable) = environment::variables(&text).first() {
+            return Err(format!(
+                "The body uses the unknown variable {{{{{}}}}}",
+                variable
+            ));
+        }
+        let content_type = self.content_type.as_str();
+        let value = if content_type.contains("json") || content_type.starts_with("multipart/") {
+            serde_json::from_str(&text)
+                .map_err(|err| format!("The body is not valid JSON: {}", err))?
+        } else if content_type.contains("yaml") {
+            serde_yaml::from_str(&text)
+                .map_err(|err| format!("The body is not valid YAML: {}", err))?
+        } else {
+            Value::String(text)
+        };
+        Ok(Some(Body {
+            content_type: self.content_type.clone(),
+            value,
+        }))
+    }
+}
+
+/// The request builder for one endpoint: its parameters, then the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestForm {
+    /// Index into `spec.endpoints` of the endpoint being built.
+    pub endpoint: usize,
+    pub inputs: Vec<ParameterInput>,
+    pub body: Option<BodyEditor>,
+    /// Index into `inputs`, or `inputs.len()` for the body.
+    pub selected: usize,
+}
+
+impl RequestForm {
+    /// A form pre-filled from the endpoint's examples, defaults and the
+    /// generated sample body.
+    pub fn new(
+        index: usize,
+        endpoint: &Endpoint,
+        example_index: usize,
+        required_only: bool,
+    ) -> Self {
+        let inputs = endpoint
+            .parameters
+            .iter()
+            .map(|param| ParameterInput::new(param, example_index))
+            .collect();
+        let body = endpoint.request_body.as_ref().and_then(|body| {
+            let media = body.content.first()?;
+            let text = media
+                .example_value(example_index, required_only)
+                .map(|value| {
+                    Body {
+                        content_type: media.content_type.clone(),
+                        value,
+                    }
+                    .text()
+                })
+                .unwrap_or_default();
+            Some(BodyEditor::new(
+                media.content_type.clone(),
+                body.required,
+                &text,
+            ))
+        });
+        Self {
+            endpoint: index,
+            inputs,
+            body,
+            selected: 0,
+        }
+    }
+
+    fn item_count(&self) -> usize {
+        self.inputs.len() + usize::from(self.body.is_some())
+    }
+
+    /// The body editor while it is being typed into.
+    fn editor(&mut self) -> Option<&mut BodyEditor> {
+        self.body.as_mut().filter(|body| body.editing)
+    }
+
+    pub fn body_selected(&self) -> bool {
+        self.body.is_some() && self.selected == self.inputs.len()
+    }
+
+    pub fn next(&mut self) {
+        match self.editor() {
+            Some(editor) => editor.down(),
+            None => self.selected = (self.selected + 1).min(self.item_count().saturating_sub(1)),
+        }
+    }
+
+    pub fn previous(&mut self) {
+        match self.editor() {
+            Some(editor) => editor.up(),
+            None => self.selected = self.selected.saturating_sub(1),
+        }
+    }
+
+    /// Moves the cursor in the body, or picks another option.
+    pub fn cycle(&mut self, forward: bool) {
+        if let Some(editor) = self.editor() {
+            if forward {
+                editor.right();
+            } else {
+                editor.left();
+            }
+        } else if let Some(input) = self.inputs.get_mut(self.selected) {
+            input.field.cycle_option(forward);
+        }
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        if self.body_selected() {
+            let body = self.body.as_mut().expect("body is selected");
+            body.editing = true;
+            body.insert(c);
+        } else if let Some(input) = self.inputs.get_mut(self.selected) {
+            input.field.push_char(c);
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        if let Some(editor) = self.editor() {
+            editor.backspace();
+        } else if let Some(input) = self.inputs.get_mut(self.selected) {
+            input.field.pop_char();
+        }
+    }
+
+    /// Breaks the line in the body, starts editing it, or moves on to the
+    /// next field.
+    pub fn enter(&mut self) {
+        if let Some(editor) = self.editor() {
+            editor.newline();
+        } else if self.body_selected() {
+            if let Some(body) = self.body.as_mut() {
+                body.editing = true;
+            }
+        } else {
+            self.next();
+        }
+    }
+
+    /// Stops editing the body; returns false when there was nothing to stop
+    /// and the form should be closed.
+    pub fn escape(&mut self) -> bool {
+        match self.editor() {
+            Some(editor) => {
+                editor.editing = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Everything that keeps the request from being valid.
+    pub fn errors(&self, environment: Option<&Environment>) -> Vec<String> {
+        let mut errors: Vec<String> = self
+            .inputs
+            .iter()
+            .filter_map(|input| input.error(environment))
+            .collect();
+        if let Some(Err(err)) = self.body.as_ref().map(|body| body.body(environment)) {
+            errors.push(err);
+        }
+        errors
+    }
+
+    /// The request as filled in, with the variables of `environment`
+    /// substituted. Parameters left empty are omitted, and a body that
+    /// doesn't parse is sent as typed.
+    pub fn request(
+        &self,
+        endpoint: &Endpoint,
+        spec: &ApiSpec,
+        base_url: Option<&str>,
+        environment: Option<&Environment>,
+    ) -> Request {
+        let value = |param: &Parameter| {
+            self.inputs
+                .iter()
+                .find(|input| input.field.name == param.name && input.location == param.location)
+                .map(|input| environment::resolve(&input.field.value, environment))
+                .filter(|value| !value.is_empty())
+        };
+        let body = self.body.as_ref().and_then(|editor| {
+            editor.body(environment).unwrap_or_else(|_| {
+                Some(Body {
+                    content_type: editor.content_type.clone(),
+                    value: Value::String(environment::resolve(&editor.text(), environment)),
+                })
+            })
+        });
+        Request::with_values(endpoint, spec, base_url, value, body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::HttpMethod;
+    use crate::parser::parse_file;
+    use std::collections::BTreeMap;
+
+    fn form(path: &str, method: HttpMethod) -> (RequestForm, ApiSpec, usize) {
+        let spec = parse_file("tests/fixtures/builder.yaml").unwrap();
+        let index = spec
+            .endpoints
+            .iter()
+            .position(|e| e.path == path && e.method == method)
+            .unwrap();
+        let form = RequestForm::new(index, &spec.endpoints[index], 0, false);
+        (form, spec, index)
+    }
+
+    #[test]
+    fn test_form_inputs() {
+        let (form, _, _) = form("/pets", HttpMethod::Get);
+        let inputs: Vec<_> = form
+            .inputs
+            .iter()
+            .map(|i| {
+                (
+                    i.field.name.as_str(),
+                    i.kind,
+                    i.required,
+                    i.field.value.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            inputs,
+            vec![
+                ("limit", InputKind::Integer, true, "20"),
+                ("status", InputKind::Text, false, ""),
+                ("verbose", InputKind::Boolean, false, ""),
+                ("X-Trace", InputKind::Text, false, ""),
+            ]
+        );
+        assert_eq!(form.inputs[1].field.options, vec!["", "available", "sold"]);
+        assert_eq!(form.inputs[2].field.options, vec!["", "true", "false"]);
+        assert!(form.body.is_none());
+    }
+
+    #[test]
+    fn test_form_validation() {
+        let (mut form, _, _) = form("/pets", HttpMethod::Get);
+        assert!(form.errors(None).is_empty());
+
+        form.pop_char();
+        form.pop_char();
+        assert_eq!(form.errors(None), vec!["limit is required"]);
+        form.push_char('x');
+        assert_eq!(form.errors(None), vec!["limit must be an integer"]);
+        form.pop_char();
+        "500".chars().for_each(|c| form.push_char(c));
+        assert_eq!(form.errors(None), vec!["limit must be ≤ 100"]);
+        form.pop_char();
+        form.pop_char();
+
+        form.selected = 3;
+        "abc".chars().for_each(|c| form.push_char(c));
+        assert_eq!(
+            form.errors(None),
+            vec!["X-Trace must be at least 4 characters"]
+        );
+    }
+
+    #[test]
+    fn test_form_request() {
+        let (mut form, spec, index) = form("/pets", HttpMethod::Get);
+        form.next();
+        form.cycle(true);
+        form.next();
+        form.cycle(false);
+
+        let request = form.request(&spec.endpoints[index], &spec, None, None);
+        assert_eq!(
+            request.full_url(),
+            "/pets?limit=20&status=available&verbose=false"
+        );
+        assert!(request.headers.is_empty());
+    }
+
+    #[test]
+    fn test_form_variables() {
+        let (mut form, spec, index) = form("/pets", HttpMethod::Get);
+        let environment = Environment {
+            name: "local".to_string(),
+            variables: BTreeMap::from([("limit".to_string(), "50".to_string())]),
+            ..Default::default()
+        };
+        form.pop_char();
+        form.pop_char();
+        "{{limit}}".chars().for_each(|c| form.push_char(c));
+        assert_eq!(
+            form.errors(None),
+            vec!["limit uses the unknown variable {{limit}}"]
+        );
+        assert!(form.errors(Some(&environment)).is_empty());
+
+        let request = form.request(&spec.endpoints[index], &spec, None, Some(&environment));
+        assert_eq!(request.full_url(), "/pets?limit=50");
+    }
+
+    #[test]
+    fn test_body_editor() {
+        let (mut form, spec, index) = form("/pets/{petId}", HttpMethod::Put);
+        assert_eq!(form.errors(None), vec!["petId is required"]);
+        "7".chars().for_each(|c| form.push_char(c));
+
+        let body = form.body.as_ref().unwrap();
+        assert_eq!(body.text(), "{\n  \"name\": \"Rex\"\n}");
+
+        form.next();
+        assert!(form.body_selected());
+        form.enter();
+        // Add a field after the opening brace
+        form.cycle(true);
+        form.enter();
+        "\"tag\": \"dog\",".chars().for_each(|c| form.push_char(c));
+        assert!(form.escape());
+        assert!(!form.escape());
+
+        let request = form.request(&spec.endpoints[index], &spec, None, None);
+        assert_eq!(request.url, "/pets/7");
+        assert_eq!(
+            request.body.unwrap().value,
+            serde_json::json!({"tag": "dog", "name": "Rex"})
+        );
+
+        // Broken JSON is reported but still sent as typed
+        form.enter();
+        form.pop_char();
+        assert!(form.errors(None)[0].starts_with("The body is not valid JSON"));
+        let request = form.request(&spec.endpoints[index], &spec, None, None);
+        assert!(request.body.unwrap().value.is_string());
+    }
+
+    #[test]
+    fn test_body_editor_join_lines() {
+        let mut editor = BodyEditor::new("text/plain".to_string(), true, "ab\ncd");
+        editor.down();
+        editor.backspace();
+        assert_eq!(editor.lines, vec!["abcd"]);
+        assert_eq!((editor.row, editor.col), (0, 2));
+        editor.left();
+        editor.insert('é');
+        editor.insert('!');
+        assert_eq!(editor.text(), "aé!bcd");
+
+        let empty = BodyEditor::new("application/json".to_string(), true, "");
+        assert_eq!(empty.body(None), Err("The body is required".to_string()));
+    }
+}
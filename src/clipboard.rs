@@ -0,0 +1,127 @@
+use std::env;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// Local clipboard commands, tried in order until one succeeds.
+const COMMANDS: [&[&str]; 5] = [
+    &["pbcopy"],
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+    &["clip.exe"],
+];
+
+/// How copied text reached the clipboard, for the confirmation message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backend {
+    /// Sent to the terminal, which sets the clipboard on the user's machine.
+    Osc52,
+    Command(String),
+}
+
+impl std::fmt::Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Backend::Osc52 => f.write_str("OSC 52"),
+            Backend::Command(program) => f.write_str(program),
+        }
+    }
+}
+
+/// Copies `text` using the configured backend: `"osc52"`, or a command that
+/// reads the text from stdin.
+///
+/// Without configuration, SSH sessions use OSC 52 as a local clipboard would
+/// be on the wrong machine; elsewhere the first working clipboard command is
+/// used, with OSC 52 as the fallback.
+pub fn copy(text: &str, configured: Option<&str>) -> Result<Backend> {
+    match configured.map(str::trim) {
+        Some("osc52") => osc52(text),
+        Some(command) => {
+            let args: Vec<&str> = command.split_whitespace().collect();
+            run(&args, text)?;
+            Ok(Backend::Command(args[0].to_string()))
+        }
+        None if remote_session() => osc52(text),
+        None => match COMMANDS.iter().find(|args| run(args, text).is_ok()) {
+            Some(args) => Ok(Backend::Command(args[0].to_string())),
+            None => osc52(text),
+        },
+    }
+}
+
+fn remote_session() -> bool {
+    env::var_os("SSH_TTY").is_some() || env::var_os("SSH_CONNECTION").is_some()
+}
+
+fn run(args: &[&str], text: &str) -> Result<()> {
+    let Some((program, args)) = args.split_first() else {
+        bail!("Empty clipboard command");
+    };
+    // Output would end up in the middle of the TUI
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+fn osc52(text: &str) -> Result<Backend> {
+    let sequence = osc52_sequence(text, env::var_os("TMUX").is_some());
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()?;
+    Ok(Backend::Osc52)
+}
+
+/// The escape sequence asking the terminal to set its clipboard. Inside tmux
+/// it is wrapped in a passthrough sequence so that it reaches the outer
+/// terminal, with the inner escapes doubled.
+pub fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hello", false), "\x1b]52;c;aGVsbG8=\x07");
+        assert_eq!(
+            osc52_sequence("hello", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGVsbG8=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_copy_with_configured_command() {
+        let path = env::temp_dir().join(format!("oatui-clipboard-{}", std::process::id()));
+        let command = format!("tee {}", path.display());
+
+        let backend = copy("GET /pets", Some(&command)).unwrap();
+        assert_eq!(backend, Backend::Command("tee".to_string()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "GET /pets");
+        std::fs::remove_file(path).unwrap();
+
+        assert!(copy("text", Some("oatui-no-such-command")).is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::auth::Credentials;
+use crate::environment::EnvironmentConfig;
+use crate::theme::CustomTheme;
+
+/// User configuration loaded from `~/.config/oatui/config.toml`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Name of a built-in theme or of a table under `[themes]`.
+    pub theme: Option<String>,
+    /// Show the API overview instead of the endpoint list at startup.
+    pub overview_on_startup: bool,
+    /// `"osc52"`, or a command reading the copied text from stdin. Detected
+    /// when unset.
+    pub clipboard: Option<String>,
+    /// Name of the environment active at startup.
+    pub environment: Option<String>,
+    pub layout: LayoutConfig,
+    pub themes: BTreeMap<String, CustomTheme>,
+    pub environments: BTreeMap<String, EnvironmentConfig>,
+    /// Credentials by security scheme name.
+    pub auth: BTreeMap<String, Credentials>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// Initial share of the screen given to the endpoint list, in percent.
+    pub split_percent: u16,
+    /// Terminal width (in columns) below which the panes are stacked vertically.
+    pub stack_below: u16,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            split_percent: 40,
+            stack_below: 100,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config file if it exists, falling back to defaults otherwise.
+    pub fn load() -> Result<Self> {
+        match config_path() {
+            Some(path) if path.exists() => {
+                let content = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read config: {}", path.display()))?;
+                Self::parse(&content)
+                    .with_context(|| format!("Failed to parse config: {}", path.display()))
+            }
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("oatui").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_empty_config_uses_defaults() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.layout.split_percent, 40);
+        assert_eq!(config.layout.stack_below, 100);
+        assert!(!config.overview_on_startup);
+        assert_eq!(config.clipboard, None);
+    }
+
+    #[test]
+    fn test_parse_clipboard() {
+        let config = Config::parse("clipboard = \"osc52\"\n").unwrap();
+        assert_eq!(config.clipboard.as_deref(), Some("osc52"));
+    }
+
+    #[test]
+    fn test_parse_layout_section() {
+        let config = Config::parse("[layout]\nsplit_percent = 30\nstack_below = 80\n").unwrap();
+        assert_eq!(config.layout.split_percent, 30);
+        assert_eq!(config.layout.stack_below, 80);
+    }
+
+    #[test]
+    fn test_parse_partial_layout_section() {
+        let config = Config::parse("[layout]\nstack_below = 120\n").unwrap();
+        assert_eq!(config.layout.split_percent, 40);
+        assert_eq!(config.layout.stack_below, 120);
+    }
+
+    #[test]
+    fn test_parse_theme_and_custom_themes() {
+        let config =
+            Config::parse("theme = \"mine\"\n[themes.mine]\nbase = \"light\"\nget = \"green\"\n")
+                .unwrap();
+        assert_eq!(config.theme.as_deref(), Some("mine"));
+        let mine = &config.themes["mine"];
+        assert_eq!(mine.base.as_deref(), Some("light"));
+        assert_eq!(mine.styles["get"], "green");
+    }
+
+    #[test]
+    fn test_parse_environments() {
+        let config = Config::parse(
+            "environment = \"local\"\n\
+             [environments.local]\n\
+             base_url = \"http://localhost:8080\"\n\
+             secrets = [\"token\"]\n\
+             variables = { user_id = \"42\", token = \"abc\" }\n\
+             [environments.staging.variables]\n\
+             user_id = \"7\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.environment.as_deref(), Some("local"));
+        let local = &config.environments["local"];
+        assert_eq!(local.base_url.as_deref(), Some("http://localhost:8080"));
+        assert_eq!(local.variables["token"], "abc");
+        assert_eq!(local.secrets, vec!["token"]);
+        let staging = &config.environments["staging"];
+        assert_eq!(staging.base_url, None);
+        assert_eq!(staging.variables["user_id"], "7");
+    }
+
+    #[test]
+    fn test_parse_auth() {
+        let config = Config::parse(
+            "[auth.api_key]\n\
+             api_key = \"{{key}}\"\n\
+             [auth.oauth]\n\
+             client_id = \"app\"\n\
+             client_secret = \"shh\"\n\
+             scopes = [\"pets:read\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.auth["api_key"].api_key.as_deref(), Some("{{key}}"));
+        let oauth = &config.auth["oauth"];
+        assert_eq!(oauth.client_id.as_deref(), Some("app"));
+        assert_eq!(oauth.scopes, Some(vec!["pets:read".to_string()]));
+        assert_eq!(oauth.token, None);
+    }
+
+    #[test]
+    fn test_parse_invalid_config() {
+        assert!(Config::parse("[layout]\nsplit_percent = \"wide\"\n").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::snippet::Request;
+
+/// Shown instead of secret values, whatever their length.
+pub const MASK: &str = "••••••";
+
+/// An environment as written under `[environments.<name>]` in the config.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentConfig {
+    /// Replaces the URL of the active server.
+    pub base_url: Option<String>,
+    pub variables: BTreeMap<String, String>,
+    /// Names of the variables whose values are masked.
+    pub secrets: Vec<String>,
+}
+
+/// A named set of variables, substituted for `{{name}}` in requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Environment {
+    pub name: String,
+    pub base_url: Option<String>,
+    pub variables: BTreeMap<String, String>,
+    pub secrets: Vec<String>,
+}
+
+impl Environment {
+    pub fn new(name: &str, config: &EnvironmentConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            base_url: config.base_url.clone(),
+            variables: config.variables.clone(),
+            secrets: config.secrets.clone(),
+        }
+    }
+
+    /// Replaces `{{name}}` with the value of the variable `name`, leaving
+    /// unknown variables as they are. Values are not substituted again.
+    pub fn substitute(&self, text: &str) -> String {
+        let mut result = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+            let name = rest[start + 2..start + end].trim();
+            result.push_str(&rest[..start]);
+            match self.variables.get(name) {
+                Some(value) => result.push_str(value),
+                None => result.push_str(&rest[start..start + end + 2]),
+            }
+            rest = &rest[start + end + 2..];
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Substitutes variables everywhere in `request`: the URL, query,
+    /// headers, cookies, credentials and the strings of the body.
+    pub fn apply(&self, request: &Request) -> Request {
+        map_strings(request, &|text| self.substitute(text))
+    }
+
+    /// The values of the secret variables.
+    pub fn secret_values(&self) -> Vec<&str> {
+        self.secrets
+            .iter()
+            .filter_map(|name| self.variables.get(name))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The value of a variable as shown in the UI.
+    pub fn display_value(&self, name: &str) -> String {
+        match self.variables.get(name) {
+            Some(_) if self.secrets.iter().any(|secret| secret == name) => MASK.to_string(),
+            Some(value) => value.clone(),
+            None => String::new(),
+        }
+    }
+}
+
+/// `text` with the variables of `environment` substituted, if there is one.
+pub fn resolve(text: &str, environment: Option<&Environment>) -> String {
+    match environment {
+        Some(environment) => environment.substitute(text),
+        None => text.to_string(),
+    }
+}
+
+/// Names of the `{{variables}}` in `text`.
+pub fn variables(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        names.push(rest[start + 2..start + end].trim().to_string());
+        rest = &rest[start + end + 2..];
+    }
+    names
+}
+
+/// `text` with every one of `secrets` hidden.
+pub fn mask(text: &str, secrets: &[&str]) -> String {
+    let mut values: Vec<&str> = secrets
+        .iter()
+        .copied()
+        .filter(|value| !value.is_empty())
+        .collect();
+    // Longer values first, in case one secret contains another
+    values.sort_by_key(|value| std::cmp::Reverse(value.len()));
+    values
+        .into_iter()
+        .fold(text.to_string(), |text, value| text.replace(value, MASK))
+}
+
+/// `request` with every one of `secrets` hidden, for writing it out where its
+/// values get encoded or escaped, like the URL or a snippet.
+pub fn mask_request(request: &Request, secrets: &[&str]) -> Request {
+    map_strings(request, &|text| mask(text, secrets))
+}
+
+/// `request` with `f` applied to the URL, query, headers, cookies,
+/// credentials and the strings of the body.
+fn map_strings(request: &Request, f: &dyn Fn(&str) -> String) -> Request {
+    let pairs = |pairs: &[(String, String)]| {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.clone(), f(value)))
+            .collect()
+    };
+    let mut request = request.clone();
+    request.url = f(&request.url);
+    request.query = pairs(&request.query);
+    request.headers = pairs(&request.headers);
+    request.cookies = pairs(&request.cookies);
+    if let Some((user, password)) = &mut request.basic_auth {
+        *user = f(user);
+        *password = f(password);
+    }
+    if let Some(body) = &mut request.body {
+        map_value(&mut body.value, f);
+    }
+    request
+}
+
+fn map_value(value: &mut Value, f: &dyn Fn(&str) -> String) {
+    match value {
+        Value::String(text) => *text = f(text),
+        Value::Array(items) => items.iter_mut().for_each(|item| map_value(item, f)),
+        Value::Object(map) => map.values_mut().for_each(|item| map_value(item, f)),
+        _ => {}
+    }
+}
+
+/// Environments from the config, in name order.
+pub fn from_config(environments: &BTreeMap<String, EnvironmentConfig>) -> Vec<Environment> {
+    environments
+        .iter()
+        .map(|(name, config)| Environment::new(name, config))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippet::{Body, Language};
+    use serde_json::json;
+
+    fn staging() -> Environment {
+        Environment {
+            name: "staging".to_string(),
+            base_url: Some("https://staging.example.com".to_string()),
+            variables: BTreeMap::from([
+                ("user_id".to_string(), "42".to_string()),
+                ("token".to_string(), "s3cr3t".to_string()),
+                ("nested".to_string(), "{{user_id}}".to_string()),
+            ]),
+            secrets: vec!["token".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_substitute() {
+        let env = staging();
+        assert_eq!(env.substitute("/users/{{user_id}}"), "/users/42");
+        assert_eq!(env.substitute("{{ user_id }}-{{token}}"), "42-s3cr3t");
+        assert_eq!(
+            env.substitute("{{unknown}} {{user_id"),
+            "{{unknown}} {{user_id"
+        );
+        assert_eq!(env.substitute("{{nested}}"), "{{user_id}}");
+        assert_eq!(variables("a {{x}} b {{ y }} {{z"), vec!["x", "y"]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let request = Request {
+            url: "/users/{{user_id}}".to_string(),
+            query: vec![("owner".to_string(), "{{user_id}}".to_string())],
+            headers: vec![("Authorization".to_string(), "Bearer {{token}}".to_string())],
+            body: Some(Body {
+                content_type: "application/json".to_string(),
+                value: json!({"id": "{{user_id}}", "tags": ["{{token}}", 1]}),
+            }),
+            ..Default::default()
+        };
+        let request = staging().apply(&request);
+        assert_eq!(request.full_url(), "/users/42?owner=42");
+        assert_eq!(request.headers[0].1, "Bearer s3cr3t");
+        assert_eq!(
+            request.body.unwrap().value,
+            json!({"id": "42", "tags": ["s3cr3t", 1]})
+        );
+    }
+
+    #[test]
+    fn test_mask() {
+        let env = staging();
+        assert_eq!(
+            mask(
+                "curl -H 'Authorization: Bearer s3cr3t' /users/42",
+                &env.secret_values()
+            ),
+            "curl -H 'Authorization: Bearer ••••••' /users/42"
+        );
+        assert_eq!(env.display_value("token"), MASK);
+        assert_eq!(env.display_value("user_id"), "42");
+    }
+
+    #[test]
+    fn test_mask_escaped() {
+        let secret = r#"a+b/c="d'e\f"#;
+        let request = Request {
+            url: "/users".to_string(),
+            query: vec![("key".to_string(), secret.to_string())],
+            headers: vec![("X-Key".to_string(), secret.to_string())],
+            body: Some(Body {
+                content_type: "application/json".to_string(),
+                value: json!({ "key": secret }),
+            }),
+            ..Default::default()
+        };
+
+        let request = mask_request(&request, &[secret]);
+        assert_eq!(request.full_url(), format!("/users?key={}", MASK));
+        for language in Language::ALL {
+            let snippet = language.generate(&request);
+            assert!(!snippet.contains("b/c") && !snippet.contains("b%2Fc"));
+            assert_eq!(snippet.matches(MASK).count(), 3, "{}", snippet);
+        }
+    }
+}
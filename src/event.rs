@@ -1,36 +1,99 @@
 use std::time::Duration;
 
 use anyhow::Result;
-use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{
+    self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Event {
     Quit,
     NavigateUp,
     NavigateDown,
+    NavigateLeft,
+    NavigateRight,
     Enter,
     Back,
     Search,
+    ShrinkList,
+    GrowList,
+    ToggleZoom,
+    Overview,
+    Servers,
+    ToggleDeprecated,
+    Links,
+    NextExample,
+    PreviousExample,
+    ToggleOptional,
+    ToggleExtensions,
+    Definitions,
+    JumpBack,
+    JumpForward,
+    Snippet,
+    Copy,
+    RequestBuilder,
+    Send,
+    History,
+    Environments,
     Char(char),
     Backspace,
+    Resize,
     None,
 }
 
-pub fn poll_event(timeout: Duration) -> Result<Event> {
+/// Blocks until the next terminal event arrives.
+///
+/// When `text_input` is set, printable keys are always reported as `Event::Char`
+/// so that shortcuts like `q` or `j` can be typed into the search bar.
+pub fn read_event(text_input: bool) -> Result<Event> {
+    Ok(translate_event(event::read()?, text_input))
+}
+
+/// Waits at most `timeout` for the next terminal event, returning `Event::None`
+/// when there was none.
+pub fn poll_event(text_input: bool, timeout: Duration) -> Result<Event> {
     if event::poll(timeout)? {
-        if let CrosstermEvent::Key(key) = event::read()? {
-            return Ok(handle_key_event(key));
-        }
+        read_event(text_input)
+    } else {
+        Ok(Event::None)
+    }
+}
+
+fn translate_event(event: CrosstermEvent, text_input: bool) -> Event {
+    match event {
+        CrosstermEvent::Key(key) => handle_key_event(key, text_input),
+        CrosstermEvent::Resize(_, _) => Event::Resize,
+        _ => Event::None,
     }
-    Ok(Event::None)
 }
 
-fn handle_key_event(key: KeyEvent) -> Event {
+fn handle_key_event(key: KeyEvent, text_input: bool) -> Event {
     if key.kind != KeyEventKind::Press {
         return Event::None;
     }
 
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    if text_input {
+        return match key.code {
+            KeyCode::Char('r') if ctrl => Event::Send,
+            KeyCode::Esc => Event::Back,
+            KeyCode::Enter => Event::Enter,
+            KeyCode::Backspace => Event::Backspace,
+            KeyCode::Down => Event::NavigateDown,
+            KeyCode::Up => Event::NavigateUp,
+            KeyCode::Left => Event::NavigateLeft,
+            KeyCode::Right => Event::NavigateRight,
+            KeyCode::Char(c) => Event::Char(c),
+            _ => Event::None,
+        };
+    }
+
     match key.code {
+        // Terminals send Ctrl-i as Tab
+        KeyCode::Char('o') if ctrl => Event::JumpBack,
+        KeyCode::Char('r') if ctrl => Event::Send,
+        KeyCode::Tab => Event::JumpForward,
+        KeyCode::Char('i') if ctrl => Event::JumpForward,
         KeyCode::Char('q') => Event::Quit,
         KeyCode::Char('/') => Event::Search,
         KeyCode::Esc => Event::Back,
@@ -38,6 +101,25 @@ fn handle_key_event(key: KeyEvent) -> Event {
         KeyCode::Backspace => Event::Backspace,
         KeyCode::Down | KeyCode::Char('j') => Event::NavigateDown,
         KeyCode::Up | KeyCode::Char('k') => Event::NavigateUp,
+        KeyCode::Left => Event::NavigateLeft,
+        KeyCode::Right => Event::NavigateRight,
+        KeyCode::Char('<') => Event::ShrinkList,
+        KeyCode::Char('>') => Event::GrowList,
+        KeyCode::Char('z') => Event::ToggleZoom,
+        KeyCode::Char('i') => Event::Overview,
+        KeyCode::Char('s') => Event::Servers,
+        KeyCode::Char('d') => Event::ToggleDeprecated,
+        KeyCode::Char('l') => Event::Links,
+        KeyCode::Char(']') => Event::NextExample,
+        KeyCode::Char('[') => Event::PreviousExample,
+        KeyCode::Char('o') => Event::ToggleOptional,
+        KeyCode::Char('x') => Event::ToggleExtensions,
+        KeyCode::Char('g') => Event::Definitions,
+        KeyCode::Char('Y') => Event::Snippet,
+        KeyCode::Char('y') => Event::Copy,
+        KeyCode::Char('r') => Event::RequestBuilder,
+        KeyCode::Char('h') => Event::History,
+        KeyCode::Char('E') => Event::Environments,
         KeyCode::Char(c) => Event::Char(c),
         _ => Event::None,
     }
@@ -46,7 +128,7 @@ fn handle_key_event(key: KeyEvent) -> Event {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crossterm::event::{KeyEventState, KeyModifiers};
+    use crossterm::event::KeyEventState;
 
     fn make_key_event(code: KeyCode, kind: KeyEventKind) -> KeyEvent {
         KeyEvent {
@@ -59,70 +141,260 @@ mod tests {
 
     #[test]
     fn test_handle_key_event_quit() {
-        let event = handle_key_event(make_key_event(KeyCode::Char('q'), KeyEventKind::Press));
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('q'), KeyEventKind::Press),
+            false,
+        );
         assert_eq!(event, Event::Quit);
     }
 
     #[test]
     fn test_handle_key_event_back() {
-        let event = handle_key_event(make_key_event(KeyCode::Esc, KeyEventKind::Press));
+        let event = handle_key_event(make_key_event(KeyCode::Esc, KeyEventKind::Press), false);
         assert_eq!(event, Event::Back);
     }
 
     #[test]
     fn test_handle_key_event_enter() {
-        let event = handle_key_event(make_key_event(KeyCode::Enter, KeyEventKind::Press));
+        let event = handle_key_event(make_key_event(KeyCode::Enter, KeyEventKind::Press), false);
         assert_eq!(event, Event::Enter);
     }
 
     #[test]
     fn test_handle_key_event_navigate_down() {
-        let event = handle_key_event(make_key_event(KeyCode::Down, KeyEventKind::Press));
+        let event = handle_key_event(make_key_event(KeyCode::Down, KeyEventKind::Press), false);
         assert_eq!(event, Event::NavigateDown);
 
-        let event = handle_key_event(make_key_event(KeyCode::Char('j'), KeyEventKind::Press));
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('j'), KeyEventKind::Press),
+            false,
+        );
         assert_eq!(event, Event::NavigateDown);
     }
 
     #[test]
     fn test_handle_key_event_navigate_up() {
-        let event = handle_key_event(make_key_event(KeyCode::Up, KeyEventKind::Press));
+        let event = handle_key_event(make_key_event(KeyCode::Up, KeyEventKind::Press), false);
         assert_eq!(event, Event::NavigateUp);
 
-        let event = handle_key_event(make_key_event(KeyCode::Char('k'), KeyEventKind::Press));
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('k'), KeyEventKind::Press),
+            false,
+        );
         assert_eq!(event, Event::NavigateUp);
     }
 
     #[test]
     fn test_handle_key_event_release_ignored() {
-        let event = handle_key_event(make_key_event(KeyCode::Char('q'), KeyEventKind::Release));
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('q'), KeyEventKind::Release),
+            false,
+        );
         assert_eq!(event, Event::None);
     }
 
     #[test]
     fn test_handle_key_event_char() {
-        let event = handle_key_event(make_key_event(KeyCode::Char('x'), KeyEventKind::Press));
-        assert_eq!(event, Event::Char('x'));
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('u'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::Char('u'));
 
-        let event = handle_key_event(make_key_event(KeyCode::Char('a'), KeyEventKind::Press));
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('a'), KeyEventKind::Press),
+            false,
+        );
         assert_eq!(event, Event::Char('a'));
     }
 
     #[test]
     fn test_handle_key_event_search() {
-        let event = handle_key_event(make_key_event(KeyCode::Char('/'), KeyEventKind::Press));
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('/'), KeyEventKind::Press),
+            false,
+        );
         assert_eq!(event, Event::Search);
     }
 
     #[test]
     fn test_handle_key_event_backspace() {
-        let event = handle_key_event(make_key_event(KeyCode::Backspace, KeyEventKind::Press));
+        let event = handle_key_event(
+            make_key_event(KeyCode::Backspace, KeyEventKind::Press),
+            false,
+        );
         assert_eq!(event, Event::Backspace);
     }
 
+    #[test]
+    fn test_handle_key_event_layout() {
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('<'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::ShrinkList);
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('>'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::GrowList);
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('z'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::ToggleZoom);
+    }
+
+    #[test]
+    fn test_handle_key_event_toggle_deprecated() {
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('d'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::ToggleDeprecated);
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('l'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::Links);
+    }
+
+    #[test]
+    fn test_handle_key_event_examples() {
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char(']'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::NextExample);
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('['), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::PreviousExample);
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('o'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::ToggleOptional);
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('x'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::ToggleExtensions);
+    }
+
+    #[test]
+    fn test_handle_key_event_jumps() {
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('g'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::Definitions);
+
+        let ctrl_o = KeyEvent {
+            modifiers: KeyModifiers::CONTROL,
+            ..make_key_event(KeyCode::Char('o'), KeyEventKind::Press)
+        };
+        assert_eq!(handle_key_event(ctrl_o, false), Event::JumpBack);
+
+        let event = handle_key_event(make_key_event(KeyCode::Tab, KeyEventKind::Press), false);
+        assert_eq!(event, Event::JumpForward);
+    }
+
+    #[test]
+    fn test_handle_key_event_send() {
+        let ctrl_r = KeyEvent {
+            modifiers: KeyModifiers::CONTROL,
+            ..make_key_event(KeyCode::Char('r'), KeyEventKind::Press)
+        };
+        assert_eq!(handle_key_event(ctrl_r, false), Event::Send);
+        // Also while typing into the request builder
+        assert_eq!(handle_key_event(ctrl_r, true), Event::Send);
+
+        let r = make_key_event(KeyCode::Char('r'), KeyEventKind::Press);
+        assert_eq!(handle_key_event(r, true), Event::Char('r'));
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('h'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::History);
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('E'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::Environments);
+    }
+
+    #[test]
+    fn test_handle_key_event_snippet() {
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('Y'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::Snippet);
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('y'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::Copy);
+
+        let event = handle_key_event(
+            make_key_event(KeyCode::Char('r'), KeyEventKind::Press),
+            false,
+        );
+        assert_eq!(event, Event::RequestBuilder);
+    }
+
+    #[test]
+    fn test_handle_key_event_text_input_keeps_chars() {
+        for c in ['q', 'j', 'k', 'z', 'd', '/'] {
+            let event =
+                handle_key_event(make_key_event(KeyCode::Char(c), KeyEventKind::Press), true);
+            assert_eq!(event, Event::Char(c));
+        }
+
+        let event = handle_key_event(make_key_event(KeyCode::Down, KeyEventKind::Press), true);
+        assert_eq!(event, Event::NavigateDown);
+
+        let event = handle_key_event(make_key_event(KeyCode::Esc, KeyEventKind::Press), true);
+        assert_eq!(event, Event::Back);
+    }
+
+    #[test]
+    fn test_translate_event_resize() {
+        let event = translate_event(CrosstermEvent::Resize(80, 24), false);
+        assert_eq!(event, Event::Resize);
+    }
+
+    #[test]
+    fn test_translate_event_key() {
+        let key = make_key_event(KeyCode::Char('q'), KeyEventKind::Press);
+        assert_eq!(
+            translate_event(CrosstermEvent::Key(key), false),
+            Event::Quit
+        );
+    }
+
+    #[test]
+    fn test_translate_event_ignores_focus_changes() {
+        assert_eq!(
+            translate_event(CrosstermEvent::FocusGained, false),
+            Event::None
+        );
+    }
+
     #[test]
     fn test_handle_key_event_unknown() {
-        let event = handle_key_event(make_key_event(KeyCode::Tab, KeyEventKind::Press));
+        let event = handle_key_event(make_key_event(KeyCode::Insert, KeyEventKind::Press), false);
         assert_eq!(event, Event::None);
     }
 }
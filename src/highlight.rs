@@ -0,0 +1,231 @@
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use serde_json::Value;
+
+use crate::theme::Theme;
+
+/// Pretty-prints a value as YAML when the content type asks for it and as
+/// JSON otherwise. Plain strings (e.g. XML or CSV examples) are shown verbatim.
+pub fn render(
+    value: &Value,
+    content_type: Option<&str>,
+    theme: &Theme,
+    indent: usize,
+) -> Vec<Line<'static>> {
+    if let Value::String(text) = value {
+        return text
+            .lines()
+            .map(|line| {
+                Line::from(vec![
+                    Span::raw(" ".repeat(indent)),
+                    Span::styled(line.to_string(), theme.text),
+                ])
+            })
+            .collect();
+    }
+
+    let is_yaml = content_type.is_some_and(|ct| ct.contains("yaml"));
+    if is_yaml {
+        if let Ok(text) = serde_yaml::to_string(value) {
+            return yaml(&text, theme, indent);
+        }
+    }
+    let text = serde_json::to_string_pretty(value).unwrap_or_default();
+    json(&text, theme, indent)
+}
+
+/// Highlights pretty-printed JSON line by line.
+fn json(text: &str, theme: &Theme, indent: usize) -> Vec<Line<'static>> {
+    text.lines()
+        .map(|line| {
+            let mut spans = vec![Span::raw(" ".repeat(indent))];
+            spans.extend(json_spans(line, theme));
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn starts_json_token(c: char) -> bool {
+    c == '"' || c == '-' || c.is_ascii_alphanumeric()
+}
+
+fn json_spans(line: &str, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            let token: String = chars[start..i].iter().collect();
+            // A string followed by a colon is an object key
+            let is_key = chars[i..].iter().find(|c| !c.is_whitespace()) == Some(&':');
+            let style = if is_key {
+                theme.syntax_key
+            } else {
+                theme.syntax_string
+            };
+            spans.push(Span::styled(token, style));
+        } else if starts_json_token(c) {
+            let start = i;
+            while i < chars.len() && !matches!(chars[i], ',' | ']' | '}' | ' ') {
+                i += 1;
+            }
+            let token: String = chars[start..i].iter().collect();
+            spans.push(Span::styled(token, theme.syntax_literal));
+        } else {
+            let start = i;
+            while i < chars.len() && !starts_json_token(chars[i]) {
+                i += 1;
+            }
+            let token: String = chars[start..i].iter().collect();
+            spans.push(Span::styled(token, theme.subtle));
+        }
+    }
+    spans
+}
+
+/// Highlights YAML as produced by `serde_yaml`, one mapping entry or list item per line.
+fn yaml(text: &str, theme: &Theme, indent: usize) -> Vec<Line<'static>> {
+    text.lines()
+        .map(|line| {
+            let mut spans = vec![Span::raw(" ".repeat(indent))];
+            let trimmed = line.trim_start();
+            spans.push(Span::raw(line[..line.len() - trimmed.len()].to_string()));
+
+            let mut rest = trimmed;
+            while let Some(item) = rest.strip_prefix("- ") {
+                spans.push(Span::styled("- ", theme.subtle));
+                rest = item;
+            }
+
+            match split_yaml_key(rest) {
+                Some((key, value)) => {
+                    spans.push(Span::styled(key.to_string(), theme.syntax_key));
+                    spans.push(Span::styled(":", theme.subtle));
+                    if !value.is_empty() {
+                        spans.push(Span::raw(" "));
+                        spans.push(yaml_scalar(value, theme));
+                    }
+                }
+                None if rest == "-" => spans.push(Span::styled("-", theme.subtle)),
+                None => spans.push(yaml_scalar(rest, theme)),
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Splits `key: value` (or a bare `key:`), ignoring colons inside quoted scalars.
+fn split_yaml_key(line: &str) -> Option<(&str, &str)> {
+    if line.starts_with('"') || line.starts_with('\'') {
+        return None;
+    }
+    if let Some(key) = line.strip_suffix(':') {
+        return Some((key, ""));
+    }
+    let (key, value) = line.split_once(": ")?;
+    Some((key, value))
+}
+
+fn yaml_scalar(value: &str, theme: &Theme) -> Span<'static> {
+    let is_literal = matches!(value, "true" | "false" | "null" | "~" | "[]" | "{}")
+        || value.parse::<f64>().is_ok();
+    let style: Style = if is_literal {
+        theme.syntax_literal
+    } else {
+        theme.syntax_string
+    };
+    Span::styled(value.to_string(), style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plain(lines: &[Line<'static>]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn style_of(lines: &[Line<'static>], content: &str) -> Style {
+        lines
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .find(|span| span.content == content)
+            .unwrap_or_else(|| panic!("no span {:?}", content))
+            .style
+    }
+
+    #[test]
+    fn test_render_json() {
+        let theme = Theme::dark();
+        let value = json!({"id": 7, "name": "Rex", "tags": ["a"], "alive": true, "owner": null});
+        let lines = render(&value, Some("application/json"), &theme, 2);
+
+        assert_eq!(
+            plain(&lines),
+            vec![
+                "  {",
+                "    \"id\": 7,",
+                "    \"name\": \"Rex\",",
+                "    \"tags\": [",
+                "      \"a\"",
+                "    ],",
+                "    \"alive\": true,",
+                "    \"owner\": null",
+                "  }",
+            ]
+        );
+        assert_eq!(style_of(&lines, "\"name\""), theme.syntax_key);
+        assert_eq!(style_of(&lines, "\"Rex\""), theme.syntax_string);
+        assert_eq!(style_of(&lines, "7"), theme.syntax_literal);
+        assert_eq!(style_of(&lines, "true"), theme.syntax_literal);
+    }
+
+    #[test]
+    fn test_render_json_negative_numbers_and_escapes() {
+        let theme = Theme::dark();
+        let lines = render(&json!({"t": -1.5, "q": "say \"hi\""}), None, &theme, 0);
+
+        assert_eq!(style_of(&lines, "-1.5"), theme.syntax_literal);
+        assert_eq!(style_of(&lines, "\"say \\\"hi\\\"\""), theme.syntax_string);
+    }
+
+    #[test]
+    fn test_render_yaml() {
+        let theme = Theme::dark();
+        let value = json!({"id": 7, "name": "Rex", "tags": ["a", "b"]});
+        let lines = render(&value, Some("application/yaml"), &theme, 0);
+
+        assert_eq!(
+            plain(&lines),
+            vec!["id: 7", "name: Rex", "tags:", "- a", "- b"]
+        );
+        assert_eq!(style_of(&lines, "name"), theme.syntax_key);
+        assert_eq!(style_of(&lines, "Rex"), theme.syntax_string);
+        assert_eq!(style_of(&lines, "7"), theme.syntax_literal);
+    }
+
+    #[test]
+    fn test_render_plain_string() {
+        let theme = Theme::dark();
+        let value = json!("<pet>\n  <id>7</id>\n</pet>");
+        let lines = render(&value, Some("application/xml"), &theme, 4);
+
+        assert_eq!(
+            plain(&lines),
+            vec!["    <pet>", "      <id>7</id>", "    </pet>"]
+        );
+    }
+}
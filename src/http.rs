@@ -0,0 +1,275 @@
+use std::io::Read;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::Value;
+
+use crate::snippet::{Body, Request};
+use crate::validate::Validation;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+/// Larger bodies are cut off, as they wouldn't be readable in a pane anyway.
+const MAX_BODY_BYTES: u64 = 10 * 1024 * 1024;
+
+/// What the server sent back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub status_text: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// From sending the request until the whole body was read.
+    pub elapsed: Duration,
+}
+
+impl Response {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.header("content-type")
+    }
+
+    /// The body parsed as JSON, when it is declared (or looks) like JSON.
+    pub fn json(&self) -> Option<Value> {
+        let declared = self.content_type().is_some_and(|ct| ct.contains("json"));
+        let looks_like = self.body.trim_start().starts_with(['{', '[']);
+        if !declared && !looks_like {
+            return None;
+        }
+        serde_json::from_str(&self.body).ok()
+    }
+}
+
+/// A request sent during this session and its outcome.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exchange {
+    /// Index into `spec.endpoints` of the endpoint the request was built for.
+    pub endpoint: usize,
+    pub request: Request,
+    /// The response, or why none was received.
+    pub response: Result<Response, String>,
+    /// How the response compares to the endpoint's declared responses.
+    pub validation: Option<Validation>,
+}
+
+/// Sends `request` and waits for the response. Error statuses are returned as
+/// responses; only failing to get one at all is an error.
+pub fn send(request: &Request) -> Result<Response> {
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+    let mut call = agent.request(&request.method.to_string(), &request.full_url());
+    for (name, value) in request.headers_with_cookies() {
+        call = call.set(&name, &value);
+    }
+    if let Some((user, password)) = &request.basic_auth {
+        let credentials = STANDARD.encode(format!("{}:{}", user, password));
+        call = call.set("Authorization", &format!("Basic {}", credentials));
+    }
+
+    let start = Instant::now();
+    let result = match &request.body {
+        Some(body) => match multipart(body) {
+            Some((boundary, payload)) => call
+                .set(
+                    "Content-Type",
+                    &format!("multipart/form-data; boundary={}", boundary),
+                )
+                .send_string(&payload),
+            None => call.send_string(&body.text()),
+        },
+        None => call.call(),
+    };
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(ureq::Error::Transport(err)) => return Err(anyhow!(err)),
+    };
+
+    let status = response.status();
+    let status_text = response.status_text().to_string();
+    let mut headers = Vec::new();
+    for name in response.headers_names() {
+        for value in response.all(&name) {
+            headers.push((name.clone(), value.to_string()));
+        }
+    }
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_end(&mut bytes)?;
+
+    Ok(Response {
+        status,
+        status_text,
+        headers,
+        body: String::from_utf8_lossy(&bytes).into_owned(),
+        elapsed: start.elapsed(),
+    })
+}
+
+/// A boundary and the encoded parts of a `multipart/form-data` body.
+fn multipart(body: &Body) -> Option<(String, String)> {
+    let parts = body.parts()?;
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    let boundary = format!("oatui-{:x}", nanos);
+    let mut payload = String::new();
+    for (name, value) in parts {
+        payload.push_str(&format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            boundary, name, value
+        ));
+    }
+    payload.push_str(&format!("--{}--\r\n", boundary));
+    Some((boundary, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::HttpMethod;
+    use std::thread;
+
+    /// What the test server received.
+    struct Received {
+        method: String,
+        url: String,
+        headers: Vec<(String, String)>,
+        body: String,
+    }
+
+    impl Received {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    /// Serves a single request with `status` and `body`, handing what was
+    /// received back through the join handle.
+    fn serve_once(status: u16, body: &'static str) -> (String, thread::JoinHandle<Received>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let handle = thread::spawn(move || {
+            let mut request = server.recv().unwrap();
+            let mut received = String::new();
+            request.as_reader().read_to_string(&mut received).unwrap();
+            let headers = request
+                .headers()
+                .iter()
+                .map(|h| (h.field.to_string(), h.value.to_string()))
+                .collect();
+            let seen = Received {
+                method: request.method().to_string(),
+                url: request.url().to_string(),
+                headers,
+                body: received,
+            };
+            let header = tiny_http::Header::from_bytes("Content-Type", "application/json").unwrap();
+            let response = tiny_http::Response::from_string(body)
+                .with_status_code(status)
+                .with_header(header);
+            request.respond(response).unwrap();
+            seen
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_send() {
+        let (url, server) = serve_once(201, r#"{"id": 1, "name": "Rex"}"#);
+        let request = Request {
+            method: HttpMethod::Post,
+            url: format!("{}/pets", url),
+            query: vec![("dry run".to_string(), "yes".to_string())],
+            headers: vec![("X-Request-Id".to_string(), "42".to_string())],
+            cookies: vec![("session".to_string(), "abc".to_string())],
+            basic_auth: Some(("alice".to_string(), "secret".to_string())),
+            body: Some(Body {
+                content_type: "application/json".to_string(),
+                value: serde_json::json!({"name": "Rex"}),
+            }),
+            ..Default::default()
+        };
+
+        let response = send(&request).unwrap();
+        assert_eq!(response.status, 201);
+        assert_eq!(response.content_type(), Some("application/json"));
+        assert_eq!(
+            response.json(),
+            Some(serde_json::json!({"id": 1, "name": "Rex"}))
+        );
+
+        let received = server.join().unwrap();
+        assert_eq!(received.method, "POST");
+        assert_eq!(received.url, "/pets?dry%20run=yes");
+        assert_eq!(received.header("X-Request-Id"), Some("42"));
+        assert_eq!(received.header("Cookie"), Some("session=abc"));
+        assert_eq!(received.header("Content-Type"), Some("application/json"));
+        assert_eq!(
+            received.header("Authorization"),
+            Some("Basic YWxpY2U6c2VjcmV0")
+        );
+        assert_eq!(received.body, "{\n  \"name\": \"Rex\"\n}");
+    }
+
+    #[test]
+    fn test_send_error_status() {
+        let (url, server) = serve_once(404, r#"{"message": "Not found"}"#);
+        let request = Request {
+            url: format!("{}/pets/7", url),
+            ..Default::default()
+        };
+
+        let response = send(&request).unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.status_text, "Not Found");
+        assert_eq!(server.join().unwrap().method, "GET");
+    }
+
+    #[test]
+    fn test_send_without_server() {
+        let request = Request {
+            url: "/pets".to_string(),
+            ..Default::default()
+        };
+        assert!(send(&request).is_err());
+    }
+
+    #[test]
+    fn test_send_multipart() {
+        let (url, server) = serve_once(200, "{}");
+        let request = Request {
+            method: HttpMethod::Post,
+            url: format!("{}/uploads", url),
+            body: Some(Body {
+                content_type: "multipart/form-data".to_string(),
+                value: serde_json::json!({"note": "hi", "count": 2}),
+            }),
+            ..Default::default()
+        };
+        send(&request).unwrap();
+
+        let received = server.join().unwrap();
+        let content_type = received.header("Content-Type").unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        assert_eq!(
+            received.body,
+            format!(
+                "--{b}\r\nContent-Disposition: form-data; name=\"note\"\r\n\r\nhi\r\n\
+                 --{b}\r\nContent-Disposition: form-data; name=\"count\"\r\n\r\n2\r\n--{b}--\r\n",
+                b = boundary
+            )
+        );
+    }
+}
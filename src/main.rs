@@ -1,35 +1,102 @@
 mod app;
+mod auth;
+mod builder;
+mod clipboard;
+mod config;
+mod environment;
 mod event;
+mod highlight;
+mod http;
+mod markdown;
+mod mock;
 mod model;
 mod parser;
+mod sample;
+mod snippet;
+mod theme;
 mod ui;
+mod validate;
 
 use std::env;
 use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::thread;
 use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 
-use app::{App, Focus};
+use app::{App, Focus, Popup, Screen};
+use config::Config;
 use event::Event;
+use mock::MockLog;
+use theme::Theme;
+
+const USAGE: &str = "Usage: oatui <openapi-file>\n       oatui mock <openapi-file> [--port <port>]";
+/// The port Prism listens on, so that frontends set up for it work unchanged.
+const DEFAULT_MOCK_PORT: u16 = 4010;
 
 fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    if args.len() < 2 {
-        eprintln!("Usage: oatui <openapi-file>");
-        std::process::exit(1);
+    match args.as_slice() {
+        [command, file_path] if command == "mock" => mock(file_path, DEFAULT_MOCK_PORT),
+        [command, file_path, flag, port] if command == "mock" && flag == "--port" => {
+            match port.parse() {
+                Ok(port) => mock(file_path, port),
+                Err(_) => usage(),
+            }
+        }
+        [command, ..] if command == "mock" => usage(),
+        [file_path, ..] => browse(file_path),
+        [] => usage(),
     }
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(1);
+}
 
-    let file_path = &args[1];
+fn browse(file_path: &str) -> Result<()> {
+    let config = Config::load()?;
+    let theme = Theme::from_config(&config, no_color_requested())?;
     let spec = parser::parse_file(file_path)?;
-    let mut app = App::new(spec);
+    let mut app = App::with_config(spec, &config, theme);
+
+    with_terminal(|terminal| run_app(terminal, &mut app))
+}
 
+/// Serves example responses for the spec's operations until quitting, showing
+/// the requests as they come in.
+fn mock(file_path: &str, port: u16) -> Result<()> {
+    let config = Config::load()?;
+    let theme = Theme::from_config(&config, no_color_requested())?;
+    let spec = parser::parse_file(file_path)?;
+    let server = tiny_http::Server::http(("127.0.0.1", port))
+        .map_err(|err| anyhow!("Cannot listen on port {}: {}", port, err))?;
+    let address = match server.server_addr().to_ip() {
+        Some(address) => format!("http://{}", address),
+        None => format!("http://127.0.0.1:{}", port),
+    };
+    let mut log = MockLog::new(spec.title.clone(), address, &config.layout, theme);
+
+    let (entries, received) = mpsc::channel();
+    // The server thread ends with the process
+    thread::spawn(move || mock::serve(&server, &spec, &entries));
+
+    with_terminal(|terminal| run_mock(terminal, &mut log, &received))
+}
+
+/// Runs `run` on the alternate screen, restoring the terminal afterwards.
+fn with_terminal(
+    run: impl FnOnce(&mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()>,
+) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -38,7 +105,7 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Run the app
-    let result = run_app(&mut terminal, &mut app);
+    let result = run(&mut terminal);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -48,13 +115,90 @@ fn main() -> Result<()> {
     result
 }
 
+fn run_mock(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    log: &mut MockLog,
+    received: &mpsc::Receiver<mock::LogEntry>,
+) -> Result<()> {
+    let mut needs_redraw = true;
+    loop {
+        if needs_redraw {
+            terminal.draw(|frame| ui::render_mock(frame, log))?;
+        }
+
+        // Wake up regularly to show requests as they are answered
+        let event = event::poll_event(false, Duration::from_millis(100))?;
+        needs_redraw = event != Event::None;
+        match event {
+            Event::Quit => return Ok(()),
+            Event::NavigateDown => log.next(),
+            Event::NavigateUp => log.previous(),
+            Event::Char('J') => log.scroll_down(),
+            Event::Char('K') => log.scroll_up(),
+            Event::Char('c') => log.clear(),
+            _ => {}
+        }
+        while let Ok(entry) = received.try_recv() {
+            log.push(entry);
+            needs_redraw = true;
+        }
+    }
+}
+
+/// Honors the `NO_COLOR` convention: set to any non-empty value to disable colors.
+fn no_color_requested() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
 fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
+    let mut needs_redraw = true;
+    // Requests are sent on their own threads so that the UI stays responsive
+    let (responses, received) = mpsc::channel();
+    let mut in_flight = 0;
+
     loop {
-        terminal.draw(|frame| ui::render(frame, app))?;
+        if needs_redraw {
+            terminal.draw(|frame| ui::render(frame, app))?;
+        }
 
-        let event = event::poll_event(Duration::from_millis(100))?;
+        // Block until something happens instead of redrawing on a timer,
+        // unless a response may come in
+        let event = if in_flight > 0 {
+            event::poll_event(app.text_input(), Duration::from_millis(100))?
+        } else {
+            event::read_event(app.text_input())?
+        };
+        needs_redraw = event != Event::None;
         handle_event(app, event);
 
+        if let Some(item) = app.pending_copy.take() {
+            let result = clipboard::copy(&item.text, app.clipboard.as_deref());
+            app.copied(&item, result);
+        }
+
+        if let Some((endpoint, request)) = app.pending_send.take() {
+            let responses = responses.clone();
+            in_flight += 1;
+            let token_requests = app.token_requests(endpoint);
+            let auth = app.auth.clone();
+            thread::spawn(move || {
+                let mut request = request;
+                // A panic must still be reported, or the request stays in flight
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    auth.authorize(&mut request, &token_requests)
+                        .and_then(|()| http::send(&request))
+                }))
+                .unwrap_or_else(|_| Err(anyhow!("Sending the request failed unexpectedly")));
+                // The receiver only goes away when quitting
+                let _ = responses.send((endpoint, request, result));
+            });
+        }
+        while let Ok((endpoint, request, result)) = received.try_recv() {
+            in_flight -= 1;
+            app.received(endpoint, request, result);
+            needs_redraw = true;
+        }
+
         if app.should_quit {
             return Ok(());
         }
@@ -62,11 +206,31 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
 }
 
 fn handle_event(app: &mut App, event: Event) {
+    // The terminal is resized on the next draw; ui::render re-clamps scrolling
+    if event == Event::Resize {
+        return;
+    }
+    if event != Event::None {
+        app.clear_status();
+    }
+
     if app.search_mode {
         handle_search_mode_event(app, event);
         return;
     }
 
+    if app.popup.is_some() {
+        handle_popup_event(app, event);
+        return;
+    }
+
+    match app.screen {
+        Screen::Overview => return handle_overview_event(app, event),
+        Screen::Request => return handle_request_event(app, event),
+        Screen::Response => return handle_response_event(app, event),
+        Screen::Browse => {}
+    }
+
     match event {
         Event::Quit => app.quit(),
         Event::Search => app.enter_search_mode(),
@@ -77,7 +241,97 @@ fn handle_event(app: &mut App, event: Event) {
         Event::NavigateDown => app.scroll_down(),
         Event::NavigateUp if app.focus == Focus::List => app.select_previous(),
         Event::NavigateUp => app.scroll_up(),
-        Event::Back | Event::None | Event::Char(_) | Event::Backspace => {}
+        Event::ShrinkList => app.shrink_list(),
+        Event::GrowList => app.grow_list(),
+        Event::ToggleZoom => app.toggle_zoom(),
+        Event::Overview => app.toggle_overview(),
+        Event::Servers => app.open_server_picker(),
+        Event::ToggleDeprecated => app.toggle_hide_deprecated(),
+        Event::Links => app.open_links(),
+        Event::NextExample => app.next_example(),
+        Event::PreviousExample => app.previous_example(),
+        Event::ToggleOptional => app.toggle_required_only(),
+        Event::ToggleExtensions => app.toggle_extensions(),
+        Event::Definitions => app.open_definitions(),
+        Event::JumpBack => app.jump_back(),
+        Event::JumpForward => app.jump_forward(),
+        Event::Snippet => app.open_snippet(),
+        Event::Copy => app.open_copy(),
+        Event::RequestBuilder => app.open_request_builder(),
+        Event::Send => app.send_request(),
+        Event::History => app.open_history(),
+        Event::Environments => app.open_environments(),
+        Event::Back
+        | Event::None
+        | Event::Char(_)
+        | Event::Backspace
+        | Event::NavigateLeft
+        | Event::NavigateRight
+        | Event::Resize => {}
+    }
+}
+
+fn handle_popup_event(app: &mut App, event: Event) {
+    match event {
+        Event::Quit => app.quit(),
+        Event::Back => app.close_popup(),
+        Event::Enter => app.popup_confirm(),
+        Event::NavigateDown => app.popup_next(),
+        Event::NavigateUp => app.popup_previous(),
+        Event::NavigateLeft => app.popup_cycle(false),
+        Event::NavigateRight => app.popup_cycle(true),
+        Event::Char('e') if !app.text_input() => app.edit_server_variables(),
+        Event::Copy if matches!(app.popup, Some(Popup::Snippet { .. })) => app.copy_snippet(),
+        Event::Char(c) => app.form_push_char(c),
+        Event::Backspace => app.form_pop_char(),
+        _ => {}
+    }
+}
+
+fn handle_overview_event(app: &mut App, event: Event) {
+    match event {
+        Event::Quit => app.quit(),
+        Event::Overview | Event::Back | Event::Enter => app.close_overview(),
+        Event::NavigateDown => app.overview_scroll_down(),
+        Event::NavigateUp => app.overview_scroll_up(),
+        _ => {}
+    }
+}
+
+fn handle_request_event(app: &mut App, event: Event) {
+    let Some(form) = app.request_form.as_mut() else {
+        app.close_request_builder();
+        return;
+    };
+    match event {
+        Event::Back => {
+            // The first Esc only stops editing the body
+            let stopped_editing = form.escape();
+            if !stopped_editing {
+                app.close_request_builder();
+            }
+        }
+        Event::Send => app.send_request(),
+        Event::Enter => form.enter(),
+        Event::NavigateDown => form.next(),
+        Event::NavigateUp => form.previous(),
+        Event::NavigateLeft => form.cycle(false),
+        Event::NavigateRight => form.cycle(true),
+        Event::Char(c) => form.push_char(c),
+        Event::Backspace => form.pop_char(),
+        _ => {}
+    }
+}
+
+fn handle_response_event(app: &mut App, event: Event) {
+    match event {
+        Event::Quit => app.quit(),
+        Event::Back | Event::Enter => app.close_response(),
+        Event::NavigateDown => app.response_scroll_down(),
+        Event::NavigateUp => app.response_scroll_up(),
+        Event::Send => app.resend(),
+        Event::History => app.open_history(),
+        _ => {}
     }
 }
 
@@ -89,6 +343,6 @@ fn handle_search_mode_event(app: &mut App, event: Event) {
         Event::Backspace => app.search_pop_char(),
         Event::NavigateDown => app.select_next(),
         Event::NavigateUp => app.select_previous(),
-        Event::Quit | Event::Search | Event::None => {}
+        _ => {}
     }
 }
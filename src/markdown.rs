@@ -0,0 +1,500 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::theme::Theme;
+
+/// Renders CommonMark text (as used by OpenAPI descriptions) into styled lines.
+///
+/// `base` is the style of plain text and every line is prefixed with `indent` spaces.
+pub fn render(markdown: &str, base: Style, theme: &Theme, indent: usize) -> Vec<Line<'static>> {
+    let mut renderer = Renderer::new(base, theme, indent);
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    for event in Parser::new_ext(markdown, options) {
+        renderer.handle(event);
+    }
+    renderer.finish()
+}
+
+struct ListLevel {
+    /// Next number for ordered lists, `None` for bullet lists.
+    next_number: Option<u64>,
+    /// Width of the item marker, used to indent continuation lines.
+    marker_width: usize,
+}
+
+#[derive(Default)]
+struct TableState {
+    rows: Vec<Vec<String>>,
+    row: Vec<String>,
+    cell: String,
+    has_header: bool,
+}
+
+struct Renderer<'t> {
+    theme: &'t Theme,
+    indent: String,
+    lines: Vec<Line<'static>>,
+    spans: Vec<Span<'static>>,
+    styles: Vec<Style>,
+    lists: Vec<ListLevel>,
+    pending_marker: Option<String>,
+    quote_depth: usize,
+    in_code_block: bool,
+    link: Option<(String, String)>,
+    table: Option<TableState>,
+    needs_gap: bool,
+}
+
+impl<'t> Renderer<'t> {
+    fn new(base: Style, theme: &'t Theme, indent: usize) -> Self {
+        Self {
+            theme,
+            indent: " ".repeat(indent),
+            lines: Vec::new(),
+            spans: Vec::new(),
+            styles: vec![base],
+            lists: Vec::new(),
+            pending_marker: None,
+            quote_depth: 0,
+            in_code_block: false,
+            link: None,
+            table: None,
+            needs_gap: false,
+        }
+    }
+
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.flush_line();
+        self.lines
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, style: Style) {
+        self.styles.push(self.style().patch(style));
+    }
+
+    fn pop_style(&mut self) {
+        if self.styles.len() > 1 {
+            self.styles.pop();
+        }
+    }
+
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.text(&text),
+            Event::Code(code) => self.code(&code),
+            Event::Html(html) | Event::InlineHtml(html) => self.text(&html),
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.flush_line(),
+            Event::Rule => {
+                self.gap();
+                self.spans
+                    .push(Span::styled("─".repeat(20), self.theme.subtle));
+                self.flush_line();
+                self.needs_gap = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.gap(),
+            Tag::Heading { .. } => {
+                self.gap();
+                self.push_style(self.theme.heading);
+            }
+            Tag::BlockQuote(_) => {
+                self.gap();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.gap();
+                self.in_code_block = true;
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.gap();
+                } else {
+                    self.flush_line();
+                }
+                let marker_width = match start {
+                    Some(n) => format!("{}. ", n).len(),
+                    None => 2,
+                };
+                self.lists.push(ListLevel {
+                    next_number: start,
+                    marker_width,
+                });
+            }
+            Tag::Item => {
+                self.flush_line();
+                if let Some(level) = self.lists.last_mut() {
+                    let marker = match level.next_number.as_mut() {
+                        Some(n) => {
+                            let marker = format!("{}. ", n);
+                            *n += 1;
+                            marker
+                        }
+                        None => "• ".to_string(),
+                    };
+                    self.pending_marker = Some(marker);
+                }
+            }
+            Tag::Emphasis => self.push_style(Style::default().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(Style::default().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => {
+                self.push_style(Style::default().add_modifier(Modifier::CROSSED_OUT))
+            }
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.push_style(self.theme.link);
+                self.link = Some((dest_url.to_string(), String::new()));
+            }
+            Tag::Table(_) => {
+                self.gap();
+                self.table = Some(TableState::default());
+            }
+            Tag::TableHead => {
+                if let Some(table) = self.table.as_mut() {
+                    table.has_header = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                self.flush_line();
+                self.needs_gap = true;
+            }
+            TagEnd::Heading(_) => {
+                self.flush_line();
+                self.pop_style();
+                self.needs_gap = true;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush_line();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.needs_gap = true;
+            }
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.needs_gap = true;
+            }
+            TagEnd::List(_) => {
+                self.flush_line();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.needs_gap = true;
+                }
+            }
+            TagEnd::Item => {
+                self.flush_line();
+                // Loose list items end with a paragraph; keep items together
+                self.needs_gap = false;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => self.pop_style(),
+            TagEnd::Link | TagEnd::Image => {
+                self.pop_style();
+                if let Some((url, text)) = self.link.take() {
+                    if !url.is_empty() && url != text {
+                        self.spans
+                            .push(Span::styled(format!(" ({})", url), self.theme.subtle));
+                    }
+                }
+            }
+            TagEnd::TableHead | TagEnd::TableRow => {
+                if let Some(table) = self.table.as_mut() {
+                    let row = std::mem::take(&mut table.row);
+                    table.rows.push(row);
+                }
+            }
+            TagEnd::TableCell => {
+                if let Some(table) = self.table.as_mut() {
+                    let cell = std::mem::take(&mut table.cell);
+                    table.row.push(cell.trim().to_string());
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.render_table(table);
+                }
+                self.needs_gap = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some(table) = self.table.as_mut() {
+            table.cell.push_str(text);
+            return;
+        }
+
+        if self.in_code_block {
+            for line in text.lines() {
+                self.spans
+                    .push(Span::styled(format!("  {}", line), self.theme.code));
+                self.flush_line();
+            }
+            return;
+        }
+
+        if let Some((_, link_text)) = self.link.as_mut() {
+            link_text.push_str(text);
+        }
+        self.spans
+            .push(Span::styled(text.to_string(), self.style()));
+    }
+
+    fn code(&mut self, code: &str) {
+        if let Some(table) = self.table.as_mut() {
+            table.cell.push_str(code);
+            return;
+        }
+        if let Some((_, link_text)) = self.link.as_mut() {
+            link_text.push_str(code);
+        }
+        self.spans
+            .push(Span::styled(code.to_string(), self.theme.code));
+    }
+
+    /// Emits the blank line owed between two blocks.
+    fn gap(&mut self) {
+        self.flush_line();
+        if self.needs_gap && !self.lines.is_empty() {
+            self.lines.push(Line::raw(""));
+        }
+        self.needs_gap = false;
+    }
+
+    fn prefix(&mut self) -> String {
+        let mut prefix = self.indent.clone();
+        for _ in 0..self.quote_depth {
+            prefix.push_str("│ ");
+        }
+
+        let depth = self.lists.len();
+        for (i, level) in self.lists.iter().enumerate() {
+            if i + 1 == depth {
+                if let Some(marker) = self.pending_marker.take() {
+                    prefix.push_str(&marker);
+                    continue;
+                }
+            }
+            prefix.push_str(&" ".repeat(level.marker_width));
+        }
+        prefix
+    }
+
+    fn flush_line(&mut self) {
+        if self.spans.is_empty() && self.pending_marker.is_none() {
+            return;
+        }
+        let prefix = self.prefix();
+        let mut spans = vec![Span::styled(prefix, self.styles[0])];
+        spans.append(&mut self.spans);
+        self.lines.push(Line::from(spans));
+    }
+
+    fn render_table(&mut self, table: TableState) {
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|col| {
+                table
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(col))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let base = self.styles[0];
+        for (i, row) in table.rows.iter().enumerate() {
+            let cells: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(col, width)| {
+                    let cell = row.get(col).map(String::as_str).unwrap_or("");
+                    format!("{:width$}", cell, width = width)
+                })
+                .collect();
+            let style = if i == 0 && table.has_header {
+                base.add_modifier(Modifier::BOLD)
+            } else {
+                base
+            };
+            self.spans.push(Span::styled(cells.join(" │ "), style));
+            self.flush_line();
+
+            if i == 0 && table.has_header {
+                let rule: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+                self.spans
+                    .push(Span::styled(rule.join("─┼─"), self.theme.subtle));
+                self.flush_line();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_plain(markdown: &str) -> Vec<String> {
+        render(markdown, Style::default(), &Theme::dark(), 0)
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    fn find_span<'a>(lines: &'a [Line<'static>], content: &str) -> &'a Span<'static> {
+        lines
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .find(|span| span.content == content)
+            .unwrap_or_else(|| panic!("no span {:?}", content))
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(render_plain("Returns all pets."), vec!["Returns all pets."]);
+    }
+
+    #[test]
+    fn test_soft_break_joins_lines() {
+        assert_eq!(render_plain("first\nsecond"), vec!["first second"]);
+    }
+
+    #[test]
+    fn test_paragraphs_are_separated() {
+        assert_eq!(render_plain("one\n\ntwo"), vec!["one", "", "two"]);
+    }
+
+    #[test]
+    fn test_emphasis_and_strong() {
+        let lines = render("*soft* and **loud**", Style::default(), &Theme::dark(), 0);
+
+        assert_eq!(lines[0].to_string(), "soft and loud");
+        assert!(find_span(&lines, "soft")
+            .style
+            .add_modifier
+            .contains(Modifier::ITALIC));
+        assert!(find_span(&lines, "loud")
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_inline_code() {
+        let theme = Theme::dark();
+        let lines = render("Use `limit` here", Style::default(), &theme, 0);
+
+        assert_eq!(lines[0].to_string(), "Use limit here");
+        assert_eq!(find_span(&lines, "limit").style, theme.code);
+    }
+
+    #[test]
+    fn test_heading() {
+        let theme = Theme::dark();
+        let lines = render("# Title\n\nBody", Style::default(), &theme, 0);
+
+        assert_eq!(lines[0].to_string(), "Title");
+        assert_eq!(find_span(&lines, "Title").style, theme.heading);
+        assert_eq!(lines[2].to_string(), "Body");
+    }
+
+    #[test]
+    fn test_fenced_code_block() {
+        let theme = Theme::dark();
+        let lines = render(
+            "Example:\n\n```json\n{\n  \"id\": 1\n}\n```",
+            Style::default(),
+            &theme,
+            0,
+        );
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+
+        assert_eq!(text, vec!["Example:", "", "  {", "    \"id\": 1", "  }"]);
+        assert_eq!(find_span(&lines, "  {").style, theme.code);
+    }
+
+    #[test]
+    fn test_bullet_list() {
+        assert_eq!(
+            render_plain("- one\n- two\n  - nested"),
+            vec!["• one", "• two", "  • nested"]
+        );
+    }
+
+    #[test]
+    fn test_ordered_list() {
+        assert_eq!(
+            render_plain("Steps:\n\n1. first\n2. second"),
+            vec!["Steps:", "", "1. first", "2. second"]
+        );
+    }
+
+    #[test]
+    fn test_link_shows_url() {
+        let theme = Theme::dark();
+        let lines = render(
+            "See [docs](https://example.com).",
+            Style::default(),
+            &theme,
+            0,
+        );
+
+        assert_eq!(lines[0].to_string(), "See docs (https://example.com).");
+        assert_eq!(find_span(&lines, "docs").style, theme.link);
+    }
+
+    #[test]
+    fn test_autolink_does_not_repeat_url() {
+        assert_eq!(
+            render_plain("<https://example.com>"),
+            vec!["https://example.com"]
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            render_plain("| Code | Meaning |\n|---|---|\n| 1 | ok |\n| 22 | retry |"),
+            vec![
+                "Code │ Meaning",
+                "─────┼────────",
+                "1    │ ok     ",
+                "22   │ retry  ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_block_quote() {
+        assert_eq!(render_plain("> careful"), vec!["│ careful"]);
+    }
+
+    #[test]
+    fn test_indent_and_base_style() {
+        let theme = Theme::dark();
+        let lines = render("one\n\ntwo", theme.muted, &theme, 4);
+
+        assert_eq!(lines[0].to_string(), "    one");
+        assert_eq!(lines[2].to_string(), "    two");
+        assert_eq!(find_span(&lines, "one").style, theme.muted);
+    }
+}
@@ -0,0 +1,580 @@
+use std::io::Read;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::app::{MAX_SPLIT_PERCENT, MIN_SPLIT_PERCENT};
+use crate::config::LayoutConfig;
+use crate::model::{ApiSpec, Endpoint, EndpointKind, HttpMethod, Response};
+use crate::theme::Theme;
+
+/// Larger request bodies are cut off in the log.
+const MAX_LOGGED_BODY_BYTES: u64 = 64 * 1024;
+
+/// What the mock server answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    fn new(status: u16, content_type: Option<&str>, body: String) -> Self {
+        let mut headers = vec![("Access-Control-Allow-Origin".to_string(), "*".to_string())];
+        if let Some(content_type) = content_type {
+            headers.push(("Content-Type".to_string(), content_type.to_string()));
+        }
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    fn error(status: u16, message: String) -> Self {
+        let body = json!({ "message": message });
+        Self::new(status, Some("application/json"), body.to_string())
+    }
+}
+
+/// A request the mock server answered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub method: String,
+    pub url: String,
+    /// The operation that answered, as `METHOD /path/{template}`.
+    pub operation: Option<String>,
+    pub request_headers: Vec<(String, String)>,
+    pub request_body: String,
+    pub response: MockResponse,
+    pub elapsed: Duration,
+}
+
+impl LogEntry {
+    /// The request's method, unless it is one operations can't have.
+    pub fn http_method(&self) -> Option<HttpMethod> {
+        HttpMethod::ALL
+            .into_iter()
+            .find(|method| method.to_string().eq_ignore_ascii_case(&self.method))
+    }
+}
+
+/// Which operation a request is for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// Index into `spec.endpoints`.
+    Operation(usize),
+    /// The path exists, but not with this method.
+    MethodNotAllowed(Vec<String>),
+    NotFound,
+}
+
+/// Finds the operation for `method` and `path`, which may carry the path of
+/// the first server as a prefix. Literal segments win over templated ones, so
+/// that `/pets/mine` is preferred to `/pets/{petId}`.
+pub fn route(spec: &ApiSpec, method: &str, path: &str) -> Route {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let base = spec
+        .servers
+        .first()
+        .map(|server| base_path(&server.url))
+        .unwrap_or_default();
+    let path = match path.strip_prefix(base.as_str()) {
+        Some(rest) if !base.is_empty() && (rest.is_empty() || rest.starts_with('/')) => rest,
+        _ => path,
+    };
+
+    let mut best: Option<(usize, usize)> = None;
+    let mut allowed = Vec::new();
+    for (index, endpoint) in spec.endpoints.iter().enumerate() {
+        if endpoint.kind != EndpointKind::Operation {
+            continue;
+        }
+        let Some(score) = matches(&endpoint.path, path) else {
+            continue;
+        };
+        if !endpoint.method.to_string().eq_ignore_ascii_case(method) {
+            allowed.push(endpoint.method.to_string());
+            continue;
+        }
+        if best.is_none_or(|(_, best)| score > best) {
+            best = Some((index, score));
+        }
+    }
+    match best {
+        Some((index, _)) => Route::Operation(index),
+        None if !allowed.is_empty() => Route::MethodNotAllowed(allowed),
+        None => Route::NotFound,
+    }
+}
+
+/// The path part of a server URL, without the trailing slash.
+fn base_path(url: &str) -> String {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |start| &rest[start..]),
+        None => url,
+    };
+    path.trim_end_matches('/').to_string()
+}
+
+/// The number of literal segments, if `path` fits the `template`.
+fn matches(template: &str, path: &str) -> Option<usize> {
+    let templates: Vec<&str> = template.trim_end_matches('/').split('/').collect();
+    let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    if templates.len() != segments.len() {
+        return None;
+    }
+    let mut literal = 0;
+    for (template, segment) in templates.iter().zip(&segments) {
+        match (template.find('{'), template.rfind('}')) {
+            (Some(start), Some(end)) if start < end => {
+                let (prefix, suffix) = (&template[..start], &template[end + 1..]);
+                let fits = segment.len() > prefix.len() + suffix.len()
+                    && segment.starts_with(prefix)
+                    && segment.ends_with(suffix);
+                if !fits {
+                    return None;
+                }
+            }
+            _ if template == segment => literal += 1,
+            _ => return None,
+        }
+    }
+    Some(literal)
+}
+
+/// The status code asked for with `Prefer: code=404`, as Prism does.
+pub fn preferred_status(headers: &[(String, String)]) -> Option<u16> {
+    headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("prefer"))
+        .flat_map(|(_, value)| value.split([',', ';']))
+        .find_map(|preference| preference.trim().strip_prefix("code="))
+        .and_then(|code| code.trim().parse().ok())
+}
+
+/// Answers with the response declared for `status`, or else the first success
+/// (the lowest `2xx`, a `2XX` range, `default`, or the lowest status declared),
+/// using its first example or a sample generated from its schema.
+pub fn respond(endpoint: &Endpoint, status: Option<u16>) -> MockResponse {
+    let chosen = match status {
+        Some(status) => declared(endpoint, status).map(|response| (status, response)),
+        None => success(endpoint),
+    };
+    let Some((status, response)) = chosen else {
+        return match status {
+            Some(status) => MockResponse::error(
+                500,
+                format!(
+                    "{} {} declares no {} response",
+                    endpoint.method, endpoint.path, status
+                ),
+            ),
+            None => MockResponse::new(204, None, String::new()),
+        };
+    };
+
+    let Some(media) = response.content.first() else {
+        return MockResponse::new(status, None, String::new());
+    };
+    let body = match media.example_value(0, false) {
+        Some(Value::String(text)) if !media.content_type.contains("json") => text,
+        Some(value) => serde_json::to_string_pretty(&value).unwrap_or_default(),
+        None => String::new(),
+    };
+    MockResponse::new(status, Some(&media.content_type), body)
+}
+
+/// The response for `status`: exact, then its range, then `default`.
+fn declared(endpoint: &Endpoint, status: u16) -> Option<&Response> {
+    let range = format!("{}XX", status / 100);
+    endpoint
+        .responses
+        .get(&status.to_string())
+        .or_else(|| endpoint.responses.get(&range))
+        .or_else(|| endpoint.responses.get("default"))
+}
+
+fn success(endpoint: &Endpoint) -> Option<(u16, &Response)> {
+    let exact = endpoint
+        .responses
+        .iter()
+        .filter_map(|(key, response)| Some((key.parse::<u16>().ok()?, response)))
+        .filter(|(status, _)| (200..300).contains(status))
+        .min_by_key(|(status, _)| *status);
+    exact
+        .or_else(|| {
+            let response = endpoint.responses.get("2XX")?;
+            Some((200, response))
+        })
+        .or_else(|| {
+            let response = endpoint.responses.get("default")?;
+            Some((200, response))
+        })
+        .or_else(|| {
+            endpoint
+                .responses
+                .iter()
+                .find_map(|(key, response)| Some((key.parse().ok()?, response)))
+        })
+}
+
+/// Answers a request, returning the operation it was routed to.
+pub fn handle(
+    spec: &ApiSpec,
+    method: &str,
+    url: &str,
+    headers: &[(String, String)],
+) -> (MockResponse, Option<usize>) {
+    match route(spec, method, url) {
+        Route::Operation(index) => {
+            let response = respond(&spec.endpoints[index], preferred_status(headers));
+            (response, Some(index))
+        }
+        // Browsers ask before cross-origin requests with a JSON body
+        Route::MethodNotAllowed(allowed) if method.eq_ignore_ascii_case("OPTIONS") => {
+            let mut response = MockResponse::new(204, None, String::new());
+            response.headers.extend([
+                (
+                    "Access-Control-Allow-Methods".to_string(),
+                    allowed.join(", "),
+                ),
+                ("Access-Control-Allow-Headers".to_string(), "*".to_string()),
+            ]);
+            (response, None)
+        }
+        Route::MethodNotAllowed(allowed) => {
+            let path = url.split('?').next().unwrap_or_default();
+            let mut response = MockResponse::error(
+                405,
+                format!("{} is not allowed on {}", method.to_uppercase(), path),
+            );
+            response
+                .headers
+                .push(("Allow".to_string(), allowed.join(", ")));
+            (response, None)
+        }
+        Route::NotFound => {
+            let path = url.split('?').next().unwrap_or_default();
+            let message = format!("No operation matches {} {}", method.to_uppercase(), path);
+            (MockResponse::error(404, message), None)
+        }
+    }
+}
+
+/// Answers requests until the server is dropped, logging each one.
+pub fn serve(server: &tiny_http::Server, spec: &ApiSpec, log: &Sender<LogEntry>) {
+    for mut request in server.incoming_requests() {
+        let start = Instant::now();
+        let method = request.method().to_string();
+        let url = request.url().to_string();
+        let request_headers: Vec<(String, String)> = request
+            .headers()
+            .iter()
+            .map(|h| (h.field.to_string(), h.value.to_string()))
+            .collect();
+        let mut body = Vec::new();
+        let _ = request
+            .as_reader()
+            .take(MAX_LOGGED_BODY_BYTES)
+            .read_to_end(&mut body);
+
+        let (response, endpoint) = handle(spec, &method, &url, &request_headers);
+        let mut reply = tiny_http::Response::from_string(response.body.clone())
+            .with_status_code(response.status);
+        for (name, value) in &response.headers {
+            if let Ok(header) = tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+                reply.add_header(header);
+            }
+        }
+        // The client hanging up is worth a log entry all the same
+        let _ = request.respond(reply);
+
+        let operation = endpoint
+            .map(|index| &spec.endpoints[index])
+            .map(|endpoint| format!("{} {}", endpoint.method, endpoint.path));
+        let entry = LogEntry {
+            method,
+            url,
+            operation,
+            request_headers,
+            request_body: String::from_utf8_lossy(&body).into_owned(),
+            response,
+            elapsed: start.elapsed(),
+        };
+        if log.send(entry).is_err() {
+            return;
+        }
+    }
+}
+
+/// State of the `oatui mock` screen.
+pub struct MockLog {
+    /// The API's title.
+    pub title: String,
+    /// Where the server listens, like `http://127.0.0.1:4010`.
+    pub address: String,
+    pub entries: Vec<LogEntry>,
+    /// Index into `entries`; new entries are followed while the last one is
+    /// selected.
+    pub selected: usize,
+    pub detail_scroll: u16,
+    /// Share of the screen given to the log, in percent.
+    pub split_percent: u16,
+    /// Terminal width (in columns) below which the log is stacked above the
+    /// details.
+    pub stack_below: u16,
+    pub theme: Theme,
+}
+
+impl MockLog {
+    pub fn new(title: String, address: String, layout: &LayoutConfig, theme: Theme) -> Self {
+        Self {
+            title,
+            address,
+            entries: Vec::new(),
+            selected: 0,
+            detail_scroll: 0,
+            split_percent: layout
+                .split_percent
+                .clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT),
+            stack_below: layout.stack_below,
+            theme,
+        }
+    }
+
+    pub fn push(&mut self, entry: LogEntry) {
+        if self.selected + 1 == self.entries.len() {
+            self.selected += 1;
+            self.detail_scroll = 0;
+        }
+        self.entries.push(entry);
+    }
+
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+            self.detail_scroll = 0;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+            self.detail_scroll = 0;
+        }
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.detail_scroll = self.detail_scroll.saturating_add(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.detail_scroll = self.detail_scroll.saturating_sub(1);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.selected = 0;
+        self.detail_scroll = 0;
+    }
+
+    pub fn selected_entry(&self) -> Option<&LogEntry> {
+        self.entries.get(self.selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_file;
+    use std::sync::mpsc;
+    use std::thread;
+
+    fn spec() -> ApiSpec {
+        parse_file("tests/fixtures/mock.yaml").unwrap()
+    }
+
+    fn operation(spec: &ApiSpec, route: Route) -> String {
+        match route {
+            Route::Operation(index) => {
+                let endpoint = &spec.endpoints[index];
+                format!("{} {}", endpoint.method, endpoint.path)
+            }
+            other => format!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_route() {
+        let spec = spec();
+        let route = |method: &str, path: &str| operation(&spec, route(&spec, method, path));
+        assert_eq!(route("GET", "/pets"), "GET /pets");
+        assert_eq!(route("get", "/pets/?limit=2"), "GET /pets");
+        assert_eq!(route("POST", "/v1/pets"), "POST /pets");
+        assert_eq!(route("GET", "/pets/7"), "GET /pets/{petId}");
+        assert_eq!(route("GET", "/pets/mine"), "GET /pets/mine");
+        assert_eq!(route("DELETE", "/pets/7"), r#"MethodNotAllowed(["GET"])"#);
+        assert_eq!(route("GET", "/pets/7/toys"), "NotFound");
+        assert_eq!(route("GET", "/v1pets"), "NotFound");
+    }
+
+    #[test]
+    fn test_matches() {
+        assert_eq!(matches("/files/{name}.json", "/files/a.json"), Some(2));
+        assert_eq!(matches("/files/{name}.json", "/files/.json"), None);
+        assert_eq!(matches("/files/{name}.json", "/files/a.yaml"), None);
+        assert_eq!(base_path("https://api.example.com/v1/"), "/v1");
+        assert_eq!(base_path("https://api.example.com"), "");
+        assert_eq!(base_path("/api"), "/api");
+    }
+
+    #[test]
+    fn test_respond() {
+        let spec = spec();
+        let endpoint = |method: HttpMethod, path: &str| {
+            spec.endpoints
+                .iter()
+                .find(|e| e.method == method && e.path == path)
+                .unwrap()
+        };
+
+        let list = endpoint(HttpMethod::Get, "/pets");
+        let response = respond(list, None);
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            serde_json::from_str::<Value>(&response.body).unwrap(),
+            json!([{"id": 1, "name": "Rex"}])
+        );
+        assert!(response
+            .headers
+            .contains(&("Content-Type".to_string(), "application/json".to_string())));
+
+        // Undeclared statuses fall back to the default response
+        let response = respond(list, Some(503));
+        assert_eq!(response.status, 503);
+        let sample = list.responses["default"].content[0].example_value(0, false);
+        assert_eq!(serde_json::from_str::<Value>(&response.body).ok(), sample);
+
+        // The lowest success wins over the order of declaration
+        let create = endpoint(HttpMethod::Post, "/pets");
+        let response = respond(create, None);
+        assert_eq!(response.status, 201);
+        assert!(response.body.contains("\"name\""));
+        let response = respond(create, Some(400));
+        assert_eq!((response.status, response.body.as_str()), (400, ""));
+        let response = respond(create, Some(418));
+        assert_eq!(response.status, 500);
+        assert!(response
+            .body
+            .contains("POST /pets declares no 418 response"));
+
+        let get = endpoint(HttpMethod::Get, "/pets/{petId}");
+        assert_eq!(respond(get, None).status, 200);
+        assert!(respond(get, Some(404)).body.contains("No such pet"));
+
+        let mine = respond(endpoint(HttpMethod::Get, "/pets/mine"), None);
+        assert_eq!(mine.body, "Only mine");
+    }
+
+    #[test]
+    fn test_preferred_status() {
+        let headers = |value: &str| vec![("Prefer".to_string(), value.to_string())];
+        assert_eq!(preferred_status(&headers("code=404")), Some(404));
+        assert_eq!(
+            preferred_status(&headers("dynamic=true, code=500")),
+            Some(500)
+        );
+        assert_eq!(preferred_status(&headers("code=x")), None);
+        assert_eq!(preferred_status(&[]), None);
+    }
+
+    #[test]
+    fn test_handle_unrouted() {
+        let spec = spec();
+        let (response, endpoint) = handle(&spec, "DELETE", "/pets/7", &[]);
+        assert_eq!((response.status, endpoint), (405, None));
+        assert!(response
+            .headers
+            .contains(&("Allow".to_string(), "GET".to_string())));
+
+        let (response, _) = handle(&spec, "OPTIONS", "/pets", &[]);
+        assert_eq!(response.status, 204);
+        assert!(response.headers.contains(&(
+            "Access-Control-Allow-Methods".to_string(),
+            "GET, POST".to_string()
+        )));
+
+        let (response, _) = handle(&spec, "GET", "/owners?x=1", &[]);
+        assert_eq!(response.status, 404);
+        assert!(response.body.contains("No operation matches GET /owners"));
+    }
+
+    #[test]
+    fn test_serve() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (sender, log) = mpsc::channel();
+        thread::spawn(move || serve(&server, &spec(), &sender));
+
+        let response = ureq::post(&format!("{}/v1/pets", url))
+            .set("Prefer", "code=400")
+            .send_string("{\"name\": \"Rex\"}");
+        let Err(ureq::Error::Status(400, _)) = response else {
+            panic!("expected a 400 response");
+        };
+        let body = ureq::get(&format!("{}/pets/mine", url))
+            .call()
+            .unwrap()
+            .into_string()
+            .unwrap();
+        assert_eq!(body, "Only mine");
+
+        let entry = log.recv().unwrap();
+        assert_eq!(entry.method, "POST");
+        assert_eq!(entry.url, "/v1/pets");
+        assert_eq!(entry.operation.as_deref(), Some("POST /pets"));
+        assert_eq!(entry.request_body, "{\"name\": \"Rex\"}");
+        assert_eq!(entry.response.status, 400);
+        assert_eq!(entry.http_method(), Some(HttpMethod::Post));
+        assert_eq!(log.recv().unwrap().response.status, 200);
+    }
+
+    #[test]
+    fn test_log_follows_new_entries() {
+        let entry = |url: &str| LogEntry {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            operation: None,
+            request_headers: Vec::new(),
+            request_body: String::new(),
+            response: MockResponse::new(404, None, String::new()),
+            elapsed: Duration::ZERO,
+        };
+        let mut log = MockLog::new(
+            "Pets".to_string(),
+            String::new(),
+            &LayoutConfig::default(),
+            Theme::dark(),
+        );
+        log.push(entry("/a"));
+        log.push(entry("/b"));
+        assert_eq!(log.selected_entry().unwrap().url, "/b");
+
+        // Looking at an older entry keeps it selected
+        log.previous();
+        log.push(entry("/c"));
+        assert_eq!(log.selected_entry().unwrap().url, "/a");
+        log.next();
+        log.next();
+        log.next();
+        assert_eq!(log.selected_entry().unwrap().url, "/c");
+
+        log.clear();
+        assert_eq!(log.selected_entry(), None);
+        log.push(entry("/d"));
+        assert_eq!(log.selected_entry().unwrap().url, "/d");
+    }
+}
@@ -1,7 +1,8 @@
 use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum HttpMethod {
+    #[default]
     Get,
     Post,
     Put,
@@ -12,6 +13,19 @@ pub enum HttpMethod {
     Trace,
 }
 
+impl HttpMethod {
+    pub const ALL: [HttpMethod; 8] = [
+        HttpMethod::Get,
+        HttpMethod::Post,
+        HttpMethod::Put,
+        HttpMethod::Patch,
+        HttpMethod::Delete,
+        HttpMethod::Head,
+        HttpMethod::Options,
+        HttpMethod::Trace,
+    ];
+}
+
 impl std::fmt::Display for HttpMethod {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -27,9 +41,10 @@ impl std::fmt::Display for HttpMethod {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub enum ParameterLocation {
     Path,
+    #[default]
     Query,
     Header,
     Cookie,
@@ -46,50 +61,1219 @@ impl std::fmt::Display for ParameterLocation {
     }
 }
 
+/// How a parameter value is serialized, from the `style` field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParameterStyle {
+    Matrix,
+    Label,
+    Simple,
+    #[default]
+    Form,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
+impl std::fmt::Display for ParameterStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterStyle::Matrix => f.write_str("matrix"),
+            ParameterStyle::Label => f.write_str("label"),
+            ParameterStyle::Simple => f.write_str("simple"),
+            ParameterStyle::Form => f.write_str("form"),
+            ParameterStyle::SpaceDelimited => f.write_str("spaceDelimited"),
+            ParameterStyle::PipeDelimited => f.write_str("pipeDelimited"),
+            ParameterStyle::DeepObject => f.write_str("deepObject"),
+        }
+    }
+}
+
+// Sample values used to illustrate serialization styles, as in the specification
+const SAMPLE_ARRAY: [&str; 3] = ["blue", "black", "brown"];
+const SAMPLE_OBJECT: [(&str, &str); 3] = [("R", "100"), ("G", "200"), ("B", "150")];
+
+/// The broad shape of a value, which decides how styles serialize it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValueKind {
+    #[default]
+    Primitive,
+    Array,
+    Object,
+}
+
+/// A lower or upper limit of a numeric schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bound {
+    pub value: String,
+    pub exclusive: bool,
+}
+
+/// Validation keywords of a schema, rendered next to parameters and headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    pub format: Option<String>,
+    pub minimum: Option<Bound>,
+    pub maximum: Option<Bound>,
+    pub multiple_of: Option<String>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub pattern: Option<String>,
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>,
+    pub unique_items: bool,
+    pub enum_values: Vec<String>,
+    pub default: Option<String>,
+    pub nullable: bool,
+    pub read_only: bool,
+    pub write_only: bool,
+}
+
+impl Constraints {
+    /// Short human-readable descriptions, e.g. `1 ≤ x ≤ 100` or `default: 20`.
+    /// The format is left out as it belongs next to the type.
+    pub fn describe(&self) -> Vec<String> {
+        let mut parts = Vec::new();
+
+        let lower = self.minimum.as_ref().map(|b| {
+            let op = if b.exclusive { "<" } else { "≤" };
+            (b.value.as_str(), op)
+        });
+        let upper = self.maximum.as_ref().map(|b| {
+            let op = if b.exclusive { "<" } else { "≤" };
+            (b.value.as_str(), op)
+        });
+        match (lower, upper) {
+            (Some((min, lo)), Some((max, hi))) => {
+                parts.push(format!("{} {} x {} {}", min, lo, hi, max))
+            }
+            (Some((min, "<")), None) => parts.push(format!("> {}", min)),
+            (Some((min, _)), None) => parts.push(format!("≥ {}", min)),
+            (None, Some((max, op))) => parts.push(format!("{} {}", op, max)),
+            (None, None) => {}
+        }
+        if let Some(step) = &self.multiple_of {
+            parts.push(format!("multiple of {}", step));
+        }
+        parts.extend(range("length", self.min_length, self.max_length));
+        if let Some(pattern) = &self.pattern {
+            parts.push(format!("pattern /{}/", pattern));
+        }
+        parts.extend(range("items", self.min_items, self.max_items));
+        if self.unique_items {
+            parts.push("unique items".to_string());
+        }
+        if !self.enum_values.is_empty() {
+            parts.push(format!("one of: {}", self.enum_values.join(" | ")));
+        }
+        if let Some(default) = &self.default {
+            parts.push(format!("default: {}", default));
+        }
+        if self.nullable {
+            parts.push("nullable".to_string());
+        }
+        if self.read_only {
+            parts.push("read-only".to_string());
+        }
+        if self.write_only {
+            parts.push("write-only".to_string());
+        }
+        parts
+    }
+}
+
+fn range(label: &str, min: Option<usize>, max: Option<usize>) -> Option<String> {
+    match (min, max) {
+        (Some(min), Some(max)) if min == max => Some(format!("{} = {}", label, min)),
+        (Some(min), Some(max)) => Some(format!("{} {}..{}", label, min, max)),
+        (Some(min), None) => Some(format!("{} ≥ {}", label, min)),
+        (None, Some(max)) => Some(format!("{} ≤ {}", label, max)),
+        (None, None) => None,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Parameter {
     pub name: String,
     pub location: ParameterLocation,
     pub description: Option<String>,
     pub required: bool,
+    pub deprecated: bool,
     pub schema_type: Option<String>,
+    pub constraints: Constraints,
+    pub value_kind: ValueKind,
+    pub style: ParameterStyle,
+    /// Effective `explode`, defaulting to true for the form style only.
+    pub explode: bool,
+    pub allow_reserved: bool,
+    pub allow_empty_value: bool,
+    /// Media types of a parameter described with `content` instead of `schema`.
+    pub content: Vec<MediaType>,
+    pub examples: Vec<Example>,
+    pub extensions: Extensions,
+    pub schema_extensions: Extensions,
+    /// Name of the `components.parameters` entry this was `$ref`ed from.
+    pub reference: Option<String>,
+    /// Name of the schema component behind `schema_type`, if any.
+    pub schema_ref: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+impl Default for Parameter {
+    /// A query parameter with the default form style, which explodes.
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            location: ParameterLocation::Query,
+            description: None,
+            required: false,
+            deprecated: false,
+            schema_type: None,
+            constraints: Constraints::default(),
+            value_kind: ValueKind::Primitive,
+            style: ParameterStyle::Form,
+            explode: true,
+            allow_reserved: false,
+            allow_empty_value: false,
+            content: Vec::new(),
+            examples: Vec::new(),
+            extensions: Extensions::new(),
+            schema_extensions: Extensions::new(),
+            reference: None,
+            schema_ref: None,
+        }
+    }
+}
+
+impl Parameter {
+    pub fn references(&self) -> Vec<ComponentRef> {
+        let own = self
+            .reference
+            .as_ref()
+            .map(|name| ComponentRef::new(ComponentKind::Parameter, name));
+        let schema = self
+            .schema_ref
+            .as_ref()
+            .map(|name| ComponentRef::new(ComponentKind::Schema, name));
+        own.into_iter()
+            .chain(schema)
+            .chain(media_references(&self.content))
+            .collect()
+    }
+
+    /// The default style for a location, as defined by the specification.
+    pub fn default_style(location: &ParameterLocation) -> ParameterStyle {
+        match location {
+            ParameterLocation::Path | ParameterLocation::Header => ParameterStyle::Simple,
+            ParameterLocation::Query | ParameterLocation::Cookie => ParameterStyle::Form,
+        }
+    }
+
+    /// How a sample value of this parameter looks on the wire, e.g.
+    /// `?ids=blue&ids=black` for an exploded query array.
+    pub fn wire_example(&self) -> Option<String> {
+        if !self.content.is_empty() {
+            return None;
+        }
+        let name = self.name.as_str();
+        let explode = self.explode;
+        let values = SAMPLE_ARRAY;
+        let pairs = SAMPLE_OBJECT;
+        let flat = |sep: &str, kv: &str| -> String {
+            pairs
+                .iter()
+                .map(|(k, v)| format!("{}{}{}", k, kv, v))
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+
+        let value = match (self.style, self.value_kind) {
+            (ParameterStyle::Matrix, ValueKind::Primitive) => format!(";{}={}", name, values[0]),
+            (ParameterStyle::Matrix, ValueKind::Array) if explode => {
+                values.iter().map(|v| format!(";{}={}", name, v)).collect()
+            }
+            (ParameterStyle::Matrix, ValueKind::Array) => {
+                format!(";{}={}", name, values.join(","))
+            }
+            (ParameterStyle::Matrix, ValueKind::Object) if explode => {
+                format!(";{}", flat(";", "="))
+            }
+            (ParameterStyle::Matrix, ValueKind::Object) => format!(";{}={}", name, flat(",", ",")),
+            (ParameterStyle::Label, ValueKind::Primitive) => format!(".{}", values[0]),
+            (ParameterStyle::Label, ValueKind::Array) => format!(".{}", values.join(".")),
+            (ParameterStyle::Label, ValueKind::Object) if explode => {
+                format!(".{}", flat(".", "="))
+            }
+            (ParameterStyle::Label, ValueKind::Object) => format!(".{}", flat(".", ".")),
+            (ParameterStyle::Simple, ValueKind::Primitive) => values[0].to_string(),
+            (ParameterStyle::Simple, ValueKind::Array) => values.join(","),
+            (ParameterStyle::Simple, ValueKind::Object) if explode => flat(",", "="),
+            (ParameterStyle::Simple, ValueKind::Object) => flat(",", ","),
+            (ParameterStyle::Form, ValueKind::Primitive) => format!("{}={}", name, values[0]),
+            (ParameterStyle::Form, ValueKind::Array) if explode => values
+                .iter()
+                .map(|v| format!("{}={}", name, v))
+                .collect::<Vec<_>>()
+                .join("&"),
+            (ParameterStyle::Form, ValueKind::Array) => format!("{}={}", name, values.join(",")),
+            (ParameterStyle::Form, ValueKind::Object) if explode => flat("&", "="),
+            (ParameterStyle::Form, ValueKind::Object) => format!("{}={}", name, flat(",", ",")),
+            (ParameterStyle::SpaceDelimited, ValueKind::Array) => {
+                format!("{}={}", name, values.join("%20"))
+            }
+            (ParameterStyle::PipeDelimited, ValueKind::Array) => {
+                format!("{}={}", name, values.join("|"))
+            }
+            (ParameterStyle::DeepObject, ValueKind::Object) => pairs
+                .iter()
+                .map(|(k, v)| format!("{}[{}]={}", name, k, v))
+                .collect::<Vec<_>>()
+                .join("&"),
+            // Combinations the specification leaves undefined
+            _ => return None,
+        };
+
+        Some(match self.location {
+            ParameterLocation::Query => format!("?{}", value),
+            ParameterLocation::Header => format!("{}: {}", name, value),
+            ParameterLocation::Cookie => format!("Cookie: {}", value),
+            ParameterLocation::Path => value,
+        })
+    }
+}
+
+/// Specification extensions (`x-*` keys) in declaration order.
+pub type Extensions = Vec<(String, serde_json::Value)>;
+
+/// A named example from `example`, `examples` or the schema's `example`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    /// The example itself; for `externalValue` this is filled in from local files.
+    pub value: Option<serde_json::Value>,
+    pub external_value: Option<String>,
+}
+
+/// A content type of a request or response body and its schema.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MediaType {
+    pub content_type: String,
+    pub schema: Option<String>,
+    pub examples: Vec<Example>,
+    /// Generated from the schema when the spec has no examples.
+    pub sample: Option<Sample>,
+    pub schema_extensions: Extensions,
+    /// Name of the schema component behind `schema`, if any.
+    pub schema_ref: Option<String>,
+    /// The schema as declared, with a top-level `$ref` resolved.
+    pub schema_json: Option<serde_json::Value>,
+    /// Properties declared as `format: binary`, sent as files in multipart
+    /// bodies.
+    pub file_fields: Vec<String>,
+}
+
+impl MediaType {
+    /// The `index`-th example (wrapping around), or the generated sample.
+    pub fn example_value(&self, index: usize, required_only: bool) -> Option<serde_json::Value> {
+        if !self.examples.is_empty() {
+            return self.examples[index % self.examples.len()].value.clone();
+        }
+        self.sample.as_ref().map(|sample| {
+            if required_only {
+                sample.required.clone()
+            } else {
+                sample.all.clone()
+            }
+        })
+    }
+}
+
+/// A payload synthesized from a schema, with and without optional fields.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sample {
+    pub all: serde_json::Value,
+    pub required: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct RequestBody {
     pub description: Option<String>,
     pub required: bool,
-    pub content_types: Vec<String>,
-    pub schema: Option<String>,
+    pub content: Vec<MediaType>,
+    pub extensions: Extensions,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Header {
+    pub name: String,
+    pub description: Option<String>,
+    pub required: bool,
+    pub schema_type: Option<String>,
+    pub constraints: Constraints,
+}
+
+/// The operation a response link points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    OperationId(String),
+    /// A JSON pointer such as `#/paths/~1users~1{id}/get`.
+    OperationRef(String),
+}
+
+impl std::fmt::Display for LinkTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinkTarget::OperationId(id) => f.write_str(id),
+            LinkTarget::OperationRef(reference) => f.write_str(reference),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub name: String,
+    pub target: LinkTarget,
+    pub description: Option<String>,
+    /// Parameters of the target operation and the runtime expressions filling them.
+    pub parameters: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct Response {
     pub description: String,
-    pub content_types: Vec<String>,
-    pub schema: Option<String>,
+    pub content: Vec<MediaType>,
+    pub headers: Vec<Header>,
+    pub links: Vec<Link>,
+    /// Name of the `components.responses` entry this was `$ref`ed from.
+    pub reference: Option<String>,
+    pub extensions: Extensions,
+}
+
+impl Response {
+    pub fn references(&self) -> Vec<ComponentRef> {
+        let own = self
+            .reference
+            .as_ref()
+            .map(|name| ComponentRef::new(ComponentKind::Response, name));
+        own.into_iter()
+            .chain(media_references(&self.content))
+            .collect()
+    }
+}
+
+fn media_references(content: &[MediaType]) -> impl Iterator<Item = ComponentRef> + '_ {
+    content
+        .iter()
+        .filter_map(|media| media.schema_ref.as_ref())
+        .map(|name| ComponentRef::new(ComponentKind::Schema, name))
+}
+
+/// The kinds of `components` entries the detail pane can jump to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentKind {
+    Schema,
+    Parameter,
+    Response,
+}
+
+impl std::fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComponentKind::Schema => f.write_str("Schema"),
+            ComponentKind::Parameter => f.write_str("Parameter"),
+            ComponentKind::Response => f.write_str("Response"),
+        }
+    }
+}
+
+/// A `$ref` to a component, by kind and name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentRef {
+    pub kind: ComponentKind,
+    pub name: String,
+}
+
+impl ComponentRef {
+    pub fn new(kind: ComponentKind, name: &str) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ComponentRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.kind, self.name)
+    }
+}
+
+/// An entry of `components.schemas`, `components.parameters` or
+/// `components.responses`.
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub name: String,
+    pub definition: Definition,
 }
 
 #[derive(Debug, Clone)]
+pub enum Definition {
+    Schema(Box<SchemaDefinition>),
+    Parameter(Box<Parameter>),
+    Response(Response),
+}
+
+impl Component {
+    pub fn kind(&self) -> ComponentKind {
+        match self.definition {
+            Definition::Schema(_) => ComponentKind::Schema,
+            Definition::Parameter(_) => ComponentKind::Parameter,
+            Definition::Response(_) => ComponentKind::Response,
+        }
+    }
+
+    /// Components this one refers to, in display order without repeats.
+    pub fn references(&self) -> Vec<ComponentRef> {
+        let refs = match &self.definition {
+            Definition::Schema(schema) => schema
+                .properties
+                .iter()
+                .chain(&schema.items)
+                .chain(&schema.variants)
+                .filter_map(|p| p.schema_ref.as_ref())
+                .map(|name| ComponentRef::new(ComponentKind::Schema, name))
+                .collect(),
+            Definition::Parameter(param) => param.references(),
+            // The response itself is what's being shown
+            Definition::Response(response) => media_references(&response.content).collect(),
+        };
+        without_repeats(refs)
+    }
+}
+
+fn without_repeats(refs: Vec<ComponentRef>) -> Vec<ComponentRef> {
+    let mut unique: Vec<ComponentRef> = Vec::new();
+    for reference in refs {
+        if !unique.contains(&reference) {
+            unique.push(reference);
+        }
+    }
+    unique
+}
+
+/// How a schema combines its variants: `oneOf`, `anyOf` or `allOf`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Composition {
+    One,
+    Any,
+    All,
+}
+
+impl std::fmt::Display for Composition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Composition::One => f.write_str("One of"),
+            Composition::Any => f.write_str("Any of"),
+            Composition::All => f.write_str("All of"),
+        }
+    }
+}
+
+/// The outline of a schema component: its own type and constraints plus one
+/// level of properties, items or variants.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDefinition {
+    pub description: Option<String>,
+    pub schema_type: Option<String>,
+    pub constraints: Constraints,
+    pub properties: Vec<Property>,
+    pub items: Option<Property>,
+    pub composition: Option<Composition>,
+    pub variants: Vec<Property>,
+    pub extensions: Extensions,
+}
+
+/// A property, array item or variant of a schema.
+#[derive(Debug, Clone, Default)]
+pub struct Property {
+    pub name: String,
+    pub schema_type: Option<String>,
+    /// Name of the schema component behind `schema_type`, if any.
+    pub schema_ref: Option<String>,
+    pub required: bool,
+    pub description: Option<String>,
+    pub constraints: Constraints,
+    /// Extensions of an inline schema; referenced ones show their own.
+    pub extensions: Extensions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OAuthFlowKind {
+    Implicit,
+    Password,
+    ClientCredentials,
+    AuthorizationCode,
+}
+
+impl std::fmt::Display for OAuthFlowKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OAuthFlowKind::Implicit => f.write_str("implicit"),
+            OAuthFlowKind::Password => f.write_str("password"),
+            OAuthFlowKind::ClientCredentials => f.write_str("clientCredentials"),
+            OAuthFlowKind::AuthorizationCode => f.write_str("authorizationCode"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthFlow {
+    pub kind: OAuthFlowKind,
+    pub authorization_url: Option<String>,
+    pub token_url: Option<String>,
+    pub refresh_url: Option<String>,
+    /// Scope names and their descriptions.
+    pub scopes: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecuritySchemeKind {
+    ApiKey {
+        name: String,
+        location: ParameterLocation,
+    },
+    Http {
+        scheme: String,
+        bearer_format: Option<String>,
+    },
+    OAuth2 {
+        flows: Vec<OAuthFlow>,
+    },
+    OpenIdConnect {
+        url: String,
+    },
+}
+
+impl std::fmt::Display for SecuritySchemeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecuritySchemeKind::ApiKey { name, location } => {
+                write!(f, "API key in {} \"{}\"", location, name)
+            }
+            SecuritySchemeKind::Http {
+                scheme,
+                bearer_format: Some(format),
+            } => write!(f, "HTTP {} ({})", scheme, format),
+            SecuritySchemeKind::Http { scheme, .. } => write!(f, "HTTP {}", scheme),
+            SecuritySchemeKind::OAuth2 { .. } => f.write_str("OAuth2"),
+            SecuritySchemeKind::OpenIdConnect { .. } => f.write_str("OpenID Connect"),
+        }
+    }
+}
+
+/// An entry of `components.securitySchemes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityScheme {
+    pub name: String,
+    pub kind: SecuritySchemeKind,
+    pub description: Option<String>,
+}
+
+/// A scheme referenced by a security requirement, with the scopes it needs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemeRequirement {
+    pub scheme: String,
+    pub scopes: Vec<String>,
+}
+
+/// One alternative of a `security` list: all of its schemes must be satisfied.
+/// An empty requirement means anonymous access is allowed.
+pub type SecurityRequirement = Vec<SchemeRequirement>;
+
+/// Whether an endpoint is served by the API or called by it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EndpointKind {
+    #[default]
+    Operation,
+    /// A request the API sends after `method path` was called, to a URL given
+    /// by a runtime expression (kept in `Endpoint::path`).
+    Callback {
+        name: String,
+        method: HttpMethod,
+        path: String,
+    },
+    /// An OpenAPI 3.1 webhook; `Endpoint::path` holds its name.
+    Webhook,
+}
+
+impl std::fmt::Display for EndpointKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EndpointKind::Operation => write!(f, "Operation"),
+            EndpointKind::Callback { name, method, path } => {
+                write!(f, "Callback {} of {} {}", name, method, path)
+            }
+            EndpointKind::Webhook => write!(f, "Webhook"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct Endpoint {
+    pub kind: EndpointKind,
     pub method: HttpMethod,
+    /// The URL path; a runtime expression for callbacks and the name for webhooks.
     pub path: String,
     pub summary: Option<String>,
     pub description: Option<String>,
-    #[allow(dead_code)]
     pub operation_id: Option<String>,
-    #[allow(dead_code)]
     pub tags: Vec<String>,
+    pub deprecated: bool,
+    /// Path- or operation-level servers overriding `ApiSpec::servers`.
+    pub servers: Vec<Server>,
+    /// Effective security: the operation's own `security` if present, otherwise
+    /// the global default. Empty means no authentication is required.
+    pub security: Vec<SecurityRequirement>,
     pub parameters: Vec<Parameter>,
     pub request_body: Option<RequestBody>,
     pub responses: BTreeMap<String, Response>,
+    pub extensions: Extensions,
+    /// Extensions of the path item the operation belongs to.
+    pub path_extensions: Extensions,
 }
 
-#[derive(Debug, Clone)]
+impl Endpoint {
+    /// Whether the endpoint can be called without credentials.
+    pub fn allows_anonymous(&self) -> bool {
+        self.security.is_empty() || self.security.iter().any(|req| req.is_empty())
+    }
+
+    pub fn uses_scheme(&self, scheme: &str) -> bool {
+        self.security
+            .iter()
+            .flatten()
+            .any(|req| req.scheme.eq_ignore_ascii_case(scheme))
+    }
+
+    pub fn requires_scope(&self, scope: &str) -> bool {
+        self.security
+            .iter()
+            .flatten()
+            .flat_map(|req| &req.scopes)
+            .any(|s| s.eq_ignore_ascii_case(scope))
+    }
+
+    /// Components the endpoint refers to, in display order without repeats.
+    pub fn references(&self) -> Vec<ComponentRef> {
+        let params = self.parameters.iter().flat_map(Parameter::references);
+        let body = self
+            .request_body
+            .iter()
+            .flat_map(|body| media_references(&body.content));
+        let responses = self.responses.values().flat_map(Response::references);
+        without_repeats(params.chain(body).chain(responses).collect())
+    }
+
+    /// Whether the operation or its path item has the extension `name`, and,
+    /// if `value` is given, whether it has that value (strings unquoted).
+    pub fn has_extension(&self, name: &str, value: Option<&str>) -> bool {
+        self.extensions
+            .iter()
+            .chain(&self.path_extensions)
+            .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+            .any(|(_, v)| match value {
+                None => true,
+                Some(value) => match v {
+                    serde_json::Value::String(s) => s.eq_ignore_ascii_case(value),
+                    other => other.to_string().eq_ignore_ascii_case(value),
+                },
+            })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Contact {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct License {
+    pub name: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerVariable {
+    pub name: String,
+    pub default: String,
+    /// Allowed values; empty when any value is accepted.
+    pub enum_values: Vec<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Server {
+    /// URL template, possibly containing `{variable}` placeholders.
+    pub url: String,
+    pub description: Option<String>,
+    pub variables: Vec<ServerVariable>,
+}
+
+impl Server {
+    /// Substitutes the server variables, using `values` where given and the
+    /// declared defaults otherwise.
+    pub fn resolve_url(&self, values: &BTreeMap<String, String>) -> String {
+        let mut url = self.url.clone();
+        for variable in &self.variables {
+            let value = values.get(&variable.name).unwrap_or(&variable.default);
+            url = url.replace(&format!("{{{}}}", variable.name), value);
+        }
+        url
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Tag {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ApiSpec {
     pub title: String,
     pub version: String,
-    #[allow(dead_code)]
     pub description: Option<String>,
+    pub terms_of_service: Option<String>,
+    pub contact: Option<Contact>,
+    pub license: Option<License>,
+    pub servers: Vec<Server>,
+    /// Global security requirements, applied to operations without their own.
+    pub security: Vec<SecurityRequirement>,
+    pub security_schemes: Vec<SecurityScheme>,
+    /// Tags declared at the top level of the spec, in declaration order.
+    pub tags: Vec<Tag>,
+    /// Number of entries in `components.schemas`.
+    pub schema_count: usize,
     pub endpoints: Vec<Endpoint>,
+    /// Extensions of the document root and its `info` object.
+    pub extensions: Extensions,
+    /// Schemas, parameters and responses declared under `components`.
+    pub components: Vec<Component>,
+    /// `components.schemas` as declared, to resolve the `$ref`s of
+    /// `MediaType::schema_json`.
+    pub schemas: BTreeMap<String, serde_json::Value>,
+}
+
+/// Operation counts shown on the overview screen.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecStats {
+    pub operations: usize,
+    /// Methods in `HttpMethod::ALL` order, only those that occur.
+    pub by_method: Vec<(HttpMethod, usize)>,
+    /// Declared tags first, then tags only used by operations, alphabetically.
+    pub by_tag: Vec<(String, usize)>,
+    pub untagged: usize,
+    pub deprecated: usize,
+    pub schemas: usize,
+}
+
+impl ApiSpec {
+    /// Index into `endpoints` of the operation a link points at.
+    pub fn find_link_target(&self, target: &LinkTarget) -> Option<usize> {
+        match target {
+            LinkTarget::OperationId(id) => self
+                .endpoints
+                .iter()
+                .position(|e| e.operation_id.as_deref() == Some(id.as_str())),
+            LinkTarget::OperationRef(reference) => {
+                let pointer = reference.strip_prefix("#/paths/")?;
+                let (path, method) = pointer.rsplit_once('/')?;
+                let path = path.replace("~1", "/").replace("~0", "~");
+                self.endpoints.iter().position(|e| {
+                    e.path == path && e.method.to_string().eq_ignore_ascii_case(method)
+                })
+            }
+        }
+    }
+
+    /// Index into `components` of a referenced component.
+    pub fn find_component(&self, reference: &ComponentRef) -> Option<usize> {
+        self.components
+            .iter()
+            .position(|c| c.kind() == reference.kind && c.name == reference.name)
+    }
+
+    pub fn stats(&self) -> SpecStats {
+        let by_method = HttpMethod::ALL
+            .iter()
+            .map(|method| {
+                let count = self
+                    .endpoints
+                    .iter()
+                    .filter(|ep| &ep.method == method)
+                    .count();
+                (method.clone(), count)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect();
+
+        let mut tag_counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for tag in self.endpoints.iter().flat_map(|ep| &ep.tags) {
+            *tag_counts.entry(tag).or_default() += 1;
+        }
+        let mut by_tag: Vec<(String, usize)> = self
+            .tags
+            .iter()
+            .map(|tag| {
+                let count = tag_counts.remove(tag.name.as_str()).unwrap_or(0);
+                (tag.name.clone(), count)
+            })
+            .collect();
+        by_tag.extend(
+            tag_counts
+                .into_iter()
+                .map(|(name, count)| (name.to_string(), count)),
+        );
+
+        SpecStats {
+            operations: self.endpoints.len(),
+            by_method,
+            by_tag,
+            untagged: self
+                .endpoints
+                .iter()
+                .filter(|ep| ep.tags.is_empty())
+                .count(),
+            deprecated: self.endpoints.iter().filter(|ep| ep.deprecated).count(),
+            schemas: self.schema_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn templated_server() -> Server {
+        Server {
+            url: "https://{region}.example.com:{port}/v1".to_string(),
+            description: None,
+            variables: vec![
+                ServerVariable {
+                    name: "region".to_string(),
+                    default: "eu".to_string(),
+                    enum_values: vec!["eu".to_string(), "us".to_string()],
+                    description: None,
+                },
+                ServerVariable {
+                    name: "port".to_string(),
+                    default: "443".to_string(),
+                    enum_values: vec![],
+                    description: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_server_resolve_url_with_defaults() {
+        let url = templated_server().resolve_url(&BTreeMap::new());
+        assert_eq!(url, "https://eu.example.com:443/v1");
+    }
+
+    #[test]
+    fn test_server_resolve_url_with_values() {
+        let values = BTreeMap::from([("region".to_string(), "us".to_string())]);
+        let url = templated_server().resolve_url(&values);
+        assert_eq!(url, "https://us.example.com:443/v1");
+    }
+
+    fn endpoint(method: HttpMethod, tags: &[&str], deprecated: bool) -> Endpoint {
+        Endpoint {
+            method,
+            path: "/x".to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            deprecated,
+            ..Default::default()
+        }
+    }
+
+    fn requirement(scheme: &str, scopes: &[&str]) -> SchemeRequirement {
+        SchemeRequirement {
+            scheme: scheme.to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_endpoint_security_queries() {
+        let endpoint = Endpoint {
+            security: vec![
+                vec![requirement("oauth", &["admin:write", "pets:read"])],
+                vec![requirement("api_key", &[])],
+            ],
+            ..Default::default()
+        };
+
+        assert!(!endpoint.allows_anonymous());
+        assert!(endpoint.uses_scheme("oauth"));
+        assert!(endpoint.uses_scheme("API_KEY"));
+        assert!(!endpoint.uses_scheme("basic"));
+        assert!(endpoint.requires_scope("admin:write"));
+        assert!(!endpoint.requires_scope("admin"));
+    }
+
+    #[test]
+    fn test_endpoint_allows_anonymous() {
+        let public = Endpoint::default();
+        assert!(public.allows_anonymous());
+
+        let optional = Endpoint {
+            security: vec![vec![requirement("oauth", &[])], vec![]],
+            ..Default::default()
+        };
+        assert!(optional.allows_anonymous());
+    }
+
+    #[test]
+    fn test_security_scheme_kind_display() {
+        let api_key = SecuritySchemeKind::ApiKey {
+            name: "X-API-Key".to_string(),
+            location: ParameterLocation::Header,
+        };
+        assert_eq!(api_key.to_string(), "API key in header \"X-API-Key\"");
+
+        let bearer = SecuritySchemeKind::Http {
+            scheme: "bearer".to_string(),
+            bearer_format: Some("JWT".to_string()),
+        };
+        assert_eq!(bearer.to_string(), "HTTP bearer (JWT)");
+    }
+
+    fn styled(
+        location: ParameterLocation,
+        style: ParameterStyle,
+        kind: ValueKind,
+        explode: bool,
+    ) -> Parameter {
+        Parameter {
+            name: "id".to_string(),
+            location,
+            style,
+            value_kind: kind,
+            explode,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_wire_example_query_styles() {
+        use ParameterLocation::Query;
+        let example = |style, kind, explode| styled(Query, style, kind, explode).wire_example();
+
+        assert_eq!(
+            example(ParameterStyle::Form, ValueKind::Primitive, true).as_deref(),
+            Some("?id=blue")
+        );
+        assert_eq!(
+            example(ParameterStyle::Form, ValueKind::Array, true).as_deref(),
+            Some("?id=blue&id=black&id=brown")
+        );
+        assert_eq!(
+            example(ParameterStyle::Form, ValueKind::Array, false).as_deref(),
+            Some("?id=blue,black,brown")
+        );
+        assert_eq!(
+            example(ParameterStyle::Form, ValueKind::Object, true).as_deref(),
+            Some("?R=100&G=200&B=150")
+        );
+        assert_eq!(
+            example(ParameterStyle::SpaceDelimited, ValueKind::Array, false).as_deref(),
+            Some("?id=blue%20black%20brown")
+        );
+        assert_eq!(
+            example(ParameterStyle::PipeDelimited, ValueKind::Array, false).as_deref(),
+            Some("?id=blue|black|brown")
+        );
+        assert_eq!(
+            example(ParameterStyle::DeepObject, ValueKind::Object, true).as_deref(),
+            Some("?id[R]=100&id[G]=200&id[B]=150")
+        );
+        assert_eq!(
+            example(ParameterStyle::DeepObject, ValueKind::Array, true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_wire_example_path_and_header_styles() {
+        use ParameterLocation::{Header, Path};
+
+        let matrix = styled(Path, ParameterStyle::Matrix, ValueKind::Array, true);
+        assert_eq!(
+            matrix.wire_example().as_deref(),
+            Some(";id=blue;id=black;id=brown")
+        );
+        let label = styled(Path, ParameterStyle::Label, ValueKind::Object, false);
+        assert_eq!(label.wire_example().as_deref(), Some(".R.100.G.200.B.150"));
+        let simple = styled(Header, ParameterStyle::Simple, ValueKind::Object, true);
+        assert_eq!(
+            simple.wire_example().as_deref(),
+            Some("id: R=100,G=200,B=150")
+        );
+    }
+
+    #[test]
+    fn test_wire_example_skips_content_parameters() {
+        let param = Parameter {
+            content: vec![MediaType {
+                content_type: "application/json".to_string(),
+                schema: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(param.wire_example(), None);
+    }
+
+    #[test]
+    fn test_constraints_describe() {
+        let constraints = Constraints {
+            format: Some("int32".to_string()),
+            minimum: Some(Bound {
+                value: "1".to_string(),
+                exclusive: false,
+            }),
+            maximum: Some(Bound {
+                value: "100".to_string(),
+                exclusive: true,
+            }),
+            default: Some("20".to_string()),
+            nullable: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            constraints.describe(),
+            vec!["1 ≤ x < 100", "default: 20", "nullable"]
+        );
+
+        let constraints = Constraints {
+            min_length: Some(3),
+            max_length: Some(64),
+            pattern: Some("^[a-z]+$".to_string()),
+            enum_values: vec!["asc".to_string(), "desc".to_string()],
+            max_items: Some(10),
+            unique_items: true,
+            read_only: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            constraints.describe(),
+            vec![
+                "length 3..64",
+                "pattern /^[a-z]+$/",
+                "items ≤ 10",
+                "unique items",
+                "one of: asc | desc",
+                "read-only",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_constraints_describe_one_sided_bounds() {
+        let exclusive_min = Constraints {
+            minimum: Some(Bound {
+                value: "0".to_string(),
+                exclusive: true,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(exclusive_min.describe(), vec!["> 0"]);
+
+        let max = Constraints {
+            maximum: Some(Bound {
+                value: "9.5".to_string(),
+                exclusive: false,
+            }),
+            multiple_of: Some("0.5".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(max.describe(), vec!["≤ 9.5", "multiple of 0.5"]);
+        assert!(Constraints::default().describe().is_empty());
+    }
+
+    #[test]
+    fn test_find_link_target() {
+        let spec = ApiSpec {
+            endpoints: vec![
+                Endpoint {
+                    path: "/users".to_string(),
+                    operation_id: Some("listUsers".to_string()),
+                    ..Default::default()
+                },
+                Endpoint {
+                    method: HttpMethod::Delete,
+                    path: "/users/{id}".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let by_id = LinkTarget::OperationId("listUsers".to_string());
+        assert_eq!(spec.find_link_target(&by_id), Some(0));
+
+        let by_ref = LinkTarget::OperationRef("#/paths/~1users~1{id}/delete".to_string());
+        assert_eq!(spec.find_link_target(&by_ref), Some(1));
+
+        let external = LinkTarget::OperationRef("https://example.com/spec#/paths/~1a/get".into());
+        assert_eq!(spec.find_link_target(&external), None);
+        let missing = LinkTarget::OperationId("nope".to_string());
+        assert_eq!(spec.find_link_target(&missing), None);
+    }
+
+    #[test]
+    fn test_stats() {
+        let spec = ApiSpec {
+            tags: vec![
+                Tag {
+                    name: "pets".to_string(),
+                    description: None,
+                },
+                Tag {
+                    name: "admin".to_string(),
+                    description: None,
+                },
+            ],
+            schema_count: 4,
+            endpoints: vec![
+                endpoint(HttpMethod::Get, &["pets"], false),
+                endpoint(HttpMethod::Get, &["pets", "store"], false),
+                endpoint(HttpMethod::Delete, &[], true),
+            ],
+            ..Default::default()
+        };
+
+        let stats = spec.stats();
+
+        assert_eq!(stats.operations, 3);
+        assert_eq!(
+            stats.by_method,
+            vec![(HttpMethod::Get, 2), (HttpMethod::Delete, 1)]
+        );
+        assert_eq!(
+            stats.by_tag,
+            vec![
+                ("pets".to_string(), 2),
+                ("admin".to_string(), 0),
+                ("store".to_string(), 1)
+            ]
+        );
+        assert_eq!(stats.untagged, 1);
+        assert_eq!(stats.deprecated, 1);
+        assert_eq!(stats.schemas, 4);
+    }
 }
@@ -1,16 +1,24 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use indexmap::IndexMap;
 use openapiv3::{
-    OpenAPI, Operation, Parameter as OApiParameter, ParameterSchemaOrContent, PathItem,
-    ReferenceOr, Schema, StatusCode, Type,
+    OpenAPI, Operation, Parameter as OApiParameter, ParameterSchemaOrContent, PathItem, PathStyle,
+    QueryStyle, ReferenceOr, Schema, SchemaKind, StatusCode, StringFormat, Type,
+    VariantOrUnknownOrEmpty,
 };
+use serde::Deserialize;
 
 use crate::model::{
-    ApiSpec, Endpoint, HttpMethod, Parameter, ParameterLocation, RequestBody, Response,
+    ApiSpec, Bound, Component, Composition, Constraints, Contact, Definition, Endpoint,
+    EndpointKind, Example, Extensions, Header, HttpMethod, License, Link, LinkTarget, MediaType,
+    OAuthFlow, OAuthFlowKind, Parameter, ParameterLocation, ParameterStyle, Property, RequestBody,
+    Response, Sample, SchemaDefinition, SchemeRequirement, SecurityRequirement, SecurityScheme,
+    SecuritySchemeKind, Server, ServerVariable, Tag, ValueKind,
 };
+use crate::sample;
 
 pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<ApiSpec> {
     let path = path.as_ref();
@@ -19,21 +27,60 @@ pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<ApiSpec> {
 
     let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
-    let openapi: OpenAPI = match extension.to_lowercase().as_str() {
-        "json" => serde_json::from_str(&content).with_context(|| "Failed to parse JSON")?,
-        "yaml" | "yml" => serde_yaml::from_str(&content).with_context(|| "Failed to parse YAML")?,
+    let (openapi, webhooks) = match extension.to_lowercase().as_str() {
+        "json" => from_json(&content).with_context(|| "Failed to parse JSON")?,
+        "yaml" | "yml" => from_yaml(&content).with_context(|| "Failed to parse YAML")?,
         _ => {
             // Try YAML first, then JSON
-            serde_yaml::from_str(&content)
-                .or_else(|_| serde_json::from_str(&content))
+            from_yaml(&content)
+                .or_else(|_| from_json(&content))
                 .with_context(|| "Failed to parse file as YAML or JSON")?
         }
     };
 
-    convert_openapi(openapi)
+    let mut spec = convert_openapi(openapi, &webhooks)?;
+    if let Some(base_dir) = path.parent() {
+        load_external_examples(&mut spec, base_dir);
+    }
+    Ok(spec)
+}
+
+/// OpenAPI 3.1 `webhooks`, which `openapiv3` doesn't know about.
+type Webhooks = IndexMap<String, ReferenceOr<PathItem>>;
+
+/// Takes `webhooks` out of the document before handing the rest to
+/// `openapiv3`, adding the empty `paths` that 3.1 allows to leave out.
+fn from_yaml(content: &str) -> Result<(OpenAPI, Webhooks)> {
+    let mut document: serde_yaml::Value = serde_yaml::from_str(content)?;
+    let mut webhooks = None;
+    if let Some(map) = document.as_mapping_mut() {
+        webhooks = map.remove("webhooks");
+        map.entry("paths".into())
+            .or_insert_with(|| serde_yaml::Mapping::new().into());
+    }
+    let webhooks = webhooks.map(serde_yaml::from_value).transpose()?;
+    Ok((
+        serde_yaml::from_value(document)?,
+        webhooks.unwrap_or_default(),
+    ))
+}
+
+fn from_json(content: &str) -> Result<(OpenAPI, Webhooks)> {
+    let mut document: serde_json::Value = serde_json::from_str(content)?;
+    let mut webhooks = None;
+    if let Some(map) = document.as_object_mut() {
+        webhooks = map.remove("webhooks");
+        map.entry("paths")
+            .or_insert_with(|| serde_json::Map::new().into());
+    }
+    let webhooks = webhooks.map(serde_json::from_value).transpose()?;
+    Ok((
+        serde_json::from_value(document)?,
+        webhooks.unwrap_or_default(),
+    ))
 }
 
-fn convert_openapi(openapi: OpenAPI) -> Result<ApiSpec> {
+fn convert_openapi(openapi: OpenAPI, webhooks: &Webhooks) -> Result<ApiSpec> {
     let mut endpoints = Vec::new();
 
     for (path, path_item) in &openapi.paths.paths {
@@ -41,22 +88,74 @@ fn convert_openapi(openapi: OpenAPI) -> Result<ApiSpec> {
             endpoints.extend(extract_endpoints(path, item, &openapi));
         }
     }
+    for (name, path_item) in webhooks {
+        if let ReferenceOr::Item(item) = path_item {
+            let mut webhook = extract_endpoints(name, item, &openapi);
+            for endpoint in &mut webhook {
+                endpoint.kind = EndpointKind::Webhook;
+            }
+            endpoints.extend(webhook);
+        }
+    }
 
-    // Sort endpoints by path, then by method
-    endpoints.sort_by(|a, b| {
-        a.path
-            .cmp(&b.path)
-            .then_with(|| method_order(&a.method).cmp(&method_order(&b.method)))
-    });
+    // Sort endpoints by path, then by method, keeping callbacks right after
+    // their operation and webhooks at the end
+    endpoints.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
 
+    let info = &openapi.info;
     Ok(ApiSpec {
-        title: openapi.info.title.clone(),
-        version: openapi.info.version.clone(),
-        description: openapi.info.description.clone(),
+        title: info.title.clone(),
+        version: info.version.clone(),
+        description: info.description.clone(),
+        terms_of_service: info.terms_of_service.clone(),
+        contact: info.contact.as_ref().map(|c| Contact {
+            name: c.name.clone(),
+            url: c.url.clone(),
+            email: c.email.clone(),
+        }),
+        license: info.license.as_ref().map(|l| License {
+            name: l.name.clone(),
+            url: l.url.clone(),
+        }),
+        servers: openapi.servers.iter().map(convert_server).collect(),
+        security: openapi
+            .security
+            .as_deref()
+            .map(convert_security)
+            .unwrap_or_default(),
+        security_schemes: convert_security_schemes(&openapi),
+        tags: openapi
+            .tags
+            .iter()
+            .map(|t| Tag {
+                name: t.name.clone(),
+                description: t.description.clone(),
+            })
+            .collect(),
+        schema_count: openapi.components.as_ref().map_or(0, |c| c.schemas.len()),
         endpoints,
+        extensions: convert_extensions(&openapi.extensions)
+            .into_iter()
+            .chain(convert_extensions(&info.extensions))
+            .collect(),
+        components: convert_components(&openapi),
+        schemas: openapi
+            .components
+            .iter()
+            .flat_map(|c| &c.schemas)
+            .filter_map(|(name, schema)| Some((name.clone(), serde_json::to_value(schema).ok()?)))
+            .collect(),
     })
 }
 
+fn sort_key(endpoint: &Endpoint) -> (bool, &str, u8, bool) {
+    match &endpoint.kind {
+        EndpointKind::Operation => (false, &endpoint.path, method_order(&endpoint.method), false),
+        EndpointKind::Callback { method, path, .. } => (false, path, method_order(method), true),
+        EndpointKind::Webhook => (true, &endpoint.path, method_order(&endpoint.method), false),
+    }
+}
+
 fn method_order(method: &HttpMethod) -> u8 {
     match method {
         HttpMethod::Get => 0,
@@ -88,30 +187,58 @@ fn extract_endpoints(path: &str, item: &PathItem, openapi: &OpenAPI) -> Vec<Endp
         if let Some(operation) = op {
             endpoints.push(convert_operation(
                 path,
-                method,
+                method.clone(),
                 operation,
-                &item.parameters,
+                item,
                 openapi,
             ));
+            endpoints.extend(extract_callbacks(path, method, operation, openapi));
         }
     }
 
     endpoints
 }
 
+/// The requests an operation's `callbacks` describe, one per expression and method.
+fn extract_callbacks(
+    path: &str,
+    method: HttpMethod,
+    op: &Operation,
+    openapi: &OpenAPI,
+) -> Vec<Endpoint> {
+    let mut endpoints = Vec::new();
+    for (name, callback) in &op.callbacks {
+        for (expression, item) in callback {
+            let mut callback_endpoints = extract_endpoints(expression, item, openapi);
+            for endpoint in &mut callback_endpoints {
+                // Callbacks of callbacks keep the kind they were given
+                if endpoint.kind == EndpointKind::Operation {
+                    endpoint.kind = EndpointKind::Callback {
+                        name: name.clone(),
+                        method: method.clone(),
+                        path: path.to_string(),
+                    };
+                }
+            }
+            endpoints.extend(callback_endpoints);
+        }
+    }
+    endpoints
+}
+
 fn convert_operation(
     path: &str,
     method: HttpMethod,
     op: &Operation,
-    path_params: &[ReferenceOr<OApiParameter>],
+    item: &PathItem,
     openapi: &OpenAPI,
 ) -> Endpoint {
-    // Use HashMap to handle parameter override (operation-level overrides path-level)
-    // Key: (name, location)
-    let mut param_map: HashMap<(String, ParameterLocation), Parameter> = HashMap::new();
+    // Operation-level parameters override path-level ones with the same name and
+    // location; an IndexMap keeps the declaration order for forms and snippets
+    let mut param_map: IndexMap<(String, ParameterLocation), Parameter> = IndexMap::new();
 
     // Add path-level parameters first
-    for param in path_params {
+    for param in &item.parameters {
         if let Some(p) = convert_parameter(param, openapi) {
             param_map.insert((p.name.clone(), p.location.clone()), p);
         }
@@ -128,19 +255,11 @@ fn convert_operation(
 
     let request_body = op.request_body.as_ref().and_then(|rb| {
         let body = resolve_request_body(rb, openapi)?;
-        let content_types: Vec<String> = body.content.keys().cloned().collect();
-        let schema = body
-            .content
-            .values()
-            .next()
-            .and_then(|mt| mt.schema.as_ref())
-            .and_then(|s| schema_type_to_string(s, openapi));
-
         Some(RequestBody {
             description: body.description.clone(),
             required: body.required,
-            content_types,
-            schema,
+            content: convert_content(&body.content, openapi),
+            extensions: convert_extensions(&body.extensions),
         })
     });
 
@@ -152,56 +271,432 @@ fn convert_operation(
         };
 
         if let Some(resp) = resolve_response(response, openapi) {
-            responses.insert(status_code.clone(), convert_response(&status_code, resp, openapi));
+            let mut converted = convert_response(&status_code, resp, openapi);
+            converted.reference = component_name(response, "#/components/responses/");
+            responses.insert(status_code, converted);
         }
     }
 
-    if let Some(resp) = op
-        .responses
-        .default
-        .as_ref()
-        .and_then(|r| resolve_response(r, openapi))
-    {
-        responses.insert("default".to_string(), convert_response("default", resp, openapi));
+    if let Some(response) = &op.responses.default {
+        if let Some(resp) = resolve_response(response, openapi) {
+            let mut converted = convert_response("default", resp, openapi);
+            converted.reference = component_name(response, "#/components/responses/");
+            responses.insert("default".to_string(), converted);
+        }
     }
 
+    // Operation-level servers override path-level ones
+    let servers = if op.servers.is_empty() {
+        &item.servers
+    } else {
+        &op.servers
+    };
+    let servers = servers.iter().map(convert_server).collect();
+
+    // An operation's `security` replaces the global one; `security: []` removes it
+    let security = op
+        .security
+        .as_ref()
+        .or(openapi.security.as_ref())
+        .map(|reqs| convert_security(reqs))
+        .unwrap_or_default();
+
     Endpoint {
+        kind: EndpointKind::Operation,
         method,
         path: path.to_string(),
         summary: op.summary.clone(),
         description: op.description.clone(),
         operation_id: op.operation_id.clone(),
         tags: op.tags.clone(),
+        deprecated: op.deprecated,
+        servers,
+        security,
         parameters,
         request_body,
         responses,
+        extensions: convert_extensions(&op.extensions),
+        path_extensions: convert_extensions(&item.extensions),
+    }
+}
+
+fn convert_server(server: &openapiv3::Server) -> Server {
+    let variables = server
+        .variables
+        .iter()
+        .flatten()
+        .map(|(name, variable)| ServerVariable {
+            name: name.clone(),
+            default: variable.default.clone(),
+            enum_values: variable.enumeration.clone(),
+            description: variable.description.clone(),
+        })
+        .collect();
+
+    Server {
+        url: server.url.clone(),
+        description: server.description.clone(),
+        variables,
+    }
+}
+
+fn convert_security(requirements: &[openapiv3::SecurityRequirement]) -> Vec<SecurityRequirement> {
+    requirements
+        .iter()
+        .map(|req| {
+            req.iter()
+                .map(|(scheme, scopes)| SchemeRequirement {
+                    scheme: scheme.clone(),
+                    scopes: scopes.clone(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn convert_security_schemes(openapi: &OpenAPI) -> Vec<SecurityScheme> {
+    let Some(components) = &openapi.components else {
+        return Vec::new();
+    };
+
+    components
+        .security_schemes
+        .iter()
+        .filter_map(|(name, scheme)| {
+            let ReferenceOr::Item(scheme) = scheme else {
+                return None;
+            };
+            let (kind, description) = match scheme {
+                openapiv3::SecurityScheme::APIKey {
+                    location,
+                    name,
+                    description,
+                    ..
+                } => {
+                    let location = match location {
+                        openapiv3::APIKeyLocation::Query => ParameterLocation::Query,
+                        openapiv3::APIKeyLocation::Header => ParameterLocation::Header,
+                        openapiv3::APIKeyLocation::Cookie => ParameterLocation::Cookie,
+                    };
+                    let kind = SecuritySchemeKind::ApiKey {
+                        name: name.clone(),
+                        location,
+                    };
+                    (kind, description)
+                }
+                openapiv3::SecurityScheme::HTTP {
+                    scheme,
+                    bearer_format,
+                    description,
+                    ..
+                } => {
+                    let kind = SecuritySchemeKind::Http {
+                        scheme: scheme.clone(),
+                        bearer_format: bearer_format.clone(),
+                    };
+                    (kind, description)
+                }
+                openapiv3::SecurityScheme::OAuth2 {
+                    flows, description, ..
+                } => {
+                    let flows = [
+                        (OAuthFlowKind::Implicit, convert_flow(&flows.implicit)),
+                        (OAuthFlowKind::Password, convert_flow(&flows.password)),
+                        (
+                            OAuthFlowKind::ClientCredentials,
+                            convert_flow(&flows.client_credentials),
+                        ),
+                        (
+                            OAuthFlowKind::AuthorizationCode,
+                            convert_flow(&flows.authorization_code),
+                        ),
+                    ]
+                    .into_iter()
+                    .filter_map(|(kind, flow)| flow.map(|f| f.into_flow(kind)))
+                    .collect();
+                    (SecuritySchemeKind::OAuth2 { flows }, description)
+                }
+                openapiv3::SecurityScheme::OpenIDConnect {
+                    open_id_connect_url,
+                    description,
+                    ..
+                } => {
+                    let kind = SecuritySchemeKind::OpenIdConnect {
+                        url: open_id_connect_url.clone(),
+                    };
+                    (kind, description)
+                }
+            };
+            Some(SecurityScheme {
+                name: name.clone(),
+                kind,
+                description: description.clone(),
+            })
+        })
+        .collect()
+}
+
+/// The fields shared by all OAuth2 flow objects.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOAuthFlow {
+    authorization_url: Option<String>,
+    token_url: Option<String>,
+    refresh_url: Option<String>,
+    #[serde(default)]
+    scopes: IndexMap<String, String>,
+}
+
+impl RawOAuthFlow {
+    fn into_flow(self, kind: OAuthFlowKind) -> OAuthFlow {
+        OAuthFlow {
+            kind,
+            authorization_url: self.authorization_url,
+            token_url: self.token_url,
+            refresh_url: self.refresh_url,
+            scopes: self.scopes.into_iter().collect(),
+        }
     }
 }
 
+/// openapiv3 keeps the fields of its flow types private, so read them back
+/// through their serialized form.
+fn convert_flow<T: serde::Serialize>(flow: &Option<T>) -> Option<RawOAuthFlow> {
+    let value = serde_json::to_value(flow.as_ref()?).ok()?;
+    serde_json::from_value(value).ok()
+}
+
 fn convert_parameter(param: &ReferenceOr<OApiParameter>, openapi: &OpenAPI) -> Option<Parameter> {
+    let reference = component_name(param, "#/components/parameters/");
     let param = resolve_parameter(param, openapi)?;
 
-    let (location, parameter_data) = match param {
-        OApiParameter::Path { parameter_data, .. } => (ParameterLocation::Path, parameter_data),
-        OApiParameter::Query { parameter_data, .. } => (ParameterLocation::Query, parameter_data),
-        OApiParameter::Header { parameter_data, .. } => (ParameterLocation::Header, parameter_data),
-        OApiParameter::Cookie { parameter_data, .. } => (ParameterLocation::Cookie, parameter_data),
+    let mut allow_reserved = false;
+    let mut allow_empty_value = false;
+    let (location, parameter_data, style) = match param {
+        OApiParameter::Path {
+            parameter_data,
+            style,
+        } => {
+            let style = match style {
+                PathStyle::Matrix => ParameterStyle::Matrix,
+                PathStyle::Label => ParameterStyle::Label,
+                PathStyle::Simple => ParameterStyle::Simple,
+            };
+            (ParameterLocation::Path, parameter_data, style)
+        }
+        OApiParameter::Query {
+            parameter_data,
+            allow_reserved: reserved,
+            style,
+            allow_empty_value: empty,
+        } => {
+            allow_reserved = *reserved;
+            allow_empty_value = empty.unwrap_or(false);
+            let style = match style {
+                QueryStyle::Form => ParameterStyle::Form,
+                QueryStyle::SpaceDelimited => ParameterStyle::SpaceDelimited,
+                QueryStyle::PipeDelimited => ParameterStyle::PipeDelimited,
+                QueryStyle::DeepObject => ParameterStyle::DeepObject,
+            };
+            (ParameterLocation::Query, parameter_data, style)
+        }
+        OApiParameter::Header { parameter_data, .. } => (
+            ParameterLocation::Header,
+            parameter_data,
+            ParameterStyle::Simple,
+        ),
+        OApiParameter::Cookie { parameter_data, .. } => (
+            ParameterLocation::Cookie,
+            parameter_data,
+            ParameterStyle::Form,
+        ),
     };
 
-    let schema_type = match &parameter_data.format {
-        ParameterSchemaOrContent::Schema(s) => schema_type_to_string(s, openapi),
-        ParameterSchemaOrContent::Content(_) => None,
-    };
+    let (schema_type, constraints, value_kind, content, schema_extensions) =
+        match &parameter_data.format {
+            ParameterSchemaOrContent::Schema(s) => (
+                schema_type_to_string(s, openapi),
+                schema_constraints(s, openapi),
+                value_kind(s, openapi),
+                Vec::new(),
+                schema_extensions(s, openapi),
+            ),
+            ParameterSchemaOrContent::Content(content) => {
+                let content = convert_content(content, openapi);
+                let schema_type = content.first().and_then(|m| m.schema.clone());
+                (
+                    schema_type,
+                    Constraints::default(),
+                    ValueKind::Primitive,
+                    content,
+                    Extensions::new(),
+                )
+            }
+        };
 
     Some(Parameter {
         name: parameter_data.name.clone(),
         location,
         description: parameter_data.description.clone(),
         required: parameter_data.required,
+        deprecated: parameter_data.deprecated.unwrap_or(false),
         schema_type,
+        constraints,
+        value_kind,
+        style,
+        explode: parameter_data
+            .explode
+            .unwrap_or(style == ParameterStyle::Form),
+        allow_reserved,
+        allow_empty_value,
+        content,
+        extensions: convert_extensions(&parameter_data.extensions),
+        schema_extensions,
+        reference,
+        schema_ref: match &parameter_data.format {
+            ParameterSchemaOrContent::Schema(s) => schema_ref(s),
+            ParameterSchemaOrContent::Content(_) => None,
+        },
+        examples: convert_examples(
+            parameter_data.example.as_ref(),
+            &parameter_data.examples,
+            match &parameter_data.format {
+                ParameterSchemaOrContent::Schema(s) => Some(s),
+                ParameterSchemaOrContent::Content(_) => None,
+            },
+            openapi,
+        ),
     })
 }
 
+/// Collects `examples`, or else the single `example`, or else the schema's
+/// own example.
+fn convert_examples(
+    example: Option<&serde_json::Value>,
+    examples: &IndexMap<String, ReferenceOr<openapiv3::Example>>,
+    schema: Option<&ReferenceOr<Schema>>,
+    openapi: &OpenAPI,
+) -> Vec<Example> {
+    if !examples.is_empty() {
+        return examples
+            .iter()
+            .filter_map(|(name, example)| {
+                let example = resolve_ref(example, "#/components/examples/", |name| {
+                    openapi.components.as_ref()?.examples.get(name)
+                })?;
+                Some(Example {
+                    name: name.clone(),
+                    summary: example.summary.clone(),
+                    description: example.description.clone(),
+                    value: example.value.clone(),
+                    external_value: example.external_value.clone(),
+                })
+            })
+            .collect();
+    }
+
+    let schema_example = || {
+        let schema = resolve_ref(schema?, "#/components/schemas/", |name| {
+            openapi.components.as_ref()?.schemas.get(name)
+        })?;
+        schema.schema_data.example.clone()
+    };
+    let (name, value) = match example {
+        Some(value) => ("example", Some(value.clone())),
+        None => ("schema", schema_example()),
+    };
+    value
+        .map(|value| Example {
+            name: name.to_string(),
+            value: Some(value),
+            ..Default::default()
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Reads `externalValue` examples that point at local files, relative to the
+/// spec file. Remote URLs are left for the user to follow.
+fn load_external_examples(spec: &mut ApiSpec, base_dir: &Path) {
+    let load = |example: &mut Example| {
+        let Some(location) = &example.external_value else {
+            return;
+        };
+        if example.value.is_some() || location.contains("://") {
+            return;
+        }
+        let Some(path) = local_file(base_dir, location) else {
+            return;
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            return;
+        };
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let parsed = match extension.to_lowercase().as_str() {
+            "json" => serde_json::from_str(&content).ok(),
+            "yaml" | "yml" => serde_yaml::from_str(&content).ok(),
+            _ => None,
+        };
+        example.value = Some(parsed.unwrap_or(serde_json::Value::String(content)));
+    };
+
+    for endpoint in &mut spec.endpoints {
+        for param in &mut endpoint.parameters {
+            param.examples.iter_mut().for_each(load);
+            for media in &mut param.content {
+                media.examples.iter_mut().for_each(load);
+            }
+        }
+        let bodies = endpoint
+            .request_body
+            .iter_mut()
+            .flat_map(|body| &mut body.content);
+        let responses = endpoint
+            .responses
+            .values_mut()
+            .flat_map(|response| &mut response.content);
+        for media in bodies.chain(responses) {
+            media.examples.iter_mut().for_each(load);
+        }
+    }
+}
+
+/// The file at the relative `location`, if it is inside `base_dir`. Absolute
+/// paths and `..` leading out are refused, so that a spec can't make us read
+/// (or the mock server serve) arbitrary files.
+fn local_file(base_dir: &Path, location: &str) -> Option<PathBuf> {
+    if Path::new(location).is_absolute() {
+        return None;
+    }
+    // The parent of a bare file name is empty
+    let base_dir = if base_dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        base_dir
+    };
+    let base_dir = base_dir.canonicalize().ok()?;
+    let path = base_dir.join(location).canonicalize().ok()?;
+    path.starts_with(&base_dir).then_some(path)
+}
+
+/// Classifies a schema as array, object or primitive, following a single
+/// `$ref` into `components.schemas`.
+fn value_kind(schema: &ReferenceOr<Schema>, openapi: &OpenAPI) -> ValueKind {
+    let schema = resolve_ref(schema, "#/components/schemas/", |name| {
+        openapi.components.as_ref()?.schemas.get(name)
+    });
+    match schema.map(|s| &s.schema_kind) {
+        Some(SchemaKind::Type(Type::Array(_))) => ValueKind::Array,
+        Some(SchemaKind::Type(Type::Object(_)))
+        | Some(SchemaKind::AllOf { .. })
+        | Some(SchemaKind::OneOf { .. })
+        | Some(SchemaKind::AnyOf { .. }) => ValueKind::Object,
+        Some(SchemaKind::Any(any)) if any.typ.as_deref() == Some("array") => ValueKind::Array,
+        Some(SchemaKind::Any(any)) if !any.properties.is_empty() => ValueKind::Object,
+        _ => ValueKind::Primitive,
+    }
+}
+
 /// Resolves a `ReferenceOr<T>` to `&T` by looking up the component if it's a reference.
 fn resolve_ref<'a, T, F>(
     ref_or_item: &'a ReferenceOr<T>,
@@ -223,6 +718,14 @@ where
     }
 }
 
+/// The name a `$ref` points at under `prefix`, e.g. `#/components/parameters/`.
+fn component_name<T>(ref_or_item: &ReferenceOr<T>, prefix: &str) -> Option<String> {
+    match ref_or_item {
+        ReferenceOr::Reference { reference } => reference.strip_prefix(prefix).map(str::to_string),
+        ReferenceOr::Item(_) => None,
+    }
+}
+
 fn resolve_parameter<'a>(
     param: &'a ReferenceOr<OApiParameter>,
     openapi: &'a OpenAPI,
@@ -250,33 +753,429 @@ fn resolve_response<'a>(
     })
 }
 
+fn resolve_header<'a>(
+    header: &'a ReferenceOr<openapiv3::Header>,
+    openapi: &'a OpenAPI,
+) -> Option<&'a openapiv3::Header> {
+    resolve_ref(header, "#/components/headers/", |name| {
+        openapi.components.as_ref()?.headers.get(name)
+    })
+}
+
+fn resolve_link<'a>(
+    link: &'a ReferenceOr<openapiv3::Link>,
+    openapi: &'a OpenAPI,
+) -> Option<&'a openapiv3::Link> {
+    resolve_ref(link, "#/components/links/", |name| {
+        openapi.components.as_ref()?.links.get(name)
+    })
+}
+
 fn convert_response(_status_code: &str, resp: &openapiv3::Response, openapi: &OpenAPI) -> Response {
-    let content_types: Vec<String> = resp.content.keys().cloned().collect();
-    let schema = resp
-        .content
-        .values()
-        .next()
-        .and_then(|mt| mt.schema.as_ref())
-        .and_then(|s| schema_type_to_string(s, openapi));
+    let headers = resp
+        .headers
+        .iter()
+        .filter_map(|(name, header)| {
+            let header = resolve_header(header, openapi)?;
+            let (schema_type, constraints) = match &header.format {
+                ParameterSchemaOrContent::Schema(s) => (
+                    schema_type_to_string(s, openapi),
+                    schema_constraints(s, openapi),
+                ),
+                ParameterSchemaOrContent::Content(_) => (None, Constraints::default()),
+            };
+            Some(Header {
+                name: name.clone(),
+                description: header.description.clone(),
+                required: header.required,
+                schema_type,
+                constraints,
+            })
+        })
+        .collect();
+
+    let links = resp
+        .links
+        .iter()
+        .filter_map(|(name, link)| {
+            let link = resolve_link(link, openapi)?;
+            let target = match &link.operation {
+                openapiv3::LinkOperation::OperationId(id) => LinkTarget::OperationId(id.clone()),
+                openapiv3::LinkOperation::OperationRef(reference) => {
+                    LinkTarget::OperationRef(reference.clone())
+                }
+            };
+            let parameters = link
+                .parameters
+                .iter()
+                .map(|(name, value)| (name.clone(), json_to_string(value)))
+                .collect();
+            Some(Link {
+                name: name.clone(),
+                target,
+                description: link.description.clone(),
+                parameters,
+            })
+        })
+        .collect();
 
     Response {
         description: resp.description.clone(),
-        content_types,
-        schema,
+        content: convert_content(&resp.content, openapi),
+        headers,
+        links,
+        reference: None,
+        extensions: convert_extensions(&resp.extensions),
+    }
+}
+
+fn convert_content(content: &openapiv3::Content, openapi: &OpenAPI) -> Vec<MediaType> {
+    content
+        .iter()
+        .map(|(content_type, media)| {
+            let examples = convert_examples(
+                media.example.as_ref(),
+                &media.examples,
+                media.schema.as_ref(),
+                openapi,
+            );
+            let sample = match &media.schema {
+                Some(schema) if examples.is_empty() => Some(Sample {
+                    all: sample::generate(schema, openapi, false),
+                    required: sample::generate(schema, openapi, true),
+                }),
+                _ => None,
+            };
+            MediaType {
+                content_type: content_type.clone(),
+                schema: media
+                    .schema
+                    .as_ref()
+                    .and_then(|s| schema_type_to_string(s, openapi)),
+                examples,
+                sample,
+                schema_extensions: media
+                    .schema
+                    .as_ref()
+                    .map(|s| schema_extensions(s, openapi))
+                    .unwrap_or_default(),
+                schema_ref: media.schema.as_ref().and_then(schema_ref),
+                schema_json: media.schema.as_ref().and_then(|s| schema_json(s, openapi)),
+                file_fields: media
+                    .schema
+                    .as_ref()
+                    .map(|s| file_fields(s, openapi))
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// The properties of an object schema that are `format: binary` strings.
+fn file_fields(schema: &ReferenceOr<Schema>, openapi: &OpenAPI) -> Vec<String> {
+    let schema = resolve_ref(schema, "#/components/schemas/", |name| {
+        openapi.components.as_ref()?.schemas.get(name)
+    });
+    let Some(SchemaKind::Type(Type::Object(object))) = schema.map(|s| &s.schema_kind) else {
+        return Vec::new();
+    };
+    object
+        .properties
+        .iter()
+        .filter(|(_, property)| {
+            matches!(
+                property,
+                ReferenceOr::Item(property) if matches!(
+                    &property.schema_kind,
+                    SchemaKind::Type(Type::String(string))
+                        if string.format == VariantOrUnknownOrEmpty::Item(StringFormat::Binary)
+                )
+            )
+        })
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+fn schema_json(schema: &ReferenceOr<Schema>, openapi: &OpenAPI) -> Option<serde_json::Value> {
+    let schema = resolve_ref(schema, "#/components/schemas/", |name| {
+        openapi.components.as_ref()?.schemas.get(name)
+    })?;
+    serde_json::to_value(schema).ok()
+}
+
+fn convert_extensions(extensions: &IndexMap<String, serde_json::Value>) -> Extensions {
+    extensions
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+/// Extensions of a schema, following a `$ref` into `components.schemas`.
+fn schema_extensions(schema: &ReferenceOr<Schema>, openapi: &OpenAPI) -> Extensions {
+    resolve_ref(schema, "#/components/schemas/", |name| {
+        openapi.components.as_ref()?.schemas.get(name)
+    })
+    .map(|schema| convert_extensions(&schema.schema_data.extensions))
+    .unwrap_or_default()
+}
+
+/// Renders a JSON value for display, leaving strings unquoted.
+fn json_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn format_name<T: serde::Serialize>(format: &VariantOrUnknownOrEmpty<T>) -> Option<String> {
+    match format {
+        VariantOrUnknownOrEmpty::Item(known) => serde_json::to_value(known)
+            .ok()?
+            .as_str()
+            .map(str::to_string),
+        VariantOrUnknownOrEmpty::Unknown(name) => Some(name.clone()),
+        VariantOrUnknownOrEmpty::Empty => None,
+    }
+}
+
+fn bound<T: ToString>(value: Option<T>, exclusive: bool) -> Option<Bound> {
+    value.map(|v| Bound {
+        value: v.to_string(),
+        exclusive,
+    })
+}
+
+/// Collects the validation keywords of a schema, following a `$ref` into
+/// `components.schemas`.
+fn schema_constraints(schema: &ReferenceOr<Schema>, openapi: &OpenAPI) -> Constraints {
+    resolve_ref(schema, "#/components/schemas/", |name| {
+        openapi.components.as_ref()?.schemas.get(name)
+    })
+    .map(constraints_of)
+    .unwrap_or_default()
+}
+
+fn constraints_of(schema: &Schema) -> Constraints {
+    let data = &schema.schema_data;
+    let mut constraints = Constraints {
+        default: data.default.as_ref().map(json_to_string),
+        nullable: data.nullable,
+        read_only: data.read_only,
+        write_only: data.write_only,
+        ..Default::default()
+    };
+
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::String(s)) => {
+            constraints.format = format_name(&s.format);
+            constraints.pattern = s.pattern.clone();
+            constraints.min_length = s.min_length;
+            constraints.max_length = s.max_length;
+            constraints.enum_values = s.enumeration.iter().flatten().cloned().collect();
+        }
+        SchemaKind::Type(Type::Number(n)) => {
+            constraints.format = format_name(&n.format);
+            constraints.minimum = bound(n.minimum, n.exclusive_minimum);
+            constraints.maximum = bound(n.maximum, n.exclusive_maximum);
+            constraints.multiple_of = n.multiple_of.map(|m| m.to_string());
+            constraints.enum_values = n.enumeration.iter().flatten().map(f64::to_string).collect();
+        }
+        SchemaKind::Type(Type::Integer(i)) => {
+            constraints.format = format_name(&i.format);
+            constraints.minimum = bound(i.minimum, i.exclusive_minimum);
+            constraints.maximum = bound(i.maximum, i.exclusive_maximum);
+            constraints.multiple_of = i.multiple_of.map(|m| m.to_string());
+            constraints.enum_values = i.enumeration.iter().flatten().map(i64::to_string).collect();
+        }
+        SchemaKind::Type(Type::Boolean(b)) => {
+            constraints.enum_values = b
+                .enumeration
+                .iter()
+                .flatten()
+                .map(bool::to_string)
+                .collect();
+        }
+        SchemaKind::Type(Type::Array(a)) => {
+            constraints.min_items = a.min_items;
+            constraints.max_items = a.max_items;
+            constraints.unique_items = a.unique_items;
+        }
+        SchemaKind::Any(any) => {
+            constraints.format = any.format.clone();
+            constraints.pattern = any.pattern.clone();
+            constraints.minimum = bound(any.minimum, any.exclusive_minimum.unwrap_or(false));
+            constraints.maximum = bound(any.maximum, any.exclusive_maximum.unwrap_or(false));
+            constraints.multiple_of = any.multiple_of.map(|m| m.to_string());
+            constraints.min_length = any.min_length;
+            constraints.max_length = any.max_length;
+            constraints.min_items = any.min_items;
+            constraints.max_items = any.max_items;
+            constraints.unique_items = any.unique_items.unwrap_or(false);
+            constraints.enum_values = any.enumeration.iter().map(json_to_string).collect();
+        }
+        _ => {}
     }
+    constraints
 }
 
 fn schema_type_to_string(schema: &ReferenceOr<Schema>, _openapi: &OpenAPI) -> Option<String> {
     match schema {
+        ReferenceOr::Reference { reference } => schema_name(reference),
+        ReferenceOr::Item(schema) => kind_to_string(&schema.schema_kind),
+    }
+}
+
+/// The type of an inline schema; arrays of a schema component read `Name[]`.
+fn kind_to_string(kind: &SchemaKind) -> Option<String> {
+    if let Some(name) = items_ref(kind) {
+        return Some(format!("{}[]", name));
+    }
+    match kind {
+        SchemaKind::Type(t) => Some(type_to_string(t).to_string()),
+        SchemaKind::Any(any) => any.typ.clone(),
+        _ => None,
+    }
+}
+
+fn schema_name(reference: &str) -> Option<String> {
+    reference
+        .strip_prefix("#/components/schemas/")
+        .map(str::to_string)
+}
+
+/// The schema component a schema is, or is an array of.
+fn schema_ref(schema: &ReferenceOr<Schema>) -> Option<String> {
+    match schema {
+        ReferenceOr::Reference { reference } => schema_name(reference),
+        ReferenceOr::Item(schema) => items_ref(&schema.schema_kind),
+    }
+}
+
+fn items_ref(kind: &SchemaKind) -> Option<String> {
+    let items = match kind {
+        SchemaKind::Type(Type::Array(array)) => array.items.as_ref(),
+        SchemaKind::Any(any) => any.items.as_ref(),
+        _ => None,
+    };
+    match items? {
+        ReferenceOr::Reference { reference } => schema_name(reference),
+        ReferenceOr::Item(_) => None,
+    }
+}
+
+/// Schemas, parameters and responses under `components`, in that order.
+fn convert_components(openapi: &OpenAPI) -> Vec<Component> {
+    let Some(components) = &openapi.components else {
+        return Vec::new();
+    };
+    let schemas = components.schemas.iter().filter_map(|(name, schema)| {
+        let ReferenceOr::Item(schema) = schema else {
+            return None;
+        };
+        Some(Component {
+            name: name.clone(),
+            definition: Definition::Schema(Box::new(convert_schema_definition(schema, openapi))),
+        })
+    });
+    let parameters = components.parameters.iter().filter_map(|(name, param)| {
+        Some(Component {
+            name: name.clone(),
+            definition: Definition::Parameter(Box::new(convert_parameter(param, openapi)?)),
+        })
+    });
+    let responses = components.responses.iter().filter_map(|(name, response)| {
+        let response = resolve_response(response, openapi)?;
+        Some(Component {
+            name: name.clone(),
+            definition: Definition::Response(convert_response(name, response, openapi)),
+        })
+    });
+    schemas.chain(parameters).chain(responses).collect()
+}
+
+fn convert_schema_definition(schema: &Schema, openapi: &OpenAPI) -> SchemaDefinition {
+    let mut definition = SchemaDefinition {
+        description: schema.schema_data.description.clone(),
+        schema_type: kind_to_string(&schema.schema_kind),
+        constraints: constraints_of(schema),
+        extensions: convert_extensions(&schema.schema_data.extensions),
+        ..Default::default()
+    };
+
+    let (properties, required, items) = match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => {
+            (Some(&object.properties), &object.required[..], None)
+        }
+        SchemaKind::Type(Type::Array(array)) => (None, &[][..], array.items.as_ref()),
+        SchemaKind::Any(any) => (Some(&any.properties), &any.required[..], any.items.as_ref()),
+        _ => (None, &[][..], None),
+    };
+    for (name, property) in properties.into_iter().flatten() {
+        let required = required.contains(name);
+        definition.properties.push(match property {
+            ReferenceOr::Reference { reference } => {
+                referenced_property(name, required, reference, openapi)
+            }
+            ReferenceOr::Item(property) => inline_property(name, required, property),
+        });
+    }
+    definition.items = items.map(|items| match items {
         ReferenceOr::Reference { reference } => {
-            let name = reference.strip_prefix("#/components/schemas/")?;
-            Some(name.to_string())
+            referenced_property("items", false, reference, openapi)
         }
-        ReferenceOr::Item(schema) => match &schema.schema_kind {
-            openapiv3::SchemaKind::Type(t) => Some(type_to_string(t).to_string()),
-            openapiv3::SchemaKind::Any(any) => any.typ.clone(),
-            _ => None,
-        },
+        ReferenceOr::Item(items) => inline_property("items", false, items),
+    });
+
+    let composition = match &schema.schema_kind {
+        SchemaKind::OneOf { one_of } => Some((Composition::One, one_of)),
+        SchemaKind::AnyOf { any_of } => Some((Composition::Any, any_of)),
+        SchemaKind::AllOf { all_of } => Some((Composition::All, all_of)),
+        SchemaKind::Any(any) if !any.one_of.is_empty() => Some((Composition::One, &any.one_of)),
+        SchemaKind::Any(any) if !any.any_of.is_empty() => Some((Composition::Any, &any.any_of)),
+        SchemaKind::Any(any) if !any.all_of.is_empty() => Some((Composition::All, &any.all_of)),
+        _ => None,
+    };
+    if let Some((composition, variants)) = composition {
+        definition.composition = Some(composition);
+        definition.variants = variants
+            .iter()
+            .map(|variant| match variant {
+                ReferenceOr::Reference { reference } => {
+                    referenced_property("", false, reference, openapi)
+                }
+                ReferenceOr::Item(variant) => inline_property("", false, variant),
+            })
+            .collect();
+    }
+    definition
+}
+
+fn referenced_property(name: &str, required: bool, reference: &str, openapi: &OpenAPI) -> Property {
+    let target = schema_name(reference);
+    let resolved = target
+        .as_deref()
+        .and_then(|target| openapi.components.as_ref()?.schemas.get(target))
+        .and_then(|schema| schema.as_item());
+    Property {
+        name: name.to_string(),
+        schema_type: target.clone().or_else(|| Some(reference.to_string())),
+        schema_ref: target,
+        required,
+        description: resolved.and_then(|s| s.schema_data.description.clone()),
+        constraints: resolved.map(constraints_of).unwrap_or_default(),
+        extensions: Extensions::new(),
+    }
+}
+
+fn inline_property(name: &str, required: bool, schema: &Schema) -> Property {
+    Property {
+        name: name.to_string(),
+        schema_type: kind_to_string(&schema.schema_kind),
+        schema_ref: items_ref(&schema.schema_kind),
+        required,
+        description: schema.schema_data.description.clone(),
+        constraints: constraints_of(schema),
+        extensions: convert_extensions(&schema.schema_data.extensions),
     }
 }
 
@@ -307,6 +1206,530 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_info_metadata() {
+        let spec = parse_file("tests/fixtures/info-metadata.yaml").unwrap();
+
+        assert_eq!(
+            spec.terms_of_service,
+            Some("https://example.com/terms".to_string())
+        );
+        let contact = spec.contact.unwrap();
+        assert_eq!(contact.name, Some("API Support".to_string()));
+        assert_eq!(contact.email, Some("support@example.com".to_string()));
+        let license = spec.license.unwrap();
+        assert_eq!(license.name, "Apache 2.0");
+        assert!(license.url.is_some());
+    }
+
+    #[test]
+    fn test_servers_and_tags() {
+        let spec = parse_file("tests/fixtures/info-metadata.yaml").unwrap();
+
+        assert_eq!(spec.servers.len(), 2);
+        assert_eq!(spec.servers[0].url, "https://api.example.com/v2");
+        assert_eq!(spec.servers[0].description, Some("Production".to_string()));
+        assert_eq!(spec.servers[1].description, None);
+
+        let tags: Vec<_> = spec.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(tags, vec!["books", "authors"]);
+        assert_eq!(
+            spec.tags[0].description,
+            Some("Everything about books".to_string())
+        );
+        assert_eq!(spec.schema_count, 3);
+    }
+
+    #[test]
+    fn test_server_variables() {
+        let spec = parse_file("tests/fixtures/servers.yaml").unwrap();
+
+        assert_eq!(spec.servers.len(), 2);
+        let server = &spec.servers[0];
+        assert_eq!(
+            server.url,
+            "https://{environment}.example.com:{port}/{basePath}"
+        );
+        let names: Vec<_> = server.variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["environment", "port", "basePath"]);
+        assert_eq!(server.variables[0].default, "api");
+        assert_eq!(
+            server.variables[0].enum_values,
+            vec!["api", "staging", "dev"]
+        );
+        assert!(server.variables[1].enum_values.is_empty());
+    }
+
+    #[test]
+    fn test_path_and_operation_servers() {
+        let spec = parse_file("tests/fixtures/servers.yaml").unwrap();
+        let find = |path: &str, method: HttpMethod| {
+            spec.endpoints
+                .iter()
+                .find(|e| e.path == path && e.method == method)
+                .unwrap()
+        };
+
+        assert!(find("/users", HttpMethod::Get).servers.is_empty());
+
+        let files = find("/files", HttpMethod::Get);
+        assert_eq!(files.servers.len(), 1);
+        assert_eq!(files.servers[0].url, "https://files.example.com");
+
+        let upload = find("/files", HttpMethod::Post);
+        assert_eq!(upload.servers[0].url, "https://upload.example.com");
+    }
+
+    #[test]
+    fn test_security_schemes() {
+        let spec = parse_file("tests/fixtures/security.yaml").unwrap();
+
+        let names: Vec<_> = spec
+            .security_schemes
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["api_key", "bearer", "oauth", "oidc"]);
+
+        assert_eq!(
+            spec.security_schemes[0].kind,
+            SecuritySchemeKind::ApiKey {
+                name: "X-API-Key".to_string(),
+                location: ParameterLocation::Header,
+            }
+        );
+
+        let SecuritySchemeKind::OAuth2 { flows } = &spec.security_schemes[2].kind else {
+            panic!("expected an OAuth2 scheme");
+        };
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].kind, OAuthFlowKind::ClientCredentials);
+        assert_eq!(
+            flows[0].token_url.as_deref(),
+            Some("https://auth.example.com/token")
+        );
+        let scopes: Vec<_> = flows[0].scopes.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(scopes, vec!["pets:read", "admin:write"]);
+    }
+
+    #[test]
+    fn test_security_requirements() {
+        let spec = parse_file("tests/fixtures/security.yaml").unwrap();
+        let find = |path: &str, method: HttpMethod| {
+            spec.endpoints
+                .iter()
+                .find(|e| e.path == path && e.method == method)
+                .unwrap()
+        };
+
+        assert_eq!(spec.security.len(), 1);
+
+        // Inherits the global requirement
+        let list = find("/pets", HttpMethod::Get);
+        assert_eq!(list.security, spec.security);
+
+        // Operation override with alternatives
+        let create = find("/pets", HttpMethod::Post);
+        assert_eq!(create.security.len(), 2);
+        assert_eq!(create.security[0][0].scheme, "oauth");
+        assert_eq!(create.security[0][0].scopes, vec!["admin:write"]);
+        assert_eq!(create.security[1][0].scheme, "bearer");
+
+        // Explicit `security: []` opts out of authentication
+        let health = find("/health", HttpMethod::Get);
+        assert!(health.security.is_empty());
+        assert!(health.allows_anonymous());
+    }
+
+    #[test]
+    fn test_parameter_serialization_fields() {
+        let spec = parse_file("tests/fixtures/parameter-styles.yaml").unwrap();
+        let params = &spec.endpoints[0].parameters;
+        let param = |name: &str| params.iter().find(|p| p.name == name).unwrap();
+
+        let ids = param("ids");
+        assert_eq!(ids.style, ParameterStyle::Matrix);
+        assert_eq!(ids.value_kind, ValueKind::Array);
+        assert!(ids.explode);
+
+        // Form style explodes by default
+        let tags = param("tags");
+        assert_eq!(tags.style, ParameterStyle::Form);
+        assert!(tags.explode);
+
+        let colors = param("colors");
+        assert_eq!(colors.style, ParameterStyle::PipeDelimited);
+        assert!(!colors.explode);
+
+        let filter = param("filter");
+        assert_eq!(filter.style, ParameterStyle::DeepObject);
+        assert_eq!(filter.value_kind, ValueKind::Object);
+
+        let q = param("q");
+        assert!(q.allow_reserved);
+        assert!(q.allow_empty_value);
+
+        let trace = param("X-Trace");
+        assert_eq!(trace.style, ParameterStyle::Simple);
+        assert!(!trace.explode);
+    }
+
+    #[test]
+    fn test_schema_constraints() {
+        let spec = parse_file("tests/fixtures/constraints.yaml").unwrap();
+        let endpoint = &spec.endpoints[0];
+        let constraints = |name: &str| {
+            &endpoint
+                .parameters
+                .iter()
+                .find(|p| p.name == name)
+                .unwrap()
+                .constraints
+        };
+
+        let limit = constraints("limit");
+        assert_eq!(limit.format.as_deref(), Some("int32"));
+        assert_eq!(limit.minimum.as_ref().unwrap().value, "1");
+        assert_eq!(limit.maximum.as_ref().unwrap().value, "100");
+        assert_eq!(limit.default.as_deref(), Some("20"));
+
+        // Followed through $ref
+        let sort = constraints("sort");
+        assert_eq!(sort.enum_values, vec!["asc", "desc"]);
+        assert_eq!(sort.default.as_deref(), Some("asc"));
+
+        let code = constraints("code");
+        assert_eq!(code.pattern.as_deref(), Some("^[A-Z]{3}$"));
+        assert_eq!((code.min_length, code.max_length), (Some(3), Some(3)));
+        assert!(code.nullable);
+
+        let price = constraints("price");
+        assert_eq!(price.format.as_deref(), Some("double"));
+        assert!(price.minimum.as_ref().unwrap().exclusive);
+        assert_eq!(price.multiple_of.as_deref(), Some("0.01"));
+
+        let ids = constraints("ids");
+        assert_eq!(ids.max_items, Some(10));
+        assert!(ids.unique_items);
+
+        assert_eq!(constraints("since").format.as_deref(), Some("date-time"));
+
+        let header = &endpoint.responses["200"].headers[0];
+        assert_eq!(header.constraints.maximum.as_ref().unwrap().value, "100");
+    }
+
+    #[test]
+    fn test_parameter_examples() {
+        let spec = parse_file("tests/fixtures/examples.yaml").unwrap();
+        let params = &spec.endpoints[0].parameters;
+        let param = |name: &str| params.iter().find(|p| p.name == name).unwrap();
+
+        let limit = &param("limit").examples;
+        assert_eq!(limit.len(), 1);
+        assert_eq!(limit[0].name, "example");
+        assert_eq!(limit[0].value, Some(serde_json::json!(20)));
+
+        let status = &param("status").examples;
+        let names: Vec<_> = status.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["available", "sold"]);
+        assert_eq!(status[1].summary.as_deref(), Some("Already sold"));
+    }
+
+    #[test]
+    fn test_local_file() {
+        let base_dir = Path::new("tests/fixtures");
+        assert!(local_file(base_dir, "examples/pet.json").is_some());
+        assert!(local_file(base_dir, "examples/../examples/pet.json").is_some());
+        assert_eq!(local_file(base_dir, "/etc/passwd"), None);
+        assert_eq!(local_file(base_dir, "../x"), None);
+        assert_eq!(local_file(base_dir, "../../Cargo.toml"), None);
+        assert!(local_file(Path::new(""), "Cargo.toml").is_some());
+    }
+
+    #[test]
+    fn test_media_type_examples() {
+        let spec = parse_file("tests/fixtures/examples.yaml").unwrap();
+        let content = &spec.endpoints[0].responses["200"].content;
+
+        let json = &content[0].examples;
+        assert_eq!(json.len(), 4);
+        assert_eq!(
+            json[0].value,
+            Some(serde_json::json!([{"id": 1, "name": "Rex"}]))
+        );
+        // Resolved from components.examples
+        assert_eq!(json[1].summary.as_deref(), Some("A cat"));
+        // Local externalValue files are loaded relative to the spec
+        assert_eq!(
+            json[2].value,
+            Some(serde_json::json!({"id": 3, "name": "Whiskers"}))
+        );
+        // Remote ones are kept as references
+        assert_eq!(json[3].value, None);
+        assert_eq!(
+            json[3].external_value.as_deref(),
+            Some("https://example.com/pets.json")
+        );
+
+        // Falls back to the schema's example
+        let yaml = &content[1].examples;
+        assert_eq!(yaml[0].name, "schema");
+        assert_eq!(
+            yaml[0].value,
+            Some(serde_json::json!({"id": 7, "name": "Fido"}))
+        );
+
+        let csv = &content[2].examples;
+        assert_eq!(csv[0].value, Some(serde_json::json!("id,name\n1,Rex\n")));
+    }
+
+    #[test]
+    fn test_generated_samples() {
+        let spec = parse_file("tests/fixtures/samples.yaml").unwrap();
+        let endpoint = &spec.endpoints[0];
+
+        let body = &endpoint.request_body.as_ref().unwrap().content[0];
+        let sample = body.sample.as_ref().unwrap();
+        assert_eq!(sample.all["tags"], serde_json::json!(["string", "string"]));
+        assert_eq!(
+            sample.required,
+            serde_json::json!({
+                "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                "name": "stringxx",
+                "status": "available",
+            })
+        );
+
+        // Media types with examples don't get one
+        assert!(endpoint.responses["400"].content[0].sample.is_none());
+    }
+
+    #[test]
+    fn test_content_parameter() {
+        let spec = parse_file("tests/fixtures/parameter-styles.yaml").unwrap();
+        let param = spec.endpoints[0]
+            .parameters
+            .iter()
+            .find(|p| p.name == "where")
+            .unwrap();
+
+        assert_eq!(param.schema_type.as_deref(), Some("Filter"));
+        assert_eq!(param.content.len(), 1);
+        assert_eq!(param.content[0].content_type, "application/json");
+    }
+
+    #[test]
+    fn test_callbacks_and_webhooks() {
+        let spec = parse_file("tests/fixtures/callbacks.yaml").unwrap();
+        let listed: Vec<_> = spec
+            .endpoints
+            .iter()
+            .map(|e| (e.method.to_string(), e.path.as_str()))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("GET".to_string(), "/events"),
+                ("GET".to_string(), "/subscriptions"),
+                ("POST".to_string(), "/subscriptions"),
+                ("POST".to_string(), "{$request.body#/callbackUrl}"),
+                ("DELETE".to_string(), "{$request.body#/callbackUrl}/cancel"),
+                ("POST".to_string(), "newPet"),
+            ]
+        );
+
+        let callback = &spec.endpoints[3];
+        assert_eq!(
+            callback.kind,
+            EndpointKind::Callback {
+                name: "onEvent".to_string(),
+                method: HttpMethod::Post,
+                path: "/subscriptions".to_string(),
+            }
+        );
+        assert_eq!(callback.summary.as_deref(), Some("Event notification"));
+        assert!(callback.request_body.is_some());
+        assert!(callback.responses.contains_key("204"));
+
+        assert_eq!(spec.endpoints[5].kind, EndpointKind::Webhook);
+        assert_eq!(spec.endpoints[0].kind, EndpointKind::Operation);
+    }
+
+    #[test]
+    fn test_webhooks_without_paths() {
+        let spec = parse_file("tests/fixtures/webhooks-only.json").unwrap();
+        assert_eq!(spec.endpoints.len(), 1);
+        assert_eq!(spec.endpoints[0].path, "orderShipped");
+        assert_eq!(spec.endpoints[0].kind, EndpointKind::Webhook);
+    }
+
+    #[test]
+    fn test_extensions() {
+        let spec = parse_file("tests/fixtures/extensions.yaml").unwrap();
+        let names = |extensions: &Extensions| -> Vec<String> {
+            extensions.iter().map(|(name, _)| name.clone()).collect()
+        };
+        assert_eq!(names(&spec.extensions), vec!["x-api-owner", "x-audience"]);
+
+        let list = &spec.endpoints[1];
+        assert_eq!(list.path, "/users");
+        assert_eq!(
+            list.extensions,
+            vec![(
+                "x-rate-limit".to_string(),
+                serde_json::json!({"requests": 100, "period": "minute"})
+            )]
+        );
+        assert_eq!(names(&list.path_extensions), vec!["x-gateway"]);
+        let limit = &list.parameters[0];
+        assert_eq!(names(&limit.extensions), vec!["x-example-hint"]);
+        assert_eq!(names(&limit.schema_extensions), vec!["x-max-page"]);
+        // Resolved through the $ref
+        let content = &list.responses["200"].content[0];
+        assert_eq!(names(&content.schema_extensions), vec!["x-paginated"]);
+
+        let create = &spec.endpoints[2];
+        assert_eq!(
+            names(&create.extensions),
+            vec!["x-internal", "x-codegen-request-body-name"]
+        );
+        assert_eq!(
+            names(&list.responses["200"].extensions),
+            vec!["x-cache-ttl"]
+        );
+        let body = create.request_body.as_ref().unwrap();
+        assert_eq!(names(&body.extensions), vec!["x-body-name"]);
+        assert_eq!(names(&body.content[0].schema_extensions), vec!["x-go-type"]);
+
+        // Component schemas and their inline properties have their own
+        let Some(Definition::Schema(user)) = spec
+            .components
+            .iter()
+            .find(|c| c.name == "User")
+            .map(|c| &c.definition)
+        else {
+            panic!("User is a schema");
+        };
+        assert_eq!(names(&user.extensions), vec!["x-entity"]);
+        assert_eq!(names(&user.properties[0].extensions), vec!["x-pii"]);
+        assert!(user.properties[1].extensions.is_empty());
+    }
+
+    #[test]
+    fn test_components() {
+        let spec = parse_file("tests/fixtures/components.yaml").unwrap();
+        let components: Vec<_> = spec
+            .components
+            .iter()
+            .map(|c| format!("{} {}", c.kind(), c.name))
+            .collect();
+        assert_eq!(
+            components,
+            vec![
+                "Schema Pet",
+                "Schema Owner",
+                "Schema Animal",
+                "Schema Error",
+                "Parameter Limit",
+                "Response NotFound",
+            ]
+        );
+        // Kept as declared as well, to validate responses against
+        assert_eq!(
+            spec.schemas.keys().collect::<Vec<_>>(),
+            vec!["Animal", "Error", "Owner", "Pet"]
+        );
+        assert_eq!(spec.schemas["Error"]["type"], "object");
+
+        let Definition::Schema(pet) = &spec.components[0].definition else {
+            panic!("Pet is not a schema");
+        };
+        assert_eq!(pet.description.as_deref(), Some("A pet in the store."));
+        let properties: Vec<_> = pet
+            .properties
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.schema_type.as_deref(),
+                    p.schema_ref.as_deref(),
+                    p.required,
+                )
+            })
+            .collect();
+        assert_eq!(
+            properties,
+            vec![
+                ("id", Some("integer"), None, true),
+                ("name", Some("string"), None, true),
+                ("owner", Some("Owner"), Some("Owner"), false),
+                ("friends", Some("Pet[]"), Some("Pet"), false),
+            ]
+        );
+        // Referenced properties take the description of their schema
+        assert_eq!(
+            pet.properties[2].description.as_deref(),
+            Some("Who takes care of a pet.")
+        );
+
+        let Definition::Schema(animal) = &spec.components[2].definition else {
+            panic!("Animal is not a schema");
+        };
+        assert_eq!(animal.composition, Some(Composition::One));
+        assert_eq!(animal.variants[0].schema_ref.as_deref(), Some("Pet"));
+        assert_eq!(animal.variants[1].schema_type.as_deref(), Some("string"));
+    }
+
+    #[test]
+    fn test_component_references() {
+        let spec = parse_file("tests/fixtures/components.yaml").unwrap();
+        let list = &spec.endpoints[0];
+
+        let limit = list.parameters.iter().find(|p| p.name == "limit").unwrap();
+        assert_eq!(limit.reference.as_deref(), Some("Limit"));
+        assert_eq!(limit.description.as_deref(), Some("Page size."));
+        let ok = &list.responses["200"];
+        assert_eq!(ok.reference, None);
+        assert_eq!(ok.content[0].schema.as_deref(), Some("Pet[]"));
+        assert_eq!(ok.content[0].schema_ref.as_deref(), Some("Pet"));
+        assert_eq!(list.responses["404"].reference.as_deref(), Some("NotFound"));
+
+        let mut references: Vec<_> = list.references().iter().map(|r| r.to_string()).collect();
+        references.sort();
+        assert_eq!(
+            references,
+            vec![
+                "Parameter Limit",
+                "Response NotFound",
+                "Schema Error",
+                "Schema Owner",
+                "Schema Pet",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deprecated_operation() {
+        let spec = parse_file("tests/fixtures/info-metadata.yaml").unwrap();
+
+        let deprecated: Vec<_> = spec
+            .endpoints
+            .iter()
+            .filter(|e| e.deprecated)
+            .map(|e| e.path.as_str())
+            .collect();
+        assert_eq!(deprecated, vec!["/books/{id}"]);
+
+        let list = spec
+            .endpoints
+            .iter()
+            .find(|e| e.path == "/books" && e.method == HttpMethod::Get)
+            .unwrap();
+        let param = |name: &str| list.parameters.iter().find(|p| p.name == name).unwrap();
+        assert!(param("offset").deprecated);
+        assert!(!param("page").deprecated);
+    }
+
     #[test]
     fn test_endpoint_count() {
         let spec = parse_file("tests/fixtures/petstore.yaml").unwrap();
@@ -408,7 +1831,10 @@ mod tests {
         assert!(create_pet.request_body.is_some());
         let body = create_pet.request_body.as_ref().unwrap();
         assert!(body.required);
-        assert!(body.content_types.contains(&"application/json".to_string()));
+        assert!(body
+            .content
+            .iter()
+            .any(|m| m.content_type == "application/json"));
     }
 
     #[test]
@@ -530,7 +1956,10 @@ mod tests {
         let body = create_user.request_body.as_ref().unwrap();
         assert!(body.required);
         assert_eq!(body.description, Some("User data to create".to_string()));
-        assert!(body.content_types.contains(&"application/json".to_string()));
+        assert!(body
+            .content
+            .iter()
+            .any(|m| m.content_type == "application/json"));
     }
 
     #[test]
@@ -552,6 +1981,76 @@ mod tests {
         assert_eq!(resp_400.description, "Invalid request");
     }
 
+    #[test]
+    fn test_schema_per_content_type() {
+        let spec = parse_file("tests/fixtures/responses.yaml").unwrap();
+        let get = spec
+            .endpoints
+            .iter()
+            .find(|e| e.path == "/reports/{id}" && e.method == HttpMethod::Get)
+            .unwrap();
+
+        let content = &get.responses["200"].content;
+        let pairs: Vec<_> = content
+            .iter()
+            .map(|m| (m.content_type.as_str(), m.schema.as_deref()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("application/json", Some("Report")),
+                ("application/xml", Some("Report")),
+                ("text/csv", Some("string")),
+            ]
+        );
+
+        let create = spec
+            .endpoints
+            .iter()
+            .find(|e| e.path == "/reports" && e.method == HttpMethod::Post)
+            .unwrap();
+        let body = create.request_body.as_ref().unwrap();
+        assert_eq!(body.content.len(), 2);
+        assert_eq!(body.content[0].schema.as_deref(), Some("ReportRequest"));
+        assert_eq!(body.content[1].schema.as_deref(), Some("object"));
+    }
+
+    #[test]
+    fn test_response_headers_and_links() {
+        let spec = parse_file("tests/fixtures/responses.yaml").unwrap();
+        let create = spec
+            .endpoints
+            .iter()
+            .find(|e| e.path == "/reports" && e.method == HttpMethod::Post)
+            .unwrap();
+        let created = &create.responses["201"];
+
+        assert_eq!(created.headers.len(), 2);
+        assert_eq!(created.headers[0].name, "Location");
+        assert!(created.headers[0].required);
+        assert_eq!(created.headers[0].schema_type.as_deref(), Some("string"));
+        // Resolved from components.headers
+        assert_eq!(created.headers[1].name, "X-Rate-Limit");
+        assert_eq!(created.headers[1].schema_type.as_deref(), Some("integer"));
+
+        let links: Vec<_> = created.links.iter().map(|l| &l.target).collect();
+        assert_eq!(
+            links,
+            vec![
+                &LinkTarget::OperationId("getReport".to_string()),
+                &LinkTarget::OperationRef("#/paths/~1reports~1{id}~1download/get".to_string()),
+                &LinkTarget::OperationId("deleteReport".to_string()),
+            ]
+        );
+        assert_eq!(
+            created.links[0].parameters,
+            vec![("id".to_string(), "$response.body#/id".to_string())]
+        );
+
+        let download = spec.find_link_target(&created.links[1].target).unwrap();
+        assert_eq!(spec.endpoints[download].path, "/reports/{id}/download");
+    }
+
     #[test]
     fn test_resolve_default_response_ref() {
         let spec = parse_file("tests/fixtures/ref-test.yaml").unwrap();
@@ -0,0 +1,386 @@
+use openapiv3::{
+    AdditionalProperties, AnySchema, IntegerType, NumberType, ObjectType, OpenAPI, ReferenceOr,
+    Schema, SchemaKind, StringFormat, StringType, Type, VariantOrUnknownOrEmpty,
+};
+use serde_json::{Map, Value};
+
+/// Nesting beyond this is cut off so that deep or recursive schemas stay readable.
+const MAX_DEPTH: usize = 8;
+/// Arrays and strings are kept this short whatever `minItems` and `minLength`
+/// ask for, so that a sample can't take up all memory.
+const MAX_ITEMS: usize = 3;
+const MAX_LENGTH: usize = 64;
+
+/// Builds a plausible payload for a schema that has no example of its own.
+///
+/// Explicit `example`s and `default`s win, then the first `enum` value, then a
+/// placeholder that fits the type, format and bounds. Only `required`
+/// properties are included when `required_only` is set. Recursive `$ref`s are
+/// left out instead of being expanded again.
+pub fn generate(schema: &ReferenceOr<Schema>, openapi: &OpenAPI, required_only: bool) -> Value {
+    let mut generator = Generator {
+        openapi,
+        required_only,
+        refs: Vec::new(),
+    };
+    generator.sample(schema).unwrap_or(Value::Null)
+}
+
+struct Generator<'a> {
+    openapi: &'a OpenAPI,
+    required_only: bool,
+    /// Component names currently being expanded, innermost last.
+    refs: Vec<&'a str>,
+}
+
+impl<'a> Generator<'a> {
+    fn sample(&mut self, schema: &'a ReferenceOr<Schema>) -> Option<Value> {
+        match schema {
+            ReferenceOr::Item(schema) => self.schema(schema),
+            ReferenceOr::Reference { reference } => self.reference(reference),
+        }
+    }
+
+    fn sample_boxed(&mut self, schema: &'a ReferenceOr<Box<Schema>>) -> Option<Value> {
+        match schema {
+            ReferenceOr::Item(schema) => self.schema(schema),
+            ReferenceOr::Reference { reference } => self.reference(reference),
+        }
+    }
+
+    fn reference(&mut self, reference: &'a str) -> Option<Value> {
+        let name = reference.strip_prefix("#/components/schemas/")?;
+        if self.refs.contains(&name) || self.refs.len() >= MAX_DEPTH {
+            return None;
+        }
+        let schema = self.openapi.components.as_ref()?.schemas.get(name)?;
+        self.refs.push(name);
+        let value = self.sample(schema);
+        self.refs.pop();
+        value
+    }
+
+    fn schema(&mut self, schema: &'a Schema) -> Option<Value> {
+        let data = &schema.schema_data;
+        if let Some(value) = data.example.as_ref().or(data.default.as_ref()) {
+            return Some(value.clone());
+        }
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::String(s)) => Some(string(s)),
+            SchemaKind::Type(Type::Integer(i)) => Some(integer(i)),
+            SchemaKind::Type(Type::Number(n)) => Some(number(n)),
+            SchemaKind::Type(Type::Boolean(b)) => Some(Value::Bool(
+                b.enumeration
+                    .iter()
+                    .flatten()
+                    .next()
+                    .copied()
+                    .unwrap_or(true),
+            )),
+            SchemaKind::Type(Type::Array(a)) => {
+                Some(self.array(a.items.as_ref(), a.min_items, a.max_items))
+            }
+            SchemaKind::Type(Type::Object(o)) => Some(self.object(o)),
+            SchemaKind::OneOf { one_of: variants } | SchemaKind::AnyOf { any_of: variants } => {
+                variants.iter().find_map(|variant| self.sample(variant))
+            }
+            SchemaKind::AllOf { all_of } => self.all_of(all_of),
+            SchemaKind::Not { .. } => None,
+            SchemaKind::Any(any) => self.any(any),
+        }
+    }
+
+    /// One item, or as many as `minItems` asks for up to `MAX_ITEMS`.
+    fn array(
+        &mut self,
+        items: Option<&'a ReferenceOr<Box<Schema>>>,
+        min_items: Option<usize>,
+        max_items: Option<usize>,
+    ) -> Value {
+        let count = min_items
+            .unwrap_or(1)
+            .min(max_items.unwrap_or(usize::MAX))
+            .min(MAX_ITEMS);
+        match items.and_then(|items| self.sample_boxed(items)) {
+            Some(item) => Value::Array(vec![item; count]),
+            None => Value::Array(Vec::new()),
+        }
+    }
+
+    fn object(&mut self, object: &'a ObjectType) -> Value {
+        self.properties(
+            &object.properties,
+            &object.required,
+            object.additional_properties.as_ref(),
+        )
+    }
+
+    fn properties(
+        &mut self,
+        properties: &'a indexmap::IndexMap<String, ReferenceOr<Box<Schema>>>,
+        required: &[String],
+        additional: Option<&'a AdditionalProperties>,
+    ) -> Value {
+        let mut map = Map::new();
+        for (name, schema) in properties {
+            if self.required_only && !required.contains(name) {
+                continue;
+            }
+            if let Some(value) = self.sample_boxed(schema) {
+                map.insert(name.clone(), value);
+            }
+        }
+        // A free-form map gets a single entry to show the shape of its values
+        if properties.is_empty() && !self.required_only {
+            if let Some(AdditionalProperties::Schema(schema)) = additional {
+                if let Some(value) = self.sample(schema) {
+                    map.insert("key".to_string(), value);
+                }
+            }
+        }
+        Value::Object(map)
+    }
+
+    /// Merges the properties of every part; non-object parts fall back to the first.
+    fn all_of(&mut self, parts: &'a [ReferenceOr<Schema>]) -> Option<Value> {
+        let mut merged: Option<Value> = None;
+        for part in parts {
+            let Some(value) = self.sample(part) else {
+                continue;
+            };
+            match (&mut merged, value) {
+                (Some(Value::Object(map)), Value::Object(part)) => map.extend(part),
+                (None, value) => merged = Some(value),
+                _ => {}
+            }
+        }
+        merged
+    }
+
+    fn any(&mut self, any: &'a AnySchema) -> Option<Value> {
+        if let Some(value) = any.enumeration.first() {
+            return Some(value.clone());
+        }
+        if let Some(variant) = any.one_of.first().or(any.any_of.first()) {
+            return self.sample(variant);
+        }
+        if !any.all_of.is_empty() {
+            return self.all_of(&any.all_of);
+        }
+        let typ = any.typ.as_deref();
+        if !any.properties.is_empty() || typ == Some("object") {
+            return Some(self.properties(
+                &any.properties,
+                &any.required,
+                any.additional_properties.as_ref(),
+            ));
+        }
+        if any.items.is_some() || typ == Some("array") {
+            return Some(self.array(any.items.as_ref(), any.min_items, any.max_items));
+        }
+        match typ {
+            Some("string") => Some(Value::String(string_for_format(
+                any.format.as_deref(),
+                any.min_length,
+                any.max_length,
+            ))),
+            Some("integer") => Some(Value::from(
+                lower_bound(any.minimum, any.exclusive_minimum.unwrap_or(false), 1.0)
+                    .or(any.maximum)
+                    .unwrap_or(0.0) as i64,
+            )),
+            Some("number") => Some(Value::from(
+                lower_bound(any.minimum, any.exclusive_minimum.unwrap_or(false), 0.5)
+                    .or(any.maximum)
+                    .unwrap_or(0.0),
+            )),
+            Some("boolean") => Some(Value::Bool(true)),
+            _ => None,
+        }
+    }
+}
+
+fn lower_bound(minimum: Option<f64>, exclusive: bool, step: f64) -> Option<f64> {
+    minimum.map(|min| if exclusive { min + step } else { min })
+}
+
+fn string(s: &StringType) -> Value {
+    if let Some(value) = s.enumeration.iter().flatten().next() {
+        return Value::String(value.clone());
+    }
+    let format = match &s.format {
+        VariantOrUnknownOrEmpty::Item(StringFormat::Date) => Some("date"),
+        VariantOrUnknownOrEmpty::Item(StringFormat::DateTime) => Some("date-time"),
+        VariantOrUnknownOrEmpty::Item(StringFormat::Password) => Some("password"),
+        VariantOrUnknownOrEmpty::Item(StringFormat::Byte) => Some("byte"),
+        VariantOrUnknownOrEmpty::Item(StringFormat::Binary) => Some("binary"),
+        VariantOrUnknownOrEmpty::Unknown(name) => Some(name.as_str()),
+        VariantOrUnknownOrEmpty::Empty => None,
+    };
+    Value::String(string_for_format(format, s.min_length, s.max_length))
+}
+
+/// A well-formed value for common formats, or `"string"` stretched or cut
+/// to fit the length bounds (up to `MAX_LENGTH`).
+fn string_for_format(format: Option<&str>, min: Option<usize>, max: Option<usize>) -> String {
+    let sample = match format {
+        Some("date") => "2024-01-01",
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("time") => "00:00:00",
+        Some("uuid") => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        Some("email") => "user@example.com",
+        Some("uri" | "url") => "https://example.com",
+        Some("hostname") => "example.com",
+        Some("ipv4") => "192.0.2.1",
+        Some("ipv6") => "2001:db8::1",
+        Some("byte") => "c3RyaW5n",
+        Some("password") => "********",
+        _ => {
+            let mut text = "string".to_string();
+            while text.len() < min.unwrap_or(0).min(MAX_LENGTH) {
+                text.push('x');
+            }
+            text.truncate(max.unwrap_or(usize::MAX));
+            return text;
+        }
+    };
+    sample.to_string()
+}
+
+fn integer(i: &IntegerType) -> Value {
+    if let Some(value) = i.enumeration.iter().flatten().next() {
+        return Value::from(*value);
+    }
+    let value = match (i.minimum, i.maximum) {
+        (Some(min), _) if i.exclusive_minimum => min + 1,
+        (Some(min), _) => min,
+        (None, Some(max)) if max < 0 => {
+            if i.exclusive_maximum {
+                max - 1
+            } else {
+                max
+            }
+        }
+        _ => 0,
+    };
+    let value = match i.multiple_of {
+        Some(step) if step > 0 && value % step != 0 => value + step - value.rem_euclid(step),
+        _ => value,
+    };
+    Value::from(value)
+}
+
+fn number(n: &NumberType) -> Value {
+    if let Some(value) = n.enumeration.iter().flatten().next() {
+        return Value::from(*value);
+    }
+    let value = match (n.minimum, n.maximum) {
+        (Some(min), _) => lower_bound(Some(min), n.exclusive_minimum, 0.5).unwrap_or(min),
+        (None, Some(max)) if max < 0.0 => {
+            if n.exclusive_maximum {
+                max - 0.5
+            } else {
+                max
+            }
+        }
+        _ => 0.0,
+    };
+    let value = match n.multiple_of {
+        Some(step) if step > 0.0 => (value / step).ceil() * step,
+        _ => value,
+    };
+    Value::from(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> OpenAPI {
+        let content = std::fs::read_to_string("tests/fixtures/samples.yaml").unwrap();
+        serde_yaml::from_str(&content).unwrap()
+    }
+
+    fn sample(spec: &OpenAPI, name: &str, required_only: bool) -> Value {
+        let schema = ReferenceOr::Reference {
+            reference: format!("#/components/schemas/{}", name),
+        };
+        generate(&schema, spec, required_only)
+    }
+
+    #[test]
+    fn test_generate_all_fields() {
+        let spec = spec();
+        assert_eq!(
+            sample(&spec, "Pet", false),
+            json!({
+                "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                "name": "stringxx",
+                "status": "available",
+                "age": 1,
+                "weight": 0.5,
+                "born": "2024-01-01",
+                "updated": "2024-01-01T00:00:00Z",
+                "owner": {"email": "user@example.com", "url": "https://example.com"},
+                "tags": ["string", "string"],
+                "size": 10,
+                "vaccinated": true,
+                "labels": {"key": "string"},
+            })
+        );
+    }
+
+    #[test]
+    fn test_generate_required_only() {
+        let spec = spec();
+        assert_eq!(
+            sample(&spec, "Pet", true),
+            json!({
+                "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                "name": "stringxx",
+                "status": "available",
+            })
+        );
+    }
+
+    #[test]
+    fn test_generate_composition() {
+        let spec = spec();
+        // oneOf takes the first variant
+        assert_eq!(
+            sample(&spec, "Animal", true),
+            json!({"id": "3fa85f64-5717-4562-b3fc-2c963f66afa6", "name": "stringxx", "status": "available"})
+        );
+        // allOf merges the parts
+        assert_eq!(
+            sample(&spec, "Dog", true),
+            json!({
+                "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                "name": "stringxx",
+                "status": "available",
+                "bark": "string",
+            })
+        );
+    }
+
+    #[test]
+    fn test_generate_recursive_schema() {
+        let spec = spec();
+        assert_eq!(
+            sample(&spec, "Node", false),
+            json!({"value": 0, "children": []})
+        );
+    }
+
+    #[test]
+    fn test_generate_caps_sizes() {
+        let spec = spec();
+        let sample = sample(&spec, "Huge", false);
+        let grid = sample["grid"].as_array().unwrap();
+        assert_eq!(grid.len(), MAX_ITEMS);
+        let row = grid[0].as_array().unwrap();
+        assert_eq!(row.len(), MAX_ITEMS);
+        assert_eq!(row[0].as_str().unwrap().len(), MAX_LENGTH);
+    }
+}
@@ -0,0 +1,1061 @@
+use serde_json::Value;
+
+use crate::model::{
+    ApiSpec, Endpoint, Example, HttpMethod, Parameter, ParameterLocation, SecuritySchemeKind,
+};
+
+/// A concrete request for an endpoint, with `<name>` placeholders wherever the
+/// spec gives no value to use.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Request {
+    pub method: HttpMethod,
+    /// Server URL and path with the path parameters filled in, without the query.
+    pub url: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub cookies: Vec<(String, String)>,
+    /// User name and password for HTTP basic authentication.
+    pub basic_auth: Option<(String, String)>,
+    pub body: Option<Body>,
+    /// Multipart fields whose values name files to upload.
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Body {
+    pub content_type: String,
+    pub value: Value,
+}
+
+impl Body {
+    /// The payload as sent: pretty JSON, YAML, a form-encoded string, or a
+    /// string example verbatim.
+    pub fn text(&self) -> String {
+        let content_type = self.content_type.as_str();
+        match &self.value {
+            Value::String(text) => text.clone(),
+            Value::Object(map) if content_type.contains("x-www-form-urlencoded") => map
+                .iter()
+                .map(|(key, value)| format!("{}={}", encode(key), encode(&scalar(value))))
+                .collect::<Vec<_>>()
+                .join("&"),
+            value if content_type.contains("yaml") => serde_yaml::to_string(value)
+                .unwrap_or_default()
+                .trim_end()
+                .to_string(),
+            value => serde_json::to_string_pretty(value).unwrap_or_default(),
+        }
+    }
+
+    /// Fields of a `multipart/form-data` body, each sent as its own part.
+    pub fn parts(&self) -> Option<Vec<(String, String)>> {
+        if !self.content_type.starts_with("multipart/") {
+            return None;
+        }
+        let Value::Object(map) = &self.value else {
+            return None;
+        };
+        Some(
+            map.iter()
+                .map(|(key, value)| (key.clone(), scalar(value)))
+                .collect(),
+        )
+    }
+}
+
+impl Request {
+    /// Builds the request for `endpoint` against `base_url`.
+    ///
+    /// Path parameters and required query, header and cookie parameters are
+    /// included, the body uses the `example_index`-th example of the first
+    /// content type (or the generated sample), and the first security
+    /// requirement is applied with placeholder credentials.
+    pub fn new(
+        endpoint: &Endpoint,
+        spec: &ApiSpec,
+        base_url: Option<&str>,
+        example_index: usize,
+        required_only: bool,
+    ) -> Self {
+        let body = endpoint
+            .request_body
+            .as_ref()
+            .and_then(|body| body.content.first())
+            .map(|media| Body {
+                content_type: media.content_type.clone(),
+                value: media
+                    .example_value(example_index, required_only)
+                    .unwrap_or_else(|| placeholder("body").into()),
+            });
+        let value = |param: &Parameter| {
+            (param.location == ParameterLocation::Path || param.required)
+                .then(|| parameter_value(param, example_index))
+        };
+        Self::with_values(endpoint, spec, base_url, value, body)
+    }
+
+    /// Builds the request from given parameter values, leaving out parameters
+    /// without one; path parameters without a value stay placeholders.
+    pub fn with_values(
+        endpoint: &Endpoint,
+        spec: &ApiSpec,
+        base_url: Option<&str>,
+        value: impl Fn(&Parameter) -> Option<String>,
+        body: Option<Body>,
+    ) -> Self {
+        let files = endpoint
+            .request_body
+            .iter()
+            .flat_map(|body| body.content.first())
+            .flat_map(|media| media.file_fields.clone())
+            .collect();
+        let mut request = Request {
+            method: endpoint.method.clone(),
+            url: endpoint.path.clone(),
+            body,
+            files,
+            ..Default::default()
+        };
+
+        for param in &endpoint.parameters {
+            let value = match value(param) {
+                Some(value) => value,
+                None if param.location == ParameterLocation::Path => placeholder(&param.name),
+                None => continue,
+            };
+            match param.location {
+                ParameterLocation::Path => {
+                    request.url = request
+                        .url
+                        .replace(&format!("{{{}}}", param.name), &encode(&value));
+                }
+                ParameterLocation::Query => request.query.push((param.name.clone(), value)),
+                // These are described by the body and security instead
+                ParameterLocation::Header
+                    if ["accept", "content-type", "authorization"]
+                        .contains(&param.name.to_ascii_lowercase().as_str()) => {}
+                ParameterLocation::Header => request.headers.push((param.name.clone(), value)),
+                ParameterLocation::Cookie => request.cookies.push((param.name.clone(), value)),
+            }
+        }
+        if let Some(base) = base_url {
+            request.url = format!("{}{}", base.trim_end_matches('/'), request.url);
+        }
+
+        request.apply_security(endpoint, spec);
+        request
+    }
+
+    fn apply_security(&mut self, endpoint: &Endpoint, spec: &ApiSpec) {
+        let Some(requirement) = endpoint.security.first() else {
+            return;
+        };
+        for needed in requirement {
+            let Some(scheme) = spec
+                .security_schemes
+                .iter()
+                .find(|s| s.name == needed.scheme)
+            else {
+                continue;
+            };
+            match &scheme.kind {
+                SecuritySchemeKind::ApiKey { name, location } => {
+                    let entry = (name.clone(), placeholder("api-key"));
+                    match location {
+                        ParameterLocation::Query => self.query.push(entry),
+                        ParameterLocation::Cookie => self.cookies.push(entry),
+                        ParameterLocation::Header | ParameterLocation::Path => {
+                            self.headers.push(entry)
+                        }
+                    }
+                }
+                SecuritySchemeKind::Http { scheme, .. } if scheme.eq_ignore_ascii_case("basic") => {
+                    self.basic_auth = Some((placeholder("username"), placeholder("password")));
+                }
+                SecuritySchemeKind::Http { scheme, .. }
+                    if scheme.eq_ignore_ascii_case("bearer") =>
+                {
+                    self.headers.push(bearer(placeholder("token")));
+                }
+                SecuritySchemeKind::Http { scheme, .. } => self.headers.push((
+                    "Authorization".to_string(),
+                    format!("{} {}", scheme, placeholder("credentials")),
+                )),
+                SecuritySchemeKind::OAuth2 { .. } | SecuritySchemeKind::OpenIdConnect { .. } => {
+                    self.headers.push(bearer(placeholder("access-token")));
+                }
+            }
+        }
+    }
+
+    /// The `<placeholders>` still standing in for values, which must not be
+    /// sent to a server.
+    pub fn placeholders(&self) -> Vec<String> {
+        let pairs = self.query.iter().chain(&self.headers).chain(&self.cookies);
+        let credentials = self.basic_auth.iter().flat_map(|(u, p)| [u, p]);
+        let mut found: Vec<String> = std::iter::once(&self.url)
+            .chain(pairs.map(|(_, value)| value))
+            .chain(credentials)
+            .flat_map(|text| placeholders_in(text))
+            .collect();
+        let body = placeholder("body");
+        if self
+            .body
+            .as_ref()
+            .is_some_and(|b| b.value.as_str() == Some(&body))
+        {
+            found.push(body);
+        }
+        found.dedup();
+        found
+    }
+
+    /// The URL with the query string appended.
+    pub fn full_url(&self) -> String {
+        if self.query.is_empty() {
+            return self.url.clone();
+        }
+        let query = self
+            .query
+            .iter()
+            .map(|(name, value)| format!("{}={}", encode(name), encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        format!("{}?{}", self.url, query)
+    }
+
+    /// The `Content-Type` to send, left to the client for multipart bodies as
+    /// it carries the boundary.
+    fn content_type(&self) -> Option<&str> {
+        self.body
+            .as_ref()
+            .filter(|body| body.parts().is_none())
+            .map(|body| body.content_type.as_str())
+    }
+
+    /// Headers with the content type last, and the cookies folded into a
+    /// `Cookie` header for clients without a cookie API of their own.
+    pub fn headers_with_cookies(&self) -> Vec<(String, String)> {
+        let mut headers = self.headers.clone();
+        if !self.cookies.is_empty() {
+            headers.push(("Cookie".to_string(), self.cookie_header()));
+        }
+        headers.extend(
+            self.content_type()
+                .map(|ct| ("Content-Type".to_string(), ct.to_string())),
+        );
+        headers
+    }
+
+    fn cookie_header(&self) -> String {
+        self.cookies
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+fn bearer(token: String) -> (String, String) {
+    ("Authorization".to_string(), format!("Bearer {}", token))
+}
+
+fn placeholder(name: &str) -> String {
+    format!("<{}>", name)
+}
+
+/// The `<name>`s in `text` that look like they came from `placeholder`.
+fn placeholders_in(text: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let name = &rest[1..end];
+        let is_name = |c: char| c.is_alphanumeric() || "-_.[]".contains(c);
+        if !name.is_empty() && name.chars().all(is_name) {
+            found.push(rest[..=end].to_string());
+            rest = &rest[end + 1..];
+        } else {
+            rest = &rest[1..];
+        }
+    }
+    found
+}
+
+/// An example, default or the first allowed value, or a placeholder.
+fn parameter_value(param: &Parameter, example_index: usize) -> String {
+    parameter_example(param, example_index).unwrap_or_else(|| placeholder(&param.name))
+}
+
+/// The `example_index`-th example of a parameter, its default, or its first
+/// allowed value.
+pub fn parameter_example(param: &Parameter, example_index: usize) -> Option<String> {
+    let examples: Vec<&Example> = param
+        .examples
+        .iter()
+        .filter(|e| e.value.is_some())
+        .collect();
+    if let Some(example) = examples.get(example_index % examples.len().max(1)) {
+        if let Some(value) = &example.value {
+            return Some(scalar(value));
+        }
+    }
+    param
+        .constraints
+        .default
+        .clone()
+        .or_else(|| param.constraints.enum_values.first().cloned())
+}
+
+/// Strings unquoted, arrays comma-separated and anything else as JSON.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(scalar).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+/// Percent-encodes the characters that would change the meaning of a URL,
+/// leaving placeholders readable.
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            ' ' | '"' | '#' | '%' | '&' | '+' | '/' | '=' | '?' | '\'' => {
+                encoded.push_str(&format!("%{:02X}", c as u32))
+            }
+            c => encoded.push(c),
+        }
+    }
+    encoded
+}
+
+/// Quotes a shell word so that it is passed through literally.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// The clients snippets can be generated for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    Curl,
+    Httpie,
+    Fetch,
+    Python,
+    Go,
+    Rust,
+}
+
+impl Language {
+    pub const ALL: [Language; 6] = [
+        Language::Curl,
+        Language::Httpie,
+        Language::Fetch,
+        Language::Python,
+        Language::Go,
+        Language::Rust,
+    ];
+
+    /// Code sending `request` with this client.
+    pub fn generate(self, request: &Request) -> String {
+        match self {
+            Language::Curl => curl(request),
+            Language::Httpie => httpie(request),
+            Language::Fetch => fetch(request),
+            Language::Python => python(request),
+            Language::Go => go(request),
+            Language::Rust => rust(request),
+        }
+    }
+
+    /// The next (or previous) language, wrapping around.
+    pub fn cycle(self, forward: bool) -> Language {
+        let len = Language::ALL.len();
+        let index = Language::ALL.iter().position(|&l| l == self).unwrap_or(0);
+        let next = if forward {
+            (index + 1) % len
+        } else {
+            (index + len - 1) % len
+        };
+        Language::ALL[next]
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Language::Curl => f.write_str("curl"),
+            Language::Httpie => f.write_str("HTTPie"),
+            Language::Fetch => f.write_str("fetch"),
+            Language::Python => f.write_str("Python"),
+            Language::Go => f.write_str("Go"),
+            Language::Rust => f.write_str("Rust"),
+        }
+    }
+}
+
+/// A double-quoted string literal; JSON escapes are valid in JavaScript,
+/// Python and Go alike.
+fn quote(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+/// Prefixes every line but the first with `indent`, for nesting multi-line
+/// literals in code.
+fn indent_rest(text: &str, indent: &str) -> String {
+    text.lines()
+        .collect::<Vec<_>>()
+        .join(&format!("\n{}", indent))
+}
+
+fn curl(request: &Request) -> String {
+    let mut first = vec!["curl".to_string()];
+    match request.method {
+        HttpMethod::Get => {}
+        HttpMethod::Head => first.push("-I".to_string()),
+        ref method => first.push(format!("-X {}", method)),
+    }
+    first.push(shell_quote(&request.full_url()));
+
+    let mut options = Vec::new();
+    if let Some((user, password)) = &request.basic_auth {
+        options.push(format!(
+            "-u {}",
+            shell_quote(&format!("{}:{}", user, password))
+        ));
+    }
+    for (name, value) in &request.headers {
+        options.push(format!(
+            "-H {}",
+            shell_quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if !request.cookies.is_empty() {
+        options.push(format!("-b {}", shell_quote(&request.cookie_header())));
+    }
+    if let Some(body) = &request.body {
+        match body.parts() {
+            // curl sets the multipart Content-Type along with its boundary.
+            // -F reads values starting with @ or < from files, so it is only
+            // used for the fields that are files.
+            Some(parts) => {
+                for (name, value) in parts {
+                    options.push(if request.files.contains(&name) {
+                        format!("-F {}", shell_quote(&format!("{}=@{}", name, value)))
+                    } else {
+                        format!(
+                            "--form-string {}",
+                            shell_quote(&format!("{}={}", name, value))
+                        )
+                    });
+                }
+            }
+            None => {
+                options.push(format!(
+                    "-H {}",
+                    shell_quote(&format!("Content-Type: {}", body.content_type))
+                ));
+                options.push(format!("-d {}", shell_quote(&body.text())));
+            }
+        }
+    }
+
+    std::iter::once(first.join(" "))
+        .chain(options.into_iter().map(|option| format!("  {}", option)))
+        .collect::<Vec<_>>()
+        .join(" \\\n")
+}
+
+fn httpie(request: &Request) -> String {
+    let mut first = vec!["http".to_string()];
+    if request
+        .body
+        .as_ref()
+        .is_some_and(|body| body.parts().is_some())
+    {
+        first.push("--form".to_string());
+    }
+    first.push(request.method.to_string());
+    first.push(shell_quote(&request.url));
+
+    let mut items = Vec::new();
+    if let Some((user, password)) = &request.basic_auth {
+        items.push(format!(
+            "-a {}",
+            shell_quote(&format!("{}:{}", user, password))
+        ));
+    }
+    for (name, value) in &request.query {
+        items.push(shell_quote(&format!("{}=={}", name, value)));
+    }
+    for (name, value) in request.headers_with_cookies() {
+        items.push(shell_quote(&format!("{}:{}", name, value)));
+    }
+    if let Some(body) = &request.body {
+        match body.parts() {
+            Some(parts) => items.extend(
+                parts
+                    .iter()
+                    .map(|(name, value)| shell_quote(&format!("{}={}", name, value))),
+            ),
+            None => items.push(format!("--raw {}", shell_quote(&body.text()))),
+        }
+    }
+
+    std::iter::once(first.join(" "))
+        .chain(items.into_iter().map(|item| format!("  {}", item)))
+        .collect::<Vec<_>>()
+        .join(" \\\n")
+}
+
+fn fetch(request: &Request) -> String {
+    let mut lines = Vec::new();
+    let parts = request.body.as_ref().and_then(Body::parts);
+    if let Some(parts) = &parts {
+        lines.push("const form = new FormData();".to_string());
+        for (name, value) in parts {
+            lines.push(format!("form.append({}, {});", quote(name), quote(value)));
+        }
+        lines.push(String::new());
+    }
+
+    lines.push(format!(
+        "const response = await fetch({}, {{",
+        quote(&request.full_url())
+    ));
+    lines.push(format!("  method: {},", quote(&request.method.to_string())));
+    // Header names and the expressions giving their values
+    let mut headers: Vec<(String, String)> = request
+        .basic_auth
+        .iter()
+        .map(|(user, password)| {
+            let credentials = quote(&format!("{}:{}", user, password));
+            (
+                "Authorization".to_string(),
+                format!("{} + btoa({})", quote("Basic "), credentials),
+            )
+        })
+        .collect();
+    headers.extend(
+        request
+            .headers_with_cookies()
+            .into_iter()
+            .map(|(name, value)| (name, quote(&value))),
+    );
+    if !headers.is_empty() {
+        lines.push("  headers: {".to_string());
+        for (name, value) in headers {
+            lines.push(format!("    {}: {},", quote(&name), value));
+        }
+        lines.push("  },".to_string());
+    }
+    match &request.body {
+        Some(_) if parts.is_some() => lines.push("  body: form,".to_string()),
+        Some(body) if is_json(body) => lines.push(format!(
+            "  body: JSON.stringify({}),",
+            indent_rest(&body.text(), "  ")
+        )),
+        Some(body) => lines.push(format!("  body: {},", quote(&body.text()))),
+        None => {}
+    }
+    lines.push("});".to_string());
+    lines.push("console.log(response.status, await response.text());".to_string());
+    lines.join("\n")
+}
+
+fn python(request: &Request) -> String {
+    let method = request.method.to_string().to_lowercase();
+    let mut lines = vec!["import requests".to_string(), String::new()];
+    // requests has no shorthand for TRACE
+    if request.method == HttpMethod::Trace {
+        lines.push("response = requests.request(".to_string());
+        lines.push(format!("    {},", quote("TRACE")));
+    } else {
+        lines.push(format!("response = requests.{}(", method));
+    }
+    lines.push(format!("    {},", quote(&request.url)));
+
+    let pairs = |pairs: &[(String, String)]| -> String {
+        let entries: Vec<String> = pairs
+            .iter()
+            .map(|(name, value)| format!("{}: {}", quote(name), quote(value)))
+            .collect();
+        format!("{{{}}}", entries.join(", "))
+    };
+    if !request.query.is_empty() {
+        lines.push(format!("    params={},", pairs(&request.query)));
+    }
+    let mut headers = request.headers.clone();
+    headers.extend(
+        request
+            .content_type()
+            .map(|ct| ("Content-Type".to_string(), ct.to_string())),
+    );
+    if !headers.is_empty() {
+        lines.push(format!("    headers={},", pairs(&headers)));
+    }
+    if !request.cookies.is_empty() {
+        lines.push(format!("    cookies={},", pairs(&request.cookies)));
+    }
+    if let Some((user, password)) = &request.basic_auth {
+        lines.push(format!("    auth=({}, {}),", quote(user), quote(password)));
+    }
+    if let Some(body) = &request.body {
+        match body.parts() {
+            Some(parts) => {
+                let files: Vec<String> = parts
+                    .iter()
+                    .map(|(name, value)| format!("{}: (None, {})", quote(name), quote(value)))
+                    .collect();
+                lines.push(format!("    files={{{}}},", files.join(", ")));
+            }
+            None if is_json(body) => lines.push(format!(
+                "    json={},",
+                indent_rest(&python_literal(&body.text()), "    ")
+            )),
+            None => lines.push(format!("    data={},", quote(&body.text()))),
+        }
+    }
+    lines.push(")".to_string());
+    lines.push("print(response.status_code, response.text)".to_string());
+    lines.join("\n")
+}
+
+/// Turns pretty-printed JSON into the equivalent Python literal.
+fn python_literal(json: &str) -> String {
+    let mut literal = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut word = String::new();
+    let flush = |word: &mut String, literal: &mut String| {
+        literal.push_str(match word.as_str() {
+            "true" => "True",
+            "false" => "False",
+            "null" => "None",
+            other => other,
+        });
+        word.clear();
+    };
+    for c in json.chars() {
+        if in_string {
+            literal.push(c);
+            match (escaped, c) {
+                (true, _) => escaped = false,
+                (false, '\\') => escaped = true,
+                (false, '"') => in_string = false,
+                _ => {}
+            }
+        } else if c.is_ascii_alphabetic() {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut literal);
+            in_string = c == '"';
+            literal.push(c);
+        }
+    }
+    flush(&mut word, &mut literal);
+    literal
+}
+
+fn go(request: &Request) -> String {
+    let parts = request.body.as_ref().and_then(Body::parts);
+    let mut imports = vec!["fmt", "io", "net/http"];
+    let mut lines = Vec::new();
+    let body = match (&request.body, &parts) {
+        (Some(_), Some(parts)) => {
+            imports.extend(["bytes", "mime/multipart"]);
+            lines.push("\tbody := &bytes.Buffer{}".to_string());
+            lines.push("\tform := multipart.NewWriter(body)".to_string());
+            for (name, value) in parts {
+                lines.push(format!(
+                    "\tform.WriteField({}, {})",
+                    quote(name),
+                    quote(value)
+                ));
+            }
+            lines.push("\tform.Close()".to_string());
+            "body"
+        }
+        (Some(body), None) => {
+            imports.push("strings");
+            let text = body.text();
+            let literal = if text.contains('`') {
+                quote(&text)
+            } else {
+                format!("`{}`", text)
+            };
+            lines.push(format!("\tbody := strings.NewReader({})", literal));
+            "body"
+        }
+        (None, _) => "nil",
+    };
+    imports.sort_unstable();
+
+    lines.push(format!(
+        "\treq, err := http.NewRequest({}, {}, {})",
+        quote(&request.method.to_string()),
+        quote(&request.full_url()),
+        body
+    ));
+    lines.extend(["\tif err != nil {", "\t\tpanic(err)", "\t}"].map(String::from));
+    let mut headers = request.headers.clone();
+    headers.extend(
+        request
+            .content_type()
+            .map(|ct| ("Content-Type".to_string(), ct.to_string())),
+    );
+    for (name, value) in headers {
+        lines.push(format!(
+            "\treq.Header.Set({}, {})",
+            quote(&name),
+            quote(&value)
+        ));
+    }
+    if parts.is_some() {
+        lines.push(format!(
+            "\treq.Header.Set({}, form.FormDataContentType())",
+            quote("Content-Type")
+        ));
+    }
+    if let Some((user, password)) = &request.basic_auth {
+        lines.push(format!(
+            "\treq.SetBasicAuth({}, {})",
+            quote(user),
+            quote(password)
+        ));
+    }
+    for (name, value) in &request.cookies {
+        lines.push(format!(
+            "\treq.AddCookie(&http.Cookie{{Name: {}, Value: {}}})",
+            quote(name),
+            quote(value)
+        ));
+    }
+    lines.push(String::new());
+    lines.extend(
+        [
+            "\tresp, err := http.DefaultClient.Do(req)",
+            "\tif err != nil {",
+            "\t\tpanic(err)",
+            "\t}",
+            "\tdefer resp.Body.Close()",
+            "\tdata, err := io.ReadAll(resp.Body)",
+            "\tif err != nil {",
+            "\t\tpanic(err)",
+            "\t}",
+            "\tfmt.Println(resp.Status, string(data))",
+        ]
+        .map(String::from),
+    );
+
+    let mut program = vec![
+        "package main".to_string(),
+        String::new(),
+        "import (".to_string(),
+    ];
+    program.extend(imports.iter().map(|import| format!("\t{}", quote(import))));
+    program.extend([")".to_string(), String::new(), "func main() {".to_string()]);
+    program.extend(lines);
+    program.push("}".to_string());
+    program.join("\n")
+}
+
+fn rust(request: &Request) -> String {
+    let mut lines = vec![
+        "fn main() -> Result<(), Box<dyn std::error::Error>> {".to_string(),
+        "    let client = reqwest::blocking::Client::new();".to_string(),
+        "    let response = client".to_string(),
+    ];
+    let url = rust_string(&request.url);
+    lines.push(match request.method {
+        HttpMethod::Get
+        | HttpMethod::Post
+        | HttpMethod::Put
+        | HttpMethod::Patch
+        | HttpMethod::Delete
+        | HttpMethod::Head => format!(
+            "        .{}({})",
+            request.method.to_string().to_lowercase(),
+            url
+        ),
+        ref method => format!("        .request(reqwest::Method::{}, {})", method, url),
+    });
+
+    if !request.query.is_empty() {
+        let pairs: Vec<String> = request
+            .query
+            .iter()
+            .map(|(name, value)| format!("({}, {})", rust_string(name), rust_string(value)))
+            .collect();
+        lines.push(format!("        .query(&[{}])", pairs.join(", ")));
+    }
+    if let Some((user, password)) = &request.basic_auth {
+        lines.push(format!(
+            "        .basic_auth({}, Some({}))",
+            rust_string(user),
+            rust_string(password)
+        ));
+    }
+    for (name, value) in request.headers_with_cookies() {
+        lines.push(format!(
+            "        .header({}, {})",
+            rust_string(&name),
+            rust_string(&value)
+        ));
+    }
+    if let Some(body) = &request.body {
+        match body.parts() {
+            Some(parts) => {
+                let mut form = "reqwest::blocking::multipart::Form::new()".to_string();
+                for (name, value) in parts {
+                    form.push_str(&format!(
+                        "\n                .text({}, {})",
+                        rust_string(&name),
+                        rust_string(&value)
+                    ));
+                }
+                lines.push(format!(
+                    "        .multipart(\n            {},\n        )",
+                    form
+                ));
+            }
+            None => lines.push(format!("        .body({})", rust_raw_string(&body.text()))),
+        }
+    }
+    lines.push("        .send()?;".to_string());
+    lines.push("    println!(\"{} {}\", response.status(), response.text()?);".to_string());
+    lines.push("    Ok(())".to_string());
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+fn rust_string(text: &str) -> String {
+    format!("{:?}", text)
+}
+
+/// A raw string literal with as many `#`s as needed, keeping bodies readable.
+fn rust_raw_string(text: &str) -> String {
+    let mut hashes = String::new();
+    while text.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    format!("r{}\"{}\"{}", hashes, text, hashes)
+}
+
+fn is_json(body: &Body) -> bool {
+    body.content_type.contains("json") && !body.value.is_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_file;
+
+    fn request(path: &str, method: HttpMethod) -> Request {
+        let spec = parse_file("tests/fixtures/snippets.yaml").unwrap();
+        let endpoint = spec
+            .endpoints
+            .iter()
+            .find(|e| e.path == path && e.method == method)
+            .unwrap();
+        let base = spec.servers.first().map(|s| s.url.clone());
+        Request::new(endpoint, &spec, base.as_deref(), 0, false)
+    }
+
+    #[test]
+    fn test_request_parameters_and_security() {
+        let request = request("/pets", HttpMethod::Get);
+        assert_eq!(request.url, "https://api.example.com/v1/pets");
+        assert_eq!(request.query, vec![("limit".to_string(), "20".to_string())]);
+        assert_eq!(
+            request.headers,
+            vec![
+                ("X-Request-Id".to_string(), "<X-Request-Id>".to_string()),
+                ("Authorization".to_string(), "Bearer <token>".to_string()),
+            ]
+        );
+        assert_eq!(
+            request.cookies,
+            vec![("session".to_string(), "<session>".to_string())]
+        );
+        assert_eq!(request.body, None);
+    }
+
+    #[test]
+    fn test_request_path_placeholders_and_generated_body() {
+        let request = request("/pets/{petId}", HttpMethod::Put);
+        assert_eq!(request.url, "https://api.example.com/v1/pets/<petId>");
+        assert_eq!(
+            request.headers,
+            vec![("X-API-Key".to_string(), "<api-key>".to_string())]
+        );
+        let body = request.body.unwrap();
+        assert_eq!(body.content_type, "application/json");
+        assert_eq!(
+            body.value,
+            serde_json::json!({"name": "string", "tag": "string"})
+        );
+    }
+
+    #[test]
+    fn test_curl_get() {
+        assert_eq!(
+            curl(&request("/pets", HttpMethod::Get)),
+            "curl 'https://api.example.com/v1/pets?limit=20' \\\n\
+             \x20 -H 'X-Request-Id: <X-Request-Id>' \\\n\
+             \x20 -H 'Authorization: Bearer <token>' \\\n\
+             \x20 -b 'session=<session>'"
+        );
+    }
+
+    #[test]
+    fn test_curl_json_body_with_basic_auth() {
+        assert_eq!(
+            curl(&request("/pets", HttpMethod::Post)),
+            "curl -X POST 'https://api.example.com/v1/pets' \\\n\
+             \x20 -u '<username>:<password>' \\\n\
+             \x20 -H 'Content-Type: application/json' \\\n\
+             \x20 -d '{\n  \"name\": \"Rex\",\n  \"tag\": \"dog\"\n}'"
+        );
+    }
+
+    #[test]
+    fn test_curl_form_bodies() {
+        assert_eq!(
+            curl(&request("/login", HttpMethod::Post)),
+            "curl -X POST 'https://api.example.com/v1/login' \\\n\
+             \x20 -H 'Content-Type: application/x-www-form-urlencoded' \\\n\
+             \x20 -d 'username=alice&password=it%27s%20me'"
+        );
+        assert_eq!(
+            curl(&request("/uploads", HttpMethod::Post)),
+            "curl -X POST 'https://api.example.com/v1/uploads?api_key=<api-key>' \\\n\
+             \x20 --form-string 'note=Don'\\''t lose it' \\\n\
+             \x20 -F 'file=@photo.png'"
+        );
+    }
+
+    #[test]
+    fn test_httpie() {
+        assert_eq!(
+            Language::Httpie.generate(&request("/pets", HttpMethod::Get)),
+            "http GET 'https://api.example.com/v1/pets' \\\n\
+             \x20 'limit==20' \\\n\
+             \x20 'X-Request-Id:<X-Request-Id>' \\\n\
+             \x20 'Authorization:Bearer <token>' \\\n\
+             \x20 'Cookie:session=<session>'"
+        );
+        let upload = Language::Httpie.generate(&request("/uploads", HttpMethod::Post));
+        assert!(upload.starts_with("http --form POST 'https://api.example.com/v1/uploads'"));
+        assert!(upload.ends_with("  'note=Don'\\''t lose it' \\\n  'file=photo.png'"));
+    }
+
+    #[test]
+    fn test_fetch() {
+        assert_eq!(
+            Language::Fetch.generate(&request("/pets", HttpMethod::Post)),
+            r#"const response = await fetch("https://api.example.com/v1/pets", {
+  method: "POST",
+  headers: {
+    "Authorization": "Basic " + btoa("<username>:<password>"),
+    "Content-Type": "application/json",
+  },
+  body: JSON.stringify({
+    "name": "Rex",
+    "tag": "dog"
+  }),
+});
+console.log(response.status, await response.text());"#
+        );
+    }
+
+    #[test]
+    fn test_python() {
+        assert_eq!(
+            Language::Python.generate(&request("/pets", HttpMethod::Get)),
+            r#"import requests
+
+response = requests.get(
+    "https://api.example.com/v1/pets",
+    params={"limit": "20"},
+    headers={"X-Request-Id": "<X-Request-Id>", "Authorization": "Bearer <token>"},
+    cookies={"session": "<session>"},
+)
+print(response.status_code, response.text)"#
+        );
+        let upload = Language::Python.generate(&request("/uploads", HttpMethod::Post));
+        assert!(upload.contains(
+            r#"    files={"note": (None, "Don't lose it"), "file": (None, "photo.png")},"#
+        ));
+    }
+
+    #[test]
+    fn test_python_literal() {
+        assert_eq!(
+            python_literal(r#"{"ok": true, "text": "true \" null", "none": null, "n": false}"#),
+            r#"{"ok": True, "text": "true \" null", "none": None, "n": False}"#
+        );
+    }
+
+    #[test]
+    fn test_go() {
+        let code = Language::Go.generate(&request("/uploads", HttpMethod::Post));
+        assert!(code.starts_with(
+            "package main\n\nimport (\n\t\"bytes\"\n\t\"fmt\"\n\t\"io\"\n\t\"mime/multipart\"\n\t\"net/http\"\n)\n"
+        ));
+        assert!(code.contains("\tform.WriteField(\"note\", \"Don't lose it\")\n"));
+        assert!(code.contains(
+            "\treq, err := http.NewRequest(\"POST\", \"https://api.example.com/v1/uploads?api_key=<api-key>\", body)\n"
+        ));
+        assert!(code.contains("\treq.Header.Set(\"Content-Type\", form.FormDataContentType())\n"));
+
+        let code = Language::Go.generate(&request("/pets", HttpMethod::Get));
+        assert!(code
+            .contains("\treq.AddCookie(&http.Cookie{Name: \"session\", Value: \"<session>\"})\n"));
+        assert!(code.contains(
+            "http.NewRequest(\"GET\", \"https://api.example.com/v1/pets?limit=20\", nil)"
+        ));
+    }
+
+    #[test]
+    fn test_rust() {
+        assert_eq!(
+            Language::Rust.generate(&request("/pets", HttpMethod::Post)),
+            r##"fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .post("https://api.example.com/v1/pets")
+        .basic_auth("<username>", Some("<password>"))
+        .header("Content-Type", "application/json")
+        .body(r#"{
+  "name": "Rex",
+  "tag": "dog"
+}"#)
+        .send()?;
+    println!("{} {}", response.status(), response.text()?);
+    Ok(())
+}"##
+        );
+        assert_eq!(rust_raw_string("a \"#b"), "r##\"a \"#b\"##");
+    }
+
+    #[test]
+    fn test_language_cycle() {
+        assert_eq!(Language::Curl.cycle(true), Language::Httpie);
+        assert_eq!(Language::Rust.cycle(true), Language::Curl);
+        assert_eq!(Language::Curl.cycle(false), Language::Rust);
+    }
+
+    #[test]
+    fn test_curl_head_without_security() {
+        assert_eq!(
+            curl(&request("/health", HttpMethod::Head)),
+            "curl -I 'https://api.example.com/v1/health'"
+        );
+    }
+}
@@ -0,0 +1,494 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::model::HttpMethod;
+
+pub const BUILTIN_THEMES: [&str; 4] = ["dark", "light", "high-contrast", "monochrome"];
+
+/// Styles used by the UI, so that no colors are hard-coded in `ui`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub get: Style,
+    pub post: Style,
+    pub put: Style,
+    pub delete: Style,
+    pub patch: Style,
+    pub head: Style,
+    pub options: Style,
+    pub trace: Style,
+    pub status_2xx: Style,
+    pub status_3xx: Style,
+    pub status_4xx: Style,
+    pub status_5xx: Style,
+    pub status_other: Style,
+    pub border_focused: Style,
+    pub border: Style,
+    pub highlight: Style,
+    pub search: Style,
+    /// Section headings in the detail pane.
+    pub heading: Style,
+    /// Primary text such as summaries.
+    pub text: Style,
+    /// Secondary text such as descriptions.
+    pub muted: Style,
+    /// Labels and metadata such as content types.
+    pub subtle: Style,
+    /// Highlighted names such as parameters.
+    pub accent: Style,
+    /// Inline code and code blocks in descriptions.
+    pub code: Style,
+    /// Links in descriptions.
+    pub link: Style,
+    /// Deprecated operations and parameters.
+    pub deprecated: Style,
+    /// Object keys in highlighted JSON and YAML.
+    pub syntax_key: Style,
+    /// String values in highlighted JSON and YAML.
+    pub syntax_string: Style,
+    /// Numbers, booleans and null in highlighted JSON and YAML.
+    pub syntax_literal: Style,
+}
+
+/// A user-defined theme from the `[themes.<name>]` tables of the config file.
+///
+/// Every key other than `base` overrides one style, written as space-separated
+/// modifiers and colors, e.g. `"bold #ff8700"` or `"white on blue"`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CustomTheme {
+    pub base: Option<String>,
+    #[serde(flatten)]
+    pub styles: BTreeMap<String, String>,
+}
+
+fn fg(color: Color) -> Style {
+    Style::default().fg(color)
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            get: fg(Color::Green),
+            post: fg(Color::Blue),
+            put: fg(Color::Yellow),
+            delete: fg(Color::Red),
+            patch: fg(Color::Cyan),
+            head: fg(Color::Magenta),
+            options: fg(Color::Gray),
+            trace: fg(Color::Gray),
+            status_2xx: fg(Color::Green),
+            status_3xx: fg(Color::Yellow),
+            status_4xx: fg(Color::Red),
+            status_5xx: fg(Color::Magenta),
+            status_other: fg(Color::Gray),
+            border_focused: fg(Color::Cyan),
+            border: fg(Color::DarkGray),
+            highlight: Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+            search: fg(Color::Yellow),
+            heading: fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            text: fg(Color::White),
+            muted: fg(Color::Gray),
+            subtle: fg(Color::DarkGray),
+            accent: fg(Color::Yellow),
+            code: fg(Color::LightYellow),
+            link: fg(Color::LightBlue).add_modifier(Modifier::UNDERLINED),
+            deprecated: fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT),
+            syntax_key: fg(Color::LightBlue),
+            syntax_string: fg(Color::LightGreen),
+            syntax_literal: fg(Color::LightMagenta),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            get: fg(Color::Rgb(0, 128, 0)),
+            post: fg(Color::Rgb(0, 70, 190)),
+            put: fg(Color::Rgb(160, 100, 0)),
+            delete: fg(Color::Rgb(190, 0, 0)),
+            patch: fg(Color::Rgb(0, 120, 140)),
+            head: fg(Color::Rgb(140, 0, 140)),
+            options: fg(Color::Rgb(90, 90, 90)),
+            trace: fg(Color::Rgb(90, 90, 90)),
+            status_2xx: fg(Color::Rgb(0, 128, 0)),
+            status_3xx: fg(Color::Rgb(160, 100, 0)),
+            status_4xx: fg(Color::Rgb(190, 0, 0)),
+            status_5xx: fg(Color::Rgb(140, 0, 140)),
+            status_other: fg(Color::Rgb(90, 90, 90)),
+            border_focused: fg(Color::Rgb(0, 70, 190)),
+            border: fg(Color::Rgb(170, 170, 170)),
+            highlight: Style::default()
+                .bg(Color::Rgb(215, 225, 240))
+                .add_modifier(Modifier::BOLD),
+            search: fg(Color::Rgb(160, 100, 0)),
+            heading: fg(Color::Rgb(0, 70, 190)).add_modifier(Modifier::BOLD),
+            text: fg(Color::Black),
+            muted: fg(Color::Rgb(70, 70, 70)),
+            subtle: fg(Color::Rgb(120, 120, 120)),
+            accent: fg(Color::Rgb(150, 60, 0)),
+            code: fg(Color::Rgb(160, 30, 90)),
+            link: fg(Color::Rgb(0, 70, 190)).add_modifier(Modifier::UNDERLINED),
+            deprecated: fg(Color::Rgb(120, 120, 120)).add_modifier(Modifier::CROSSED_OUT),
+            syntax_key: fg(Color::Rgb(0, 70, 190)),
+            syntax_string: fg(Color::Rgb(0, 128, 0)),
+            syntax_literal: fg(Color::Rgb(140, 0, 140)),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        let bold = |color| fg(color).add_modifier(Modifier::BOLD);
+        Self {
+            get: bold(Color::LightGreen),
+            post: bold(Color::LightBlue),
+            put: bold(Color::LightYellow),
+            delete: bold(Color::LightRed),
+            patch: bold(Color::LightCyan),
+            head: bold(Color::LightMagenta),
+            options: bold(Color::White),
+            trace: bold(Color::White),
+            status_2xx: bold(Color::LightGreen),
+            status_3xx: bold(Color::LightYellow),
+            status_4xx: bold(Color::LightRed),
+            status_5xx: bold(Color::LightMagenta),
+            status_other: bold(Color::White),
+            border_focused: bold(Color::LightYellow),
+            border: fg(Color::White),
+            highlight: Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            search: bold(Color::LightYellow),
+            heading: bold(Color::LightCyan).add_modifier(Modifier::UNDERLINED),
+            text: bold(Color::White),
+            muted: fg(Color::White),
+            subtle: fg(Color::Gray),
+            accent: bold(Color::LightYellow),
+            code: bold(Color::LightGreen),
+            link: bold(Color::LightCyan).add_modifier(Modifier::UNDERLINED),
+            deprecated: fg(Color::Gray).add_modifier(Modifier::CROSSED_OUT),
+            syntax_key: bold(Color::LightCyan),
+            syntax_string: bold(Color::LightGreen),
+            syntax_literal: bold(Color::LightMagenta),
+        }
+    }
+
+    /// A theme without any colors, distinguishing elements with text attributes only.
+    pub fn monochrome() -> Self {
+        let plain = Style::default();
+        let bold = plain.add_modifier(Modifier::BOLD);
+        let dim = plain.add_modifier(Modifier::DIM);
+        let underlined = plain.add_modifier(Modifier::UNDERLINED);
+        Self {
+            get: plain,
+            post: bold,
+            put: bold,
+            delete: bold.add_modifier(Modifier::UNDERLINED),
+            patch: bold,
+            head: dim,
+            options: dim,
+            trace: dim,
+            status_2xx: plain,
+            status_3xx: underlined,
+            status_4xx: bold,
+            status_5xx: bold.add_modifier(Modifier::UNDERLINED),
+            status_other: dim,
+            border_focused: bold,
+            border: dim,
+            highlight: plain.add_modifier(Modifier::REVERSED),
+            search: bold,
+            heading: bold.add_modifier(Modifier::UNDERLINED),
+            text: plain,
+            muted: plain,
+            subtle: dim,
+            accent: underlined,
+            code: bold,
+            link: underlined,
+            deprecated: dim.add_modifier(Modifier::CROSSED_OUT),
+            syntax_key: bold,
+            syntax_string: plain,
+            syntax_literal: underlined,
+        }
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "monochrome" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// Picks the theme named in the config, falling back to `dark`.
+    ///
+    /// `no_color` forces the monochrome theme, see <https://no-color.org>.
+    pub fn from_config(config: &Config, no_color: bool) -> Result<Self> {
+        if no_color {
+            return Ok(Self::monochrome());
+        }
+        let name = config.theme.as_deref().unwrap_or("dark");
+        Self::named(name, &config.themes)
+    }
+
+    fn named(name: &str, custom: &BTreeMap<String, CustomTheme>) -> Result<Self> {
+        if let Some(theme) = custom.get(name) {
+            let base = theme.base.as_deref().unwrap_or("dark");
+            let mut resolved = Self::builtin(base)
+                .ok_or_else(|| anyhow!("Theme '{}' has unknown base theme '{}'", name, base))?;
+            for (slot, spec) in &theme.styles {
+                let style = parse_style(spec)
+                    .map_err(|e| anyhow!("Theme '{}', key '{}': {}", name, slot, e))?;
+                *resolved
+                    .slot_mut(slot)
+                    .ok_or_else(|| anyhow!("Theme '{}' has unknown key '{}'", name, slot))? = style;
+            }
+            return Ok(resolved);
+        }
+
+        Self::builtin(name).ok_or_else(|| {
+            anyhow!(
+                "Unknown theme '{}' (built-in themes: {})",
+                name,
+                BUILTIN_THEMES.join(", ")
+            )
+        })
+    }
+
+    fn slot_mut(&mut self, slot: &str) -> Option<&mut Style> {
+        let style = match slot {
+            "get" => &mut self.get,
+            "post" => &mut self.post,
+            "put" => &mut self.put,
+            "delete" => &mut self.delete,
+            "patch" => &mut self.patch,
+            "head" => &mut self.head,
+            "options" => &mut self.options,
+            "trace" => &mut self.trace,
+            "status_2xx" => &mut self.status_2xx,
+            "status_3xx" => &mut self.status_3xx,
+            "status_4xx" => &mut self.status_4xx,
+            "status_5xx" => &mut self.status_5xx,
+            "status_other" => &mut self.status_other,
+            "border_focused" => &mut self.border_focused,
+            "border" => &mut self.border,
+            "highlight" => &mut self.highlight,
+            "search" => &mut self.search,
+            "heading" => &mut self.heading,
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "subtle" => &mut self.subtle,
+            "accent" => &mut self.accent,
+            "code" => &mut self.code,
+            "link" => &mut self.link,
+            "deprecated" => &mut self.deprecated,
+            "syntax_key" => &mut self.syntax_key,
+            "syntax_string" => &mut self.syntax_string,
+            "syntax_literal" => &mut self.syntax_literal,
+            _ => return None,
+        };
+        Some(style)
+    }
+
+    pub fn method(&self, method: &HttpMethod) -> Style {
+        match method {
+            HttpMethod::Get => self.get,
+            HttpMethod::Post => self.post,
+            HttpMethod::Put => self.put,
+            HttpMethod::Delete => self.delete,
+            HttpMethod::Patch => self.patch,
+            HttpMethod::Head => self.head,
+            HttpMethod::Options => self.options,
+            HttpMethod::Trace => self.trace,
+        }
+    }
+
+    pub fn status(&self, status: &str) -> Style {
+        match status.chars().next() {
+            Some('2') => self.status_2xx,
+            Some('3') => self.status_3xx,
+            Some('4') => self.status_4xx,
+            Some('5') => self.status_5xx,
+            _ => self.status_other,
+        }
+    }
+
+    pub fn border(&self, is_focused: bool) -> Style {
+        if is_focused {
+            self.border_focused
+        } else {
+            self.border
+        }
+    }
+}
+
+/// Parses a style such as `"bold underlined #ff8700 on black"`.
+fn parse_style(spec: &str) -> Result<Style> {
+    let mut style = Style::default();
+    let mut tokens = spec.split_whitespace();
+
+    while let Some(token) = tokens.next() {
+        let token = token.to_lowercase();
+        style = match token.as_str() {
+            "bold" => style.add_modifier(Modifier::BOLD),
+            "dim" => style.add_modifier(Modifier::DIM),
+            "italic" => style.add_modifier(Modifier::ITALIC),
+            "underlined" | "underline" => style.add_modifier(Modifier::UNDERLINED),
+            "reversed" => style.add_modifier(Modifier::REVERSED),
+            "crossed_out" | "strikethrough" => style.add_modifier(Modifier::CROSSED_OUT),
+            "on" => {
+                let Some(bg) = tokens.next() else {
+                    bail!("expected a background color after 'on'");
+                };
+                style.bg(parse_color(bg)?)
+            }
+            color => style.fg(parse_color(color)?),
+        };
+    }
+
+    Ok(style)
+}
+
+fn parse_color(color: &str) -> Result<Color> {
+    Color::from_str(color).map_err(|_| anyhow!("invalid color '{}'", color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dark_method_colors() {
+        let theme = Theme::dark();
+        assert_eq!(theme.method(&HttpMethod::Get).fg, Some(Color::Green));
+        assert_eq!(theme.method(&HttpMethod::Post).fg, Some(Color::Blue));
+        assert_eq!(theme.method(&HttpMethod::Put).fg, Some(Color::Yellow));
+        assert_eq!(theme.method(&HttpMethod::Delete).fg, Some(Color::Red));
+        assert_eq!(theme.method(&HttpMethod::Patch).fg, Some(Color::Cyan));
+    }
+
+    #[test]
+    fn test_status_styles() {
+        let theme = Theme::dark();
+        assert_eq!(theme.status("200"), theme.status_2xx);
+        assert_eq!(theme.status("3XX"), theme.status_3xx);
+        assert_eq!(theme.status("404"), theme.status_4xx);
+        assert_eq!(theme.status("503"), theme.status_5xx);
+        assert_eq!(theme.status("default"), theme.status_other);
+    }
+
+    #[test]
+    fn test_builtin_themes_exist() {
+        for name in BUILTIN_THEMES {
+            assert!(Theme::builtin(name).is_some(), "missing theme {}", name);
+        }
+        assert!(Theme::builtin("solarized").is_none());
+    }
+
+    #[test]
+    fn test_monochrome_uses_no_colors() {
+        let theme = Theme::monochrome();
+        let styles = [
+            theme.get,
+            theme.delete,
+            theme.status_4xx,
+            theme.border_focused,
+            theme.highlight,
+            theme.heading,
+            theme.accent,
+            theme.code,
+            theme.link,
+            theme.deprecated,
+            theme.syntax_key,
+            theme.syntax_literal,
+        ];
+        for style in styles {
+            assert_eq!(style.fg, None);
+            assert_eq!(style.bg, None);
+        }
+        assert_ne!(theme.get, theme.delete);
+        assert_ne!(theme.border(true), theme.border(false));
+    }
+
+    #[test]
+    fn test_from_config_defaults_to_dark() {
+        let theme = Theme::from_config(&Config::default(), false).unwrap();
+        assert_eq!(theme, Theme::dark());
+    }
+
+    #[test]
+    fn test_from_config_no_color_forces_monochrome() {
+        let config = Config::parse("theme = \"light\"\n").unwrap();
+        let theme = Theme::from_config(&config, true).unwrap();
+        assert_eq!(theme, Theme::monochrome());
+    }
+
+    #[test]
+    fn test_from_config_builtin_name() {
+        let config = Config::parse("theme = \"light\"\n").unwrap();
+        let theme = Theme::from_config(&config, false).unwrap();
+        assert_eq!(theme, Theme::light());
+    }
+
+    #[test]
+    fn test_from_config_unknown_theme() {
+        let config = Config::parse("theme = \"nope\"\n").unwrap();
+        assert!(Theme::from_config(&config, false).is_err());
+    }
+
+    #[test]
+    fn test_from_config_custom_theme() {
+        let config = Config::parse(
+            r##"
+theme = "mine"
+
+[themes.mine]
+base = "light"
+get = "bold #00ff00"
+highlight = "black on yellow"
+"##,
+        )
+        .unwrap();
+        let theme = Theme::from_config(&config, false).unwrap();
+
+        assert_eq!(
+            theme.get,
+            Style::default()
+                .fg(Color::Rgb(0, 255, 0))
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            theme.highlight,
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        );
+        assert_eq!(theme.post, Theme::light().post);
+    }
+
+    #[test]
+    fn test_from_config_custom_theme_unknown_key() {
+        let config = Config::parse("theme = \"mine\"\n[themes.mine]\nmethod = \"red\"\n").unwrap();
+        assert!(Theme::from_config(&config, false).is_err());
+    }
+
+    #[test]
+    fn test_parse_style() {
+        assert_eq!(parse_style("").unwrap(), Style::default());
+        assert_eq!(
+            parse_style("Bold underlined red").unwrap(),
+            Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        );
+        assert_eq!(
+            parse_style("on darkgray").unwrap(),
+            Style::default().bg(Color::DarkGray)
+        );
+        assert!(parse_style("on").is_err());
+        assert!(parse_style("blurple").is_err());
+    }
+}
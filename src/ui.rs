@@ -1,63 +1,1032 @@
+use std::collections::BTreeMap;
+
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::{App, Focus};
-use crate::model::{Endpoint, HttpMethod, ParameterLocation};
-
-fn method_color(method: &HttpMethod) -> Color {
-    match method {
-        HttpMethod::Get => Color::Green,
-        HttpMethod::Post => Color::Blue,
-        HttpMethod::Put => Color::Yellow,
-        HttpMethod::Delete => Color::Red,
-        HttpMethod::Patch => Color::Cyan,
-        HttpMethod::Head => Color::Magenta,
-        HttpMethod::Options => Color::Gray,
-        HttpMethod::Trace => Color::Gray,
-    }
-}
+use crate::app::{App, Focus, FormField, Popup, Screen};
+use crate::highlight;
+use crate::http::Exchange;
+use crate::markdown;
+use crate::mock::{LogEntry, MockLog};
+use crate::model::{
+    ApiSpec, Component, Constraints, Definition, Endpoint, EndpointKind, Example, Extensions,
+    MediaType, Parameter, ParameterLocation, ParameterStyle, Response, Sample, SchemaDefinition,
+    SecuritySchemeKind, ValueKind,
+};
+use crate::snippet::Language;
+use crate::theme::Theme;
+use crate::validate::Validation;
+use serde_json::Value;
 
 fn method_width() -> usize {
     7 // "OPTIONS" is the longest method name
 }
 
-fn status_code_color(status: &str) -> Color {
-    match status.chars().next() {
-        Some('2') => Color::Green,
-        Some('3') => Color::Yellow,
-        Some('4') => Color::Red,
-        Some('5') => Color::Magenta,
-        _ => Color::Gray,
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let mut area = frame.area();
+    if app.status.is_some() && area.height > 1 {
+        area.height -= 1;
+        let status_area = Rect::new(area.x, area.bottom(), area.width, 1);
+        render_status(frame, app, status_area);
+    }
+
+    match app.screen {
+        Screen::Overview => render_overview(frame, app, area),
+        Screen::Request => render_request_builder(frame, app, area),
+        Screen::Response => render_response(frame, app, area),
+        Screen::Browse => {
+            let (list_area, detail_area) = pane_areas(area, app);
+
+            // Left (or top) pane: Endpoint list
+            if let Some(area) = list_area {
+                render_endpoint_list(frame, app, area);
+            }
+
+            // Right (or bottom) pane: Detail view
+            if let Some(area) = detail_area {
+                render_detail_view(frame, app, area);
+            }
+        }
+    }
+
+    if let Some(popup) = &app.popup {
+        render_popup(frame, app, popup);
     }
 }
 
-fn border_style(is_focused: bool) -> Style {
-    if is_focused {
-        Style::default().fg(Color::Cyan)
+fn render_status(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(status) = &app.status else {
+        return;
+    };
+    let style = if status.error {
+        app.theme.status_5xx
     } else {
-        Style::default().fg(Color::DarkGray)
+        app.theme.status_2xx
+    };
+    frame.render_widget(
+        Paragraph::new(Line::styled(format!(" {}", status.text), style)),
+        area,
+    );
+}
+
+/// A rectangle of at most `width` x `height` centered in `area`.
+fn centered_area(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+fn render_popup(frame: &mut Frame, app: &App, popup: &Popup) {
+    match popup {
+        Popup::ServerPicker { selected } => render_server_picker(frame, app, *selected),
+        Popup::ServerVariables {
+            server,
+            fields,
+            selected,
+        } => render_server_variables(frame, app, *server, fields, *selected),
+        Popup::Links { selected } => render_links(frame, app, *selected),
+        Popup::Definitions { selected } => render_definitions(frame, app, *selected),
+        Popup::Snippet { scroll } => render_snippet(frame, app, *scroll),
+        Popup::Copy { selected } => render_copy(frame, app, *selected),
+        Popup::History { selected } => render_history(frame, app, *selected),
+        Popup::Environments { selected } => render_environments(frame, app, *selected),
+    }
+}
+
+fn render_environments(frame: &mut Frame, app: &App, selected: usize) {
+    let theme = &app.theme;
+    let marker = |active: bool| if active { "● " } else { "  " };
+    let mut items = vec![ListItem::new(Line::from(vec![
+        Span::styled(marker(app.active_environment.is_none()), theme.accent),
+        Span::styled("No environment", theme.text),
+    ]))];
+    for (i, environment) in app.environments.iter().enumerate() {
+        let mut spans = vec![
+            Span::styled(marker(app.active_environment == Some(i)), theme.accent),
+            Span::styled(environment.name.clone(), theme.text),
+        ];
+        if let Some(base_url) = &environment.base_url {
+            spans.push(Span::styled(format!("  {}", base_url), theme.muted));
+        }
+        let mut lines = vec![Line::from(spans)];
+        let variables: Vec<String> = environment
+            .variables
+            .keys()
+            .map(|name| format!("{}={}", name, environment.display_value(name)))
+            .collect();
+        if !variables.is_empty() {
+            lines.push(Line::styled(
+                format!("    {}", variables.join("  ")),
+                theme.subtle,
+            ));
+        }
+        items.push(ListItem::new(lines));
+    }
+
+    let height = items.iter().map(|item| item.height() as u16).sum::<u16>() + 3;
+    let area = centered_area(frame.area(), 70, height);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                .title("Environments")
+                .border_style(theme.border(true)),
+        )
+        .highlight_style(theme.highlight);
+    let mut state = ListState::default().with_selected(Some(selected));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+    frame.render_widget(
+        Paragraph::new(" Enter: use  Esc: close").block(
+            Block::default()
+                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+                .border_style(theme.border(true)),
+        ),
+        chunks[1],
+    );
+}
+
+/// Sent requests, most recent first, with their status and timing.
+fn render_history(frame: &mut Frame, app: &App, selected: usize) {
+    let theme = &app.theme;
+    let items: Vec<ListItem> = app
+        .history
+        .iter()
+        .rev()
+        .map(|exchange| {
+            let (status, style, timing) = match &exchange.response {
+                Ok(response) => (
+                    response.status.to_string(),
+                    theme.status(&response.status.to_string()),
+                    format!("  {} ms", response.elapsed.as_millis()),
+                ),
+                Err(_) => ("ERR".to_string(), theme.status_5xx, String::new()),
+            };
+            let method = &exchange.request.method;
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {:4}", status), style),
+                Span::styled(
+                    format!("{:width$} ", method.to_string(), width = method_width()),
+                    theme.method(method),
+                ),
+                Span::styled(app.masked_url(&exchange.request), theme.text),
+                Span::styled(timing, theme.muted),
+            ]))
+        })
+        .collect();
+
+    let height = items.len() as u16 + 3;
+    let area = centered_area(frame.area(), 90, height);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                .title("History")
+                .border_style(theme.border(true)),
+        )
+        .highlight_style(theme.highlight);
+    let mut state = ListState::default().with_selected(Some(selected));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+    frame.render_widget(
+        Paragraph::new(" Enter: open  Esc: close").block(
+            Block::default()
+                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+                .border_style(theme.border(true)),
+        ),
+        chunks[1],
+    );
+}
+
+fn render_copy(frame: &mut Frame, app: &App, selected: usize) {
+    let theme = &app.theme;
+    let items: Vec<ListItem> = app
+        .copy_items()
+        .into_iter()
+        .map(|item| {
+            // A glimpse of the text, as multi-line items would crowd the list
+            let preview = item.text.lines().next().unwrap_or_default().to_string();
+            let more = if item.text.lines().nth(1).is_some() {
+                " …"
+            } else {
+                ""
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {:18}", item.label), theme.accent),
+                Span::styled(preview, theme.text),
+                Span::styled(more, theme.muted),
+            ]))
+        })
+        .collect();
+
+    let height = items.len() as u16 + 3;
+    let area = centered_area(frame.area(), 70, height);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                .title("Copy")
+                .border_style(theme.border(true)),
+        )
+        .highlight_style(theme.highlight);
+    let mut state = ListState::default().with_selected(Some(selected));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+    frame.render_widget(
+        Paragraph::new(" Enter: copy  Esc: close").block(
+            Block::default()
+                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+                .border_style(theme.border(true)),
+        ),
+        chunks[1],
+    );
+}
+
+fn render_snippet(frame: &mut Frame, app: &App, scroll: u16) {
+    let theme = &app.theme;
+    let code = app.snippet().unwrap_or_default();
+    // Tabs (as in Go) would otherwise be drawn as a single cell
+    let lines: Vec<Line> = code
+        .lines()
+        .map(|line| Line::styled(format!(" {}", line.replace('\t', "    ")), theme.code))
+        .collect();
+
+    let mut title = Vec::new();
+    for language in Language::ALL {
+        let style = if language == app.snippet_language {
+            theme.highlight
+        } else {
+            theme.subtle
+        };
+        title.push(Span::styled(format!(" {} ", language), style));
     }
+
+    let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 3;
+    let height = lines.len() as u16 + 3;
+    let area = centered_area(frame.area(), width.max(50), height);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+
+    let paragraph = Paragraph::new(lines).scroll((scroll, 0)).block(
+        Block::default()
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .title(Line::from(title))
+            .border_style(theme.border(true)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, chunks[0]);
+    frame.render_widget(
+        Paragraph::new(" ←/→: language  ↑/↓: scroll  y: copy  Esc: close").block(
+            Block::default()
+                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+                .border_style(theme.border(true)),
+        ),
+        chunks[1],
+    );
+}
+
+fn render_server_picker(frame: &mut Frame, app: &App, selected: usize) {
+    let theme = &app.theme;
+    let endpoint = app.selected_endpoint();
+    let chosen = app.chosen_server(endpoint);
+    let items: Vec<ListItem> = app
+        .selected_servers()
+        .iter()
+        .enumerate()
+        .map(|(i, server)| {
+            let marker = if i == chosen { "● " } else { "  " };
+            let mut lines = vec![Line::from(vec![
+                Span::styled(marker, theme.accent),
+                Span::styled(app.resolve_server(server), theme.text),
+            ])];
+            if let Some(description) = &server.description {
+                lines.push(Line::styled(format!("    {}", description), theme.muted));
+            }
+            ListItem::new(lines)
+        })
+        .collect();
+
+    let height = items.iter().map(|item| item.height() as u16).sum::<u16>() + 3;
+    let area = centered_area(frame.area(), 70, height);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                .title(match endpoint {
+                    Some(endpoint) if !endpoint.servers.is_empty() => {
+                        format!("Servers for {}", endpoint.path)
+                    }
+                    _ => "Servers".to_string(),
+                })
+                .border_style(theme.border(true)),
+        )
+        .highlight_style(theme.highlight);
+    let mut state = ListState::default().with_selected(Some(selected));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+    frame.render_widget(
+        Paragraph::new(" Enter: use  e: edit variables  Esc: close").block(
+            Block::default()
+                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+                .border_style(theme.border(true)),
+        ),
+        chunks[1],
+    );
+}
+
+fn render_definitions(frame: &mut Frame, app: &App, selected: usize) {
+    let theme = &app.theme;
+    let items: Vec<ListItem> = app
+        .definition_targets()
+        .into_iter()
+        .map(|index| {
+            let component = &app.spec.components[index];
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {:10}", component.kind()), theme.subtle),
+                Span::styled(component.name.clone(), theme.accent),
+            ]))
+        })
+        .collect();
+
+    let height = items.len() as u16 + 3;
+    let area = centered_area(frame.area(), 50, height);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                .title("Go to Definition")
+                .border_style(theme.border(true)),
+        )
+        .highlight_style(theme.highlight);
+    let mut state = ListState::default().with_selected(Some(selected));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+    frame.render_widget(
+        Paragraph::new(" Enter: open  Esc: close").block(
+            Block::default()
+                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+                .border_style(theme.border(true)),
+        ),
+        chunks[1],
+    );
 }
 
-pub fn render(frame: &mut Frame, app: &App) {
+fn render_links(frame: &mut Frame, app: &App, selected: usize) {
+    let theme = &app.theme;
+    let items: Vec<ListItem> = app
+        .selected_links()
+        .into_iter()
+        .map(|link| {
+            let target = match app.spec.find_link_target(&link.target) {
+                Some(idx) => {
+                    let linked = &app.spec.endpoints[idx];
+                    Span::styled(format!("{} {}", linked.method, linked.path), theme.text)
+                }
+                None => Span::styled(format!("{} (not found)", link.target), theme.muted),
+            };
+            let mut lines = vec![Line::from(vec![
+                Span::styled(format!(" {}", link.name), theme.accent),
+                Span::styled(" → ", theme.subtle),
+                target,
+            ])];
+            if let Some(description) = &link.description {
+                lines.push(Line::styled(format!("    {}", description), theme.muted));
+            }
+            ListItem::new(lines)
+        })
+        .collect();
+
+    let height = items.iter().map(|item| item.height() as u16).sum::<u16>() + 3;
+    let area = centered_area(frame.area(), 70, height);
     let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                .title("Links")
+                .border_style(theme.border(true)),
+        )
+        .highlight_style(theme.highlight);
+    let mut state = ListState::default().with_selected(Some(selected));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+    frame.render_widget(
+        Paragraph::new(" Enter: go to operation  Esc: close").block(
+            Block::default()
+                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+                .border_style(theme.border(true)),
+        ),
+        chunks[1],
+    );
+}
+
+fn render_server_variables(
+    frame: &mut Frame,
+    app: &App,
+    server: usize,
+    fields: &[FormField],
+    selected: usize,
+) {
+    let theme = &app.theme;
+    let label_width = fields
+        .iter()
+        .map(|f| f.name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line> = Vec::new();
+    let mut cursor = None;
+    for (i, field) in fields.iter().enumerate() {
+        let label = format!(" {:width$} : ", field.name, width = label_width);
+        let value = if field.options.is_empty() {
+            field.value.clone()
+        } else {
+            format!("◂ {} ▸", field.value)
+        };
+        if i == selected && field.options.is_empty() {
+            cursor = Some((
+                (label.chars().count() + value.chars().count()) as u16,
+                lines.len() as u16,
+            ));
+        }
+        let value_style = if i == selected {
+            theme.highlight
+        } else {
+            theme.text
+        };
+        lines.push(Line::from(vec![
+            Span::styled(label, theme.accent),
+            Span::styled(value, value_style),
+        ]));
+    }
+
+    lines.push(Line::raw(""));
+    if let Some(field) = fields.get(selected) {
+        if let Some(description) = &field.description {
+            lines.push(Line::styled(format!(" {}", description), theme.muted));
+        }
+        if !field.options.is_empty() {
+            lines.push(Line::styled(
+                format!(" One of: {}", field.options.join(", ")),
+                theme.subtle,
+            ));
+        }
+    }
+    let preview: BTreeMap<String, String> = fields
+        .iter()
+        .map(|f| (f.name.clone(), f.value.clone()))
+        .collect();
+    lines.push(Line::styled(
+        format!(" {}", app.spec.servers[server].resolve_url(&preview)),
+        theme.text,
+    ));
+    lines.push(Line::styled(
+        " ↑/↓: field  ←/→: choose  Enter: apply  Esc: cancel",
+        theme.subtle,
+    ));
+
+    let area = centered_area(frame.area(), 70, lines.len() as u16 + 2);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Server variables")
+        .border_style(theme.border(true));
+    let inner = block.inner(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+    if let Some((x, y)) = cursor {
+        frame.set_cursor_position((inner.x + x, inner.y + y));
+    }
+}
+
+/// The request builder: parameters and body on the left, the request as a
+/// snippet in the chosen language and what is wrong with it on the right.
+fn render_request_builder(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let (Some(form), Some(endpoint)) = (app.selected_form(), app.selected_endpoint()) else {
+        return;
+    };
+
+    let columns = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let editing = form.body.as_ref().is_some_and(|body| body.editing);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(form.inputs.len().max(1) as u16 + 2),
+            Constraint::Min(if form.body.is_some() { 3 } else { 0 }),
+            Constraint::Length(1),
+        ])
+        .split(columns[0]);
+    let errors = form.errors(app.environment());
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(errors.len().max(1) as u16 + 2),
+        ])
+        .split(columns[1]);
+
+    // Parameters
+    let label_width = form
+        .inputs
+        .iter()
+        .map(|input| input.field.name.chars().count() + usize::from(input.required))
+        .max()
+        .unwrap_or(0);
+    let mut lines: Vec<Line> = Vec::new();
+    let mut cursor = None;
+    for (i, input) in form.inputs.iter().enumerate() {
+        let name = if input.required {
+            format!("{}*", input.field.name)
+        } else {
+            input.field.name.clone()
+        };
+        let label = format!(
+            " {:6} {:width$} : ",
+            input.location.to_string(),
+            name,
+            width = label_width
+        );
+        let value = match (input.field.options.is_empty(), input.field.value.as_str()) {
+            (true, value) => value.to_string(),
+            (false, "") => "◂ (none) ▸".to_string(),
+            (false, value) => format!("◂ {} ▸", value),
+        };
+        if i == form.selected && input.field.options.is_empty() {
+            cursor = Some((
+                (label.chars().count() + value.chars().count()) as u16,
+                lines.len() as u16,
+            ));
+        }
+        let value_style = if input.error(app.environment()).is_some() {
+            theme.status_5xx
+        } else if i == form.selected {
+            theme.highlight
+        } else {
+            theme.text
+        };
+        lines.push(Line::from(vec![
+            Span::styled(label, theme.accent),
+            Span::styled(value, value_style),
+        ]));
+    }
+    if form.inputs.is_empty() {
+        lines.push(Line::styled(" No parameters", theme.muted));
+    }
+    let title = format!("{} {}", endpoint.method, endpoint.path);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(theme.border(!form.body_selected()));
+    let inner = block.inner(left[0]);
+    frame.render_widget(Paragraph::new(lines).block(block), left[0]);
+    if let Some((x, y)) = cursor.filter(|_| !form.body_selected()) {
+        frame.set_cursor_position((inner.x + x, inner.y + y));
+    }
+
+    // Body editor
+    if let Some(body) = &form.body {
+        let required = if body.required { "*" } else { "" };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Body{} ({})", required, body.content_type))
+            .border_style(theme.border(form.body_selected()));
+        let inner = block.inner(left[1]);
+        // Keep the cursor line in view
+        let scroll = body
+            .row
+            .saturating_sub(inner.height.saturating_sub(1) as usize) as u16;
+        let lines: Vec<Line> = body
+            .lines
+            .iter()
+            .map(|line| Line::styled(line.clone(), theme.code))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).scroll((scroll, 0)).block(block),
+            left[1],
+        );
+        if editing {
+            frame.set_cursor_position((
+                inner.x + body.col as u16,
+                inner.y + body.row as u16 - scroll,
+            ));
+        }
+    }
+
+    let hint = if editing {
+        " Editing body  Enter: new line  Esc: stop editing"
+    } else {
+        " ↑/↓: field  ←/→: choose  Enter: next / edit body  Esc: done"
+    };
+    frame.render_widget(Paragraph::new(Line::styled(hint, theme.subtle)), left[2]);
+
+    // Preview
+    let code = app.snippet().unwrap_or_default();
+    let lines: Vec<Line> = code
+        .lines()
+        .map(|line| Line::styled(line.replace('\t', "    "), theme.code))
+        .collect();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Request ({})", app.snippet_language))
+        .border_style(theme.border(false));
+    frame.render_widget(Paragraph::new(lines).block(block), right[0]);
+
+    let lines: Vec<Line> = if errors.is_empty() {
+        vec![Line::styled(" Ready to send", theme.status_2xx)]
+    } else {
+        errors
+            .into_iter()
+            .map(|error| Line::styled(format!(" {}", error), theme.status_5xx))
+            .collect()
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Problems")
+        .border_style(theme.border(false));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(block),
+        right[1],
+    );
+}
+
+/// The response shown on the response screen: status, timing, headers and
+/// the body, pretty-printed when it is JSON.
+fn render_response(frame: &mut Frame, app: &mut App, area: Rect) {
+    let Some(exchange) = app.history.get(app.response_index) else {
+        return;
+    };
+    let content = build_response_content(exchange, &app.theme);
+    let title = format!(
+        "{} {} ({}/{})",
+        exchange.request.method,
+        app.masked_url(&exchange.request),
+        app.response_index + 1,
+        app.history.len()
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(app.theme.border(true));
+    let inner = block.inner(chunks[0]);
+    let paragraph = Paragraph::new(content).wrap(Wrap { trim: false });
+
+    let line_count = paragraph.line_count(inner.width);
+    app.response_scroll = app
+        .response_scroll
+        .min(max_scroll(line_count, inner.height));
+
+    let paragraph = paragraph.block(block).scroll((app.response_scroll, 0));
+    frame.render_widget(paragraph, chunks[0]);
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            " ↑/↓: scroll  Ctrl-r: send again  h: history  Esc: back",
+            app.theme.subtle,
+        )),
+        chunks[1],
+    );
+}
+
+fn build_response_content(exchange: &Exchange, theme: &Theme) -> Text<'static> {
+    let response = match &exchange.response {
+        Ok(response) => response,
+        Err(err) => {
+            return Text::from(vec![
+                Line::styled("Request failed", theme.status_5xx),
+                Line::raw(""),
+                Line::styled(err.clone(), theme.text),
+            ]);
+        }
+    };
+
+    let status = response.status.to_string();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{} {}", status, response.status_text),
+            theme.status(&status),
+        ),
+        Span::styled(
+            format!(
+                "  {} ms  {}",
+                response.elapsed.as_millis(),
+                format_size(response.body.len())
+            ),
+            theme.muted,
+        ),
+    ])];
+    if let Some(validation) = &exchange.validation {
+        lines.push(Line::raw(""));
+        lines.extend(validation_lines(validation, theme));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled("Headers", theme.heading));
+    for (name, value) in &response.headers {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}: ", name), theme.accent),
+            Span::styled(value.clone(), theme.text),
+        ]));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled("Body", theme.heading));
+    match response.json() {
+        Some(value) => lines.extend(highlight::render(
+            &value,
+            Some("application/json"),
+            theme,
+            2,
+        )),
+        None if response.body.is_empty() => lines.push(Line::styled("  (empty)", theme.muted)),
+        None => lines.extend(
+            response
+                .body
+                .lines()
+                .map(|line| Line::styled(format!("  {}", line), theme.text)),
+        ),
+    }
+    Text::from(lines)
+}
+
+/// Whether the response honors the contract, and where it doesn't.
+fn validation_lines(validation: &Validation, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::styled("Contract", theme.heading)];
+    let matched = match &validation.matched {
+        Some(key) if key == "default" => "the default response".to_string(),
+        Some(key) => format!("the {} response", key),
+        None => "the declared responses".to_string(),
+    };
+    if validation.is_valid() {
+        lines.push(Line::styled(
+            format!("  ✓ Matches {}", matched),
+            theme.status_2xx,
+        ));
+        return lines;
+    }
+    if validation.matched.is_some() {
+        lines.push(Line::styled(
+            format!("  Checked against {}", matched),
+            theme.muted,
+        ));
+    }
+    for problem in &validation.problems {
+        lines.push(Line::styled(format!("  ✗ {}", problem), theme.status_5xx));
+    }
+    for violation in &validation.violations {
+        let pointer = if violation.pointer.is_empty() {
+            "(body)"
+        } else {
+            violation.pointer.as_str()
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  ✗ ", theme.status_5xx),
+            Span::styled(pointer.to_string(), theme.code),
+            Span::styled(format!(" {}", violation.message), theme.text),
+        ]));
+    }
+    lines
+}
+
+/// The `oatui mock` screen: the requests answered so far, and the one selected.
+pub fn render_mock(frame: &mut Frame, mock: &mut MockLog) {
+    let theme = &mock.theme;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
         .split(frame.area());
+    let direction = if chunks[0].width < mock.stack_below {
+        Direction::Vertical
+    } else {
+        Direction::Horizontal
+    };
+    let panes = Layout::default()
+        .direction(direction)
+        .constraints([
+            Constraint::Percentage(mock.split_percent),
+            Constraint::Percentage(100 - mock.split_percent),
+        ])
+        .split(chunks[0]);
 
-    // Left pane: Endpoint list
-    render_endpoint_list(frame, app, chunks[0]);
+    let count = match mock.entries.len() {
+        1 => "1 request".to_string(),
+        count => format!("{} requests", count),
+    };
+    let title = format!("{} on {} ({})", mock.title, mock.address, count);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(theme.border(true));
+    if mock.entries.is_empty() {
+        let waiting = format!("Waiting for requests on {}…", mock.address);
+        frame.render_widget(
+            Paragraph::new(Line::styled(waiting, theme.muted)).block(block),
+            panes[0],
+        );
+    } else {
+        let items: Vec<ListItem> = mock
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let status = entry.response.status.to_string();
+                let method_style = entry
+                    .http_method()
+                    .map_or(theme.text, |method| theme.method(&method));
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:>4} ", i + 1), theme.subtle),
+                    Span::styled(format!("{} ", status), theme.status(&status)),
+                    Span::styled(
+                        format!("{:width$} ", entry.method, width = method_width()),
+                        method_style,
+                    ),
+                    Span::styled(entry.url.clone(), theme.text),
+                    Span::styled(format!("  {} ms", entry.elapsed.as_millis()), theme.muted),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.highlight);
+        let mut state = ListState::default().with_selected(Some(mock.selected));
+        frame.render_stateful_widget(list, panes[0], &mut state);
+    }
 
-    // Right pane: Detail view
-    render_detail_view(frame, app, chunks[1]);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Details")
+        .border_style(theme.border(false));
+    let content = mock
+        .selected_entry()
+        .map(|entry| build_mock_entry_content(entry, theme))
+        .unwrap_or_default();
+    let inner = block.inner(panes[1]);
+    let paragraph = Paragraph::new(content).wrap(Wrap { trim: false });
+    let line_count = paragraph.line_count(inner.width);
+    mock.detail_scroll = mock.detail_scroll.min(max_scroll(line_count, inner.height));
+    frame.render_widget(
+        paragraph.block(block).scroll((mock.detail_scroll, 0)),
+        panes[1],
+    );
+
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            " ↑/↓: select  J/K: scroll details  c: clear  q: stop the server",
+            mock.theme.subtle,
+        )),
+        chunks[1],
+    );
 }
 
-fn render_endpoint_list(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+fn build_mock_entry_content(entry: &LogEntry, theme: &Theme) -> Text<'static> {
+    let mut lines = vec![match &entry.operation {
+        Some(operation) => Line::from(vec![
+            Span::styled("Answered by ", theme.muted),
+            Span::styled(operation.clone(), theme.accent),
+        ]),
+        None => Line::styled("No operation matched", theme.status_4xx),
+    }];
+    let header_lines = |headers: &[(String, String)]| -> Vec<Line<'static>> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                Line::from(vec![
+                    Span::styled(format!("  {}: ", name), theme.accent),
+                    Span::styled(value.clone(), theme.text),
+                ])
+            })
+            .collect()
+    };
+    let body_lines = |body: &str| -> Vec<Line<'static>> {
+        if body.is_empty() {
+            return vec![Line::styled("  (empty)", theme.muted)];
+        }
+        match serde_json::from_str::<Value>(body) {
+            Ok(value) => highlight::render(&value, Some("application/json"), theme, 2),
+            Err(_) => body
+                .lines()
+                .map(|line| Line::styled(format!("  {}", line), theme.text))
+                .collect(),
+        }
+    };
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!("Request {} {}", entry.method, entry.url),
+        theme.heading,
+    ));
+    lines.extend(header_lines(&entry.request_headers));
+    if !entry.request_body.is_empty() {
+        lines.push(Line::raw(""));
+        lines.extend(body_lines(&entry.request_body));
+    }
+
+    let status = entry.response.status.to_string();
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::styled("Response ", theme.heading),
+        Span::styled(status.clone(), theme.status(&status)),
+    ]));
+    lines.extend(header_lines(&entry.response.headers));
+    lines.push(Line::raw(""));
+    lines.extend(body_lines(&entry.response.body));
+    Text::from(lines)
+}
+
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1048576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1048576.0),
+    }
+}
+
+/// Splits the screen between the list and detail panes.
+///
+/// A zoomed layout gives the whole area to the focused pane, and terminals
+/// narrower than `App::stack_below` stack the panes vertically.
+fn pane_areas(area: Rect, app: &App) -> (Option<Rect>, Option<Rect>) {
+    if app.zoomed {
+        return match app.focus {
+            Focus::List => (Some(area), None),
+            Focus::Detail => (None, Some(area)),
+        };
+    }
+
+    let direction = if area.width < app.stack_below {
+        Direction::Vertical
+    } else {
+        Direction::Horizontal
+    };
+
+    let chunks = Layout::default()
+        .direction(direction)
+        .constraints([
+            Constraint::Percentage(app.split_percent),
+            Constraint::Percentage(100 - app.split_percent),
+        ])
+        .split(area);
+
+    (Some(chunks[0]), Some(chunks[1]))
+}
+
+fn render_endpoint_list(frame: &mut Frame, app: &mut App, area: Rect) {
     let (list_area, search_area) = if app.search_mode {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -74,104 +1043,182 @@ fn render_endpoint_list(frame: &mut Frame, app: &App, area: ratatui::layout::Rec
         .filter_map(|&idx| app.spec.endpoints.get(idx))
         .map(|endpoint| {
             let method_str = format!("{:width$}", endpoint.method, width = method_width());
-            let line = Line::from(vec![
-                Span::styled(
-                    method_str,
-                    Style::default().fg(method_color(&endpoint.method)),
-                ),
+            let path = if endpoint.deprecated {
+                Span::styled(&endpoint.path, app.theme.deprecated)
+            } else {
+                Span::raw(&endpoint.path)
+            };
+            let mut spans = vec![
+                Span::styled(method_str, app.theme.method(&endpoint.method)),
                 Span::raw(" "),
-                Span::raw(&endpoint.path),
-            ]);
-            ListItem::new(line)
+            ];
+            if let Some(marker) = kind_marker(&endpoint.kind) {
+                spans.push(Span::styled(marker, app.theme.subtle));
+            }
+            spans.push(path);
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
-    let title = if !app.search_query.is_empty() && !app.search_mode {
-        format!(
-            "{} v{} [{}] ({}/{})",
-            app.spec.title,
-            app.spec.version,
-            app.search_query,
+    let mut title = format!("{} v{}", app.spec.title, app.spec.version);
+    let searching = !app.search_query.is_empty() && !app.search_mode;
+    if searching {
+        title.push_str(&format!(" [{}]", app.search_query));
+    }
+    if let Some(environment) = app.environment() {
+        title.push_str(&format!(" [env: {}]", environment.name));
+    }
+    if app.hide_deprecated {
+        title.push_str(" [no deprecated]");
+    }
+    if searching || app.hide_deprecated {
+        title.push_str(&format!(
+            " ({}/{})",
             app.filtered_indices.len(),
             app.spec.endpoints.len()
-        )
-    } else {
-        format!("{} v{}", app.spec.title, app.spec.version)
-    };
+        ));
+    }
 
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
-                .border_style(border_style(app.focus == Focus::List && !app.search_mode)),
-        )
-        .highlight_style(
-            Style::default()
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+                .border_style(
+                    app.theme
+                        .border(app.focus == Focus::List && !app.search_mode),
+                ),
         )
+        .highlight_style(app.theme.highlight)
         .highlight_symbol("> ");
 
-    let mut list_state = ListState::default();
-    list_state.select(Some(app.selected_index));
+    // Keep the scroll offset between draws; ratatui moves it as needed to keep
+    // the selection visible, including after a resize.
+    let mut list_state = ListState::default()
+        .with_offset(app.list_offset)
+        .with_selected(Some(app.selected_index));
 
     frame.render_stateful_widget(list, list_area, &mut list_state);
+    app.list_offset = list_state.offset();
 
     if let Some(search_area) = search_area {
         render_search_bar(frame, app, search_area);
     }
 }
 
-fn render_search_bar(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let search_text = format!("/{}", app.search_query);
+fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let search_text = format!("/{}", app.search_query);
+
+    let paragraph = Paragraph::new(search_text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Search")
+            .border_style(app.theme.search),
+    );
+
+    frame.render_widget(paragraph, area);
+
+    let cursor_x = area.x + 2 + app.search_query.len() as u16;
+    let cursor_y = area.y + 1;
+    frame.set_cursor_position((cursor_x, cursor_y));
+}
+
+/// Sets callbacks and webhooks apart from the API's own operations.
+fn kind_marker(kind: &EndpointKind) -> Option<&'static str> {
+    match kind {
+        EndpointKind::Operation => None,
+        EndpointKind::Callback { .. } => Some("↳ "),
+        EndpointKind::Webhook => Some("↯ "),
+    }
+}
+
+fn render_detail_view(frame: &mut Frame, app: &mut App, area: Rect) {
+    let endpoint = app.selected_endpoint();
+    let definition = app.definition.map(|index| &app.spec.components[index]);
+
+    let content = match (definition, endpoint) {
+        (Some(component), _) => build_component_content(
+            component,
+            &app.spec,
+            app.example_index,
+            app.required_only,
+            &app.theme,
+        ),
+        (None, Some(ep)) => {
+            let url = app.server_url(ep).map(|_| app.endpoint_url(ep));
+            build_detail_content(
+                ep,
+                &app.spec,
+                url.as_deref(),
+                app.example_index,
+                app.required_only,
+                app.show_extensions,
+                &app.theme,
+            )
+        }
+        (None, None) => Text::raw("No endpoint selected"),
+    };
+    let title = match definition {
+        Some(component) => format!("Details - {} {}", component.kind(), component.name),
+        None => "Details".to_string(),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(app.theme.border(app.focus == Focus::Detail));
+    let inner = block.inner(area);
+    let paragraph = Paragraph::new(content).wrap(Wrap { trim: false });
 
-    let paragraph = Paragraph::new(search_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Search")
-                .border_style(Style::default().fg(Color::Yellow)),
-        );
+    let line_count = paragraph.line_count(inner.width);
+    app.set_detail_max_scroll(max_scroll(line_count, inner.height));
 
-    frame.render_widget(paragraph, area);
+    let paragraph = paragraph.block(block).scroll((app.detail_scroll, 0));
 
-    let cursor_x = area.x + 2 + app.search_query.len() as u16;
-    let cursor_y = area.y + 1;
-    frame.set_cursor_position((cursor_x, cursor_y));
+    frame.render_widget(paragraph, area);
 }
 
-fn render_detail_view(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let endpoint = app.selected_endpoint();
+fn render_overview(frame: &mut Frame, app: &mut App, area: Rect) {
+    let content = build_overview_content(&app.spec, &app.theme);
 
-    let content = match endpoint {
-        Some(ep) => build_detail_content(ep),
-        None => Text::raw("No endpoint selected"),
-    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Overview")
+        .border_style(app.theme.border(true));
+    let inner = block.inner(area);
+    let paragraph = Paragraph::new(content).wrap(Wrap { trim: false });
 
-    let paragraph = Paragraph::new(content)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Details")
-                .border_style(border_style(app.focus == Focus::Detail)),
-        )
-        .wrap(Wrap { trim: false })
-        .scroll((app.detail_scroll, 0));
+    let line_count = paragraph.line_count(inner.width);
+    app.overview_scroll = app
+        .overview_scroll
+        .min(max_scroll(line_count, inner.height));
+
+    let paragraph = paragraph.block(block).scroll((app.overview_scroll, 0));
 
     frame.render_widget(paragraph, area);
 }
 
-fn build_detail_content(endpoint: &Endpoint) -> Text<'static> {
+fn max_scroll(line_count: usize, viewport_height: u16) -> u16 {
+    let overflow = line_count.saturating_sub(viewport_height as usize);
+    u16::try_from(overflow).unwrap_or(u16::MAX)
+}
+
+fn build_detail_content(
+    endpoint: &Endpoint,
+    spec: &ApiSpec,
+    url: Option<&str>,
+    example: usize,
+    required_only: bool,
+    show_extensions: bool,
+    theme: &Theme,
+) -> Text<'static> {
     let mut lines: Vec<Line> = Vec::new();
 
     // Method + Path
     lines.push(Line::from(vec![
         Span::styled(
             endpoint.method.to_string(),
-            Style::default()
-                .fg(method_color(&endpoint.method))
-                .add_modifier(Modifier::BOLD),
+            theme.method(&endpoint.method).add_modifier(Modifier::BOLD),
         ),
         Span::raw(" "),
         Span::styled(
@@ -179,34 +1226,73 @@ fn build_detail_content(endpoint: &Endpoint) -> Text<'static> {
             Style::default().add_modifier(Modifier::BOLD),
         ),
     ]));
+    if endpoint.kind != EndpointKind::Operation {
+        lines.push(Line::styled(endpoint.kind.to_string(), theme.accent));
+    }
+    if endpoint.deprecated {
+        lines.push(Line::styled(
+            "DEPRECATED",
+            theme.status_4xx.add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(url) = url {
+        lines.push(Line::styled(url.to_string(), theme.subtle));
+    }
     lines.push(Line::raw(""));
 
     // Summary
     if let Some(summary) = &endpoint.summary {
-        lines.push(Line::styled(
-            summary.clone(),
-            Style::default().fg(Color::White),
-        ));
+        lines.push(Line::styled(summary.clone(), theme.text));
         lines.push(Line::raw(""));
     }
 
     // Description
     if let Some(description) = &endpoint.description {
-        lines.push(Line::styled(
-            description.clone(),
-            Style::default().fg(Color::Gray),
-        ));
+        lines.extend(markdown::render(description, theme.muted, theme, 0));
+        lines.push(Line::raw(""));
+    }
+
+    // Security (only worth a section when the API uses authentication at all)
+    if !endpoint.security.is_empty() || !spec.security.is_empty() {
+        lines.push(Line::styled("Security", theme.heading));
+        if endpoint.security.is_empty() {
+            lines.push(Line::styled("  None (public)", theme.muted));
+        }
+        for (i, requirement) in endpoint.security.iter().enumerate() {
+            let mut spans = vec![Span::raw("  ")];
+            if i > 0 {
+                spans.push(Span::styled("or ", theme.subtle));
+            }
+            if requirement.is_empty() {
+                spans.push(Span::styled("anonymous", theme.muted));
+            }
+            for (j, scheme) in requirement.iter().enumerate() {
+                if j > 0 {
+                    spans.push(Span::styled(" + ", theme.subtle));
+                }
+                spans.push(Span::styled(scheme.scheme.clone(), theme.accent));
+                if let Some(declared) = spec
+                    .security_schemes
+                    .iter()
+                    .find(|s| s.name == scheme.scheme)
+                {
+                    spans.push(Span::styled(format!(" ({})", declared.kind), theme.subtle));
+                }
+                if !scheme.scopes.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" [{}]", scheme.scopes.join(", ")),
+                        theme.text,
+                    ));
+                }
+            }
+            lines.push(Line::from(spans));
+        }
         lines.push(Line::raw(""));
     }
 
     // Parameters
     if !endpoint.parameters.is_empty() {
-        lines.push(Line::styled(
-            "Parameters",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ));
+        lines.push(Line::styled("Parameters", theme.heading));
 
         // Group by location
         for location in &[
@@ -222,31 +1308,10 @@ fn build_detail_content(endpoint: &Endpoint) -> Text<'static> {
                 .collect();
 
             if !params.is_empty() {
-                lines.push(Line::styled(
-                    format!("  {}", location),
-                    Style::default().fg(Color::DarkGray),
-                ));
+                lines.push(Line::styled(format!("  {}", location), theme.subtle));
 
                 for param in params {
-                    let required_marker = if param.required { "*" } else { "" };
-                    let type_str = param.schema_type.as_deref().unwrap_or("any");
-                    lines.push(Line::from(vec![
-                        Span::raw("    "),
-                        Span::styled(
-                            format!("{}{}", param.name, required_marker),
-                            Style::default().fg(Color::Yellow),
-                        ),
-                        Span::styled(
-                            format!(" ({})", type_str),
-                            Style::default().fg(Color::DarkGray),
-                        ),
-                    ]));
-                    if let Some(desc) = &param.description {
-                        lines.push(Line::styled(
-                            format!("      {}", desc),
-                            Style::default().fg(Color::Gray),
-                        ));
-                    }
+                    lines.extend(parameter_lines(param, example, required_only, theme));
                 }
             }
         }
@@ -260,69 +1325,801 @@ fn build_detail_content(endpoint: &Endpoint) -> Text<'static> {
                 "Request Body{}",
                 if body.required { " (required)" } else { "" }
             ),
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
+            theme.heading,
         ));
 
-        if !body.content_types.is_empty() {
-            lines.push(Line::styled(
-                format!("  Content-Type: {}", body.content_types.join(", ")),
-                Style::default().fg(Color::DarkGray),
+        if let Some(desc) = &body.description {
+            lines.extend(markdown::render(desc, theme.muted, theme, 2));
+        }
+
+        lines.extend(media_type_lines(
+            &body.content,
+            example,
+            required_only,
+            2,
+            theme,
+        ));
+        lines.push(Line::raw(""));
+    }
+
+    // Responses
+    if !endpoint.responses.is_empty() {
+        lines.push(Line::styled("Responses", theme.heading));
+
+        for (status, response) in &endpoint.responses {
+            let label = Span::styled(status.clone(), theme.status(status));
+            lines.extend(response_lines(
+                label,
+                response,
+                spec,
+                example,
+                required_only,
+                theme,
             ));
         }
+    }
 
-        if let Some(desc) = &body.description {
-            lines.push(Line::styled(
-                format!("  {}", desc),
-                Style::default().fg(Color::Gray),
+    // Extensions, collapsed by default since they are mostly of interest to tooling
+    let groups = extension_groups(endpoint);
+    if !groups.is_empty() {
+        if !endpoint.responses.is_empty() {
+            lines.push(Line::raw(""));
+        }
+        let count: usize = groups.iter().map(|(_, extensions)| extensions.len()).sum();
+        let mut heading = vec![Span::styled(
+            format!("Extensions ({})", count),
+            theme.heading,
+        )];
+        if !show_extensions {
+            heading.push(Span::styled(" - press x to show", theme.muted));
+        }
+        lines.push(Line::from(heading));
+        if show_extensions {
+            for (label, extensions) in groups {
+                let indent = match label {
+                    Some(label) => {
+                        lines.push(Line::styled(format!("  {}", label), theme.subtle));
+                        4
+                    }
+                    None => 2,
+                };
+                lines.extend(extension_lines(extensions, indent, theme));
+            }
+        }
+    }
+
+    Text::from(lines)
+}
+
+/// The non-empty extension maps of an endpoint; the operation's own come
+/// first and unlabeled.
+fn extension_groups(endpoint: &Endpoint) -> Vec<(Option<String>, &Extensions)> {
+    let mut groups = vec![
+        (None, &endpoint.extensions),
+        (Some("Path".to_string()), &endpoint.path_extensions),
+    ];
+    for param in &endpoint.parameters {
+        groups.push((Some(format!("Parameter {}", param.name)), &param.extensions));
+        groups.push((
+            Some(format!("Parameter {} schema", param.name)),
+            &param.schema_extensions,
+        ));
+    }
+    if let Some(body) = &endpoint.request_body {
+        groups.push((Some("Request body".to_string()), &body.extensions));
+    }
+    for media in endpoint.request_body.iter().flat_map(|b| &b.content) {
+        groups.push((
+            Some(format!("Request body {} schema", media.content_type)),
+            &media.schema_extensions,
+        ));
+    }
+    for (status, response) in &endpoint.responses {
+        groups.push((Some(format!("Response {}", status)), &response.extensions));
+        for media in &response.content {
+            groups.push((
+                Some(format!("Response {} {} schema", status, media.content_type)),
+                &media.schema_extensions,
             ));
         }
+    }
+    groups.retain(|(_, extensions)| !extensions.is_empty());
+    groups
+}
 
-        if let Some(schema) = &body.schema {
-            lines.push(Line::styled(
-                format!("  Schema: {}", schema),
-                Style::default().fg(Color::Gray),
+/// `name: value` for scalars, highlighted JSON below the name otherwise.
+fn extension_lines(extensions: &Extensions, indent: usize, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (name, value) in extensions {
+        let mut spans = vec![
+            Span::raw(" ".repeat(indent)),
+            Span::styled(name.clone(), theme.accent),
+            Span::styled(":", theme.subtle),
+        ];
+        match value {
+            Value::Array(_) | Value::Object(_) => {
+                lines.push(Line::from(spans));
+                lines.extend(highlight::render(value, None, theme, indent + 2));
+            }
+            Value::String(text) => {
+                spans.push(Span::styled(format!(" {}", text), theme.syntax_string));
+                lines.push(Line::from(spans));
+            }
+            other => {
+                spans.push(Span::styled(format!(" {}", other), theme.syntax_literal));
+                lines.push(Line::from(spans));
+            }
+        }
+    }
+    lines
+}
+
+/// The detail pane for a component opened with "go to definition".
+fn build_component_content(
+    component: &Component,
+    spec: &ApiSpec,
+    example: usize,
+    required_only: bool,
+    theme: &Theme,
+) -> Text<'static> {
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(component.kind().to_string(), theme.subtle),
+            Span::raw(" "),
+            Span::styled(
+                component.name.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::raw(""),
+    ];
+    match &component.definition {
+        Definition::Schema(schema) => lines.extend(schema_lines(schema, theme)),
+        Definition::Parameter(param) => {
+            lines.push(Line::styled(format!("  {}", param.location), theme.subtle));
+            lines.extend(parameter_lines(param, example, required_only, theme));
+        }
+        Definition::Response(response) => {
+            let label = Span::styled(component.name.clone(), theme.accent);
+            lines.extend(response_lines(
+                label,
+                response,
+                spec,
+                example,
+                required_only,
+                theme,
             ));
         }
+    }
+    Text::from(lines)
+}
+
+/// A schema's type and constraints, then its properties, items or variants.
+fn schema_lines(schema: &SchemaDefinition, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if let Some(description) = &schema.description {
+        lines.extend(markdown::render(description, theme.muted, theme, 0));
         lines.push(Line::raw(""));
     }
 
-    // Responses
-    if !endpoint.responses.is_empty() {
-        lines.push(Line::styled(
-            "Responses",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
+    lines.push(Line::from(vec![
+        Span::styled("Type ", theme.heading),
+        Span::styled(
+            type_label(schema.schema_type.as_deref(), &schema.constraints),
+            theme.text,
+        ),
+    ]));
+    lines.extend(constraint_line(&schema.constraints, 2, theme));
+    lines.extend(extension_lines(&schema.extensions, 2, theme));
+
+    if !schema.properties.is_empty() {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled("Properties", theme.heading));
+        for property in &schema.properties {
+            let required_marker = if property.required { "*" } else { "" };
+            let mut spans = vec![
+                Span::raw("  "),
+                Span::styled(
+                    format!("{}{}", property.name, required_marker),
+                    theme.accent,
+                ),
+            ];
+            spans.extend(type_spans(
+                property.schema_type.as_deref(),
+                property.schema_ref.as_deref(),
+                &property.constraints,
+                theme,
+            ));
+            lines.push(Line::from(spans));
+            lines.extend(constraint_line(&property.constraints, 4, theme));
+            if let Some(desc) = &property.description {
+                lines.extend(markdown::render(desc, theme.muted, theme, 4));
+            }
+            lines.extend(extension_lines(&property.extensions, 4, theme));
+        }
+    }
+
+    let variants = match (&schema.items, schema.composition) {
+        (Some(items), _) => Some(("Items".to_string(), std::slice::from_ref(items))),
+        (None, Some(composition)) => Some((composition.to_string(), &schema.variants[..])),
+        (None, None) => None,
+    };
+    if let Some((heading, variants)) = variants {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(heading, theme.heading));
+        for variant in variants {
+            let style = if variant.schema_ref.is_some() {
+                theme.link
+            } else {
+                theme.text
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    type_label(variant.schema_type.as_deref(), &variant.constraints),
+                    style,
+                ),
+            ]));
+            lines.extend(constraint_line(&variant.constraints, 4, theme));
+            // Referenced schemas describe themselves when opened
+            if variant.schema_ref.is_none() {
+                if let Some(desc) = &variant.description {
+                    lines.extend(markdown::render(desc, theme.muted, theme, 4));
+                }
+            }
+        }
+    }
+    lines
+}
+
+/// A response under its status code (or component name): description,
+/// content, headers and links.
+fn response_lines(
+    label: Span<'static>,
+    response: &Response,
+    spec: &ApiSpec,
+    example: usize,
+    required_only: bool,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut status_line = vec![Span::raw("  "), label];
+    if let Some(reference) = &response.reference {
+        status_line.push(Span::styled(format!(" ↗ {}", reference), theme.link));
+    }
+
+    // Short descriptions stay on the status line, longer ones go below it
+    let mut description = markdown::render(&response.description, theme.text, theme, 4);
+    if description.len() == 1 {
+        status_line.push(Span::raw(" - "));
+        status_line.extend(description.remove(0).spans.into_iter().skip(1));
+        lines.push(Line::from(status_line));
+    } else {
+        lines.push(Line::from(status_line));
+        lines.extend(description);
+    }
+
+    lines.extend(media_type_lines(
+        &response.content,
+        example,
+        required_only,
+        4,
+        theme,
+    ));
+
+    if !response.headers.is_empty() {
+        lines.push(Line::styled("    Headers", theme.subtle));
+        for header in &response.headers {
+            let required_marker = if header.required { "*" } else { "" };
+            let type_str = type_label(header.schema_type.as_deref(), &header.constraints);
+            lines.push(Line::from(vec![
+                Span::raw("      "),
+                Span::styled(format!("{}{}", header.name, required_marker), theme.accent),
+                Span::styled(format!(" ({})", type_str), theme.subtle),
+            ]));
+            lines.extend(constraint_line(&header.constraints, 8, theme));
+            if let Some(desc) = &header.description {
+                lines.extend(markdown::render(desc, theme.muted, theme, 8));
+            }
+        }
+    }
+
+    if !response.links.is_empty() {
+        lines.push(Line::styled("    Links", theme.subtle));
+        for link in &response.links {
+            let mut spans = vec![
+                Span::raw("      "),
+                Span::styled(link.name.clone(), theme.accent),
+                Span::styled(" → ", theme.subtle),
+            ];
+            let target = match spec.find_link_target(&link.target) {
+                Some(idx) => {
+                    let linked = &spec.endpoints[idx];
+                    Span::styled(format!("{} {}", linked.method, linked.path), theme.link)
+                }
+                None => Span::styled(link.target.to_string(), theme.muted),
+            };
+            spans.push(target);
+            lines.push(Line::from(spans));
+            for (name, expression) in &link.parameters {
+                lines.push(Line::styled(
+                    format!("        {} = {}", name, expression),
+                    theme.subtle,
+                ));
+            }
+            if let Some(desc) = &link.description {
+                lines.extend(markdown::render(desc, theme.muted, theme, 8));
+            }
+        }
+    }
+    lines
+}
+
+/// A parameter's name line followed by its constraints, serialization,
+/// examples and description.
+fn parameter_lines(
+    param: &Parameter,
+    example: usize,
+    required_only: bool,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let required_marker = if param.required { "*" } else { "" };
+    let name_style = if param.deprecated {
+        theme.deprecated
+    } else {
+        theme.accent
+    };
+    let mut spans = vec![
+        Span::raw("    "),
+        Span::styled(format!("{}{}", param.name, required_marker), name_style),
+    ];
+    spans.extend(type_spans(
+        param.schema_type.as_deref(),
+        param.schema_ref.as_deref(),
+        &param.constraints,
+        theme,
+    ));
+    if param.deprecated {
+        spans.push(Span::styled(" deprecated", theme.status_4xx));
+    }
+    if let Some(reference) = &param.reference {
+        spans.push(Span::styled(format!(" ↗ {}", reference), theme.link));
+    }
+    lines.push(Line::from(spans));
+    lines.extend(constraint_line(&param.constraints, 6, theme));
+    lines.extend(serialization_lines(param, example, required_only, theme));
+    lines.extend(example_lines(&param.examples, example, None, 6, theme));
+    if let Some(desc) = &param.description {
+        lines.extend(markdown::render(desc, theme.muted, theme, 6));
+    }
+    lines
+}
+
+/// ` (type)`, with the name of a referenced schema styled as a link.
+fn type_spans(
+    schema_type: Option<&str>,
+    schema_ref: Option<&str>,
+    constraints: &Constraints,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    let label = type_label(schema_type, constraints);
+    if schema_ref.is_none() {
+        return vec![Span::styled(format!(" ({})", label), theme.subtle)];
+    }
+    vec![
+        Span::styled(" (", theme.subtle),
+        Span::styled(label, theme.link),
+        Span::styled(")", theme.subtle),
+    ]
+}
+
+/// A schema type with its format, e.g. `integer, int32`.
+fn type_label(schema_type: Option<&str>, constraints: &Constraints) -> String {
+    let type_str = schema_type.unwrap_or("any");
+    match &constraints.format {
+        Some(format) => format!("{}, {}", type_str, format),
+        None => type_str.to_string(),
+    }
+}
+
+fn constraint_line(
+    constraints: &Constraints,
+    indent: usize,
+    theme: &Theme,
+) -> Option<Line<'static>> {
+    let parts = constraints.describe();
+    if parts.is_empty() {
+        return None;
+    }
+    Some(Line::from(vec![
+        Span::raw(" ".repeat(indent)),
+        Span::styled(parts.join(" · "), theme.muted),
+    ]))
+}
+
+/// How a parameter is serialized, shown only when it differs from a plain
+/// `name=value` so simple parameters stay compact.
+fn serialization_lines(
+    param: &Parameter,
+    example: usize,
+    required_only: bool,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    if !param.content.is_empty() {
+        return media_type_lines(&param.content, example, required_only, 6, theme);
+    }
+
+    let default_style = Parameter::default_style(&param.location);
+    let default_explode = param.style == ParameterStyle::Form;
+    let notable = param.value_kind != ValueKind::Primitive
+        || param.style != default_style
+        || param.explode != default_explode
+        || param.allow_reserved
+        || param.allow_empty_value;
+    if !notable {
+        return Vec::new();
+    }
+
+    let mut details = vec![
+        param.style.to_string(),
+        format!("explode={}", param.explode),
+    ];
+    if param.allow_reserved {
+        details.push("allowReserved".to_string());
+    }
+    if param.allow_empty_value {
+        details.push("allowEmptyValue".to_string());
+    }
+    let mut spans = vec![Span::styled(
+        format!("      {}", details.join(", ")),
+        theme.subtle,
+    )];
+    if let Some(example) = param.wire_example() {
+        spans.push(Span::styled(" → ", theme.subtle));
+        spans.push(Span::styled(example, theme.code));
+    }
+    vec![Line::from(spans)]
+}
+
+/// One line per content type, with its schema when known.
+fn media_type_lines(
+    content: &[MediaType],
+    example: usize,
+    required_only: bool,
+    indent: usize,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for media in content {
+        let mut spans = vec![
+            Span::raw(" ".repeat(indent)),
+            Span::styled(media.content_type.clone(), theme.subtle),
+        ];
+        if let Some(schema) = &media.schema {
+            let style = if media.schema_ref.is_some() {
+                theme.link
+            } else {
+                theme.muted
+            };
+            spans.push(Span::styled(": ", theme.muted));
+            spans.push(Span::styled(schema.clone(), style));
+        }
+        lines.push(Line::from(spans));
+        lines.extend(example_lines(
+            &media.examples,
+            example,
+            Some(&media.content_type),
+            indent + 2,
+            theme,
         ));
+        if let Some(sample) = &media.sample {
+            lines.extend(sample_lines(
+                sample,
+                required_only,
+                Some(&media.content_type),
+                indent + 2,
+                theme,
+            ));
+        }
+    }
+    lines
+}
 
-        for (status, response) in &endpoint.responses {
-            let status_color = status_code_color(status);
+/// A payload generated from the schema, with or without optional fields.
+fn sample_lines(
+    sample: &Sample,
+    required_only: bool,
+    content_type: Option<&str>,
+    indent: usize,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let (value, label) = if required_only {
+        (&sample.required, "required fields")
+    } else {
+        (&sample.all, "all fields")
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::raw(" ".repeat(indent)),
+        Span::styled("Generated example", theme.subtle),
+        Span::styled(format!(" ({})", label), theme.muted),
+    ])];
+    lines.extend(highlight::render(value, content_type, theme, indent + 2));
+    lines
+}
+
+/// The `index`-th example (wrapping around) with its name and summary.
+/// Scalars are shown inline, anything larger as a highlighted block.
+fn example_lines(
+    examples: &[Example],
+    index: usize,
+    content_type: Option<&str>,
+    indent: usize,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    if examples.is_empty() {
+        return Vec::new();
+    }
+    let example = &examples[index % examples.len()];
+
+    let mut header = vec![
+        Span::raw(" ".repeat(indent)),
+        Span::styled("Example", theme.subtle),
+    ];
+    if examples.len() > 1 {
+        header.push(Span::styled(
+            format!(" {}/{}", index % examples.len() + 1, examples.len()),
+            theme.subtle,
+        ));
+    }
+    // `example` and `schema` are the names given to unnamed examples
+    if !matches!(example.name.as_str(), "example" | "schema") {
+        header.push(Span::styled(format!(" {}", example.name), theme.accent));
+    }
+    if let Some(summary) = &example.summary {
+        header.push(Span::styled(format!(" - {}", summary), theme.muted));
+    }
+
+    let mut lines = Vec::new();
+    match &example.value {
+        Some(value @ (Value::Bool(_) | Value::Number(_) | Value::Null)) => {
+            header.push(Span::styled(": ", theme.subtle));
+            header.push(Span::styled(value.to_string(), theme.syntax_literal));
+            lines.push(Line::from(header));
+        }
+        Some(Value::String(text)) if !text.contains('\n') => {
+            header.push(Span::styled(": ", theme.subtle));
+            header.push(Span::styled(text.clone(), theme.syntax_string));
+            lines.push(Line::from(header));
+        }
+        Some(value) => {
+            lines.push(Line::from(header));
+            lines.extend(highlight::render(value, content_type, theme, indent + 2));
+        }
+        None => {
+            lines.push(Line::from(header));
+            if let Some(external) = &example.external_value {
+                lines.push(Line::styled(
+                    format!("{}{}", " ".repeat(indent + 2), external),
+                    theme.link,
+                ));
+            }
+        }
+    }
+    if let Some(description) = &example.description {
+        lines.extend(markdown::render(
+            description,
+            theme.muted,
+            theme,
+            indent + 2,
+        ));
+    }
+    lines
+}
+
+fn build_overview_content(spec: &ApiSpec, theme: &Theme) -> Text<'static> {
+    let mut lines: Vec<Line> = Vec::new();
+
+    // Title + Version
+    lines.push(Line::from(vec![
+        Span::styled(spec.title.clone(), theme.text.add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" v{}", spec.version), theme.subtle),
+    ]));
+    lines.push(Line::raw(""));
+
+    if let Some(description) = &spec.description {
+        lines.extend(markdown::render(description, theme.muted, theme, 0));
+        lines.push(Line::raw(""));
+    }
+
+    // Terms, Contact, License
+    let mut metadata: Vec<(&str, String)> = Vec::new();
+    if let Some(terms) = &spec.terms_of_service {
+        metadata.push(("Terms of Service", terms.clone()));
+    }
+    if let Some(contact) = &spec.contact {
+        let mut parts: Vec<String> = Vec::new();
+        parts.extend(contact.name.clone());
+        parts.extend(contact.email.as_ref().map(|email| format!("<{}>", email)));
+        parts.extend(contact.url.clone());
+        if !parts.is_empty() {
+            metadata.push(("Contact", parts.join(" ")));
+        }
+    }
+    if let Some(license) = &spec.license {
+        let value = match &license.url {
+            Some(url) => format!("{} ({})", license.name, url),
+            None => license.name.clone(),
+        };
+        metadata.push(("License", value));
+    }
+    if !metadata.is_empty() {
+        for (label, value) in metadata {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}: ", label), theme.subtle),
+                Span::styled(value, theme.text),
+            ]));
+        }
+        lines.push(Line::raw(""));
+    }
+
+    // Servers
+    if !spec.servers.is_empty() {
+        lines.push(Line::styled("Servers", theme.heading));
+        for server in &spec.servers {
+            let mut spans = vec![
+                Span::raw("  "),
+                Span::styled(server.url.clone(), theme.accent),
+            ];
+            if let Some(description) = &server.description {
+                spans.push(Span::styled(format!(" - {}", description), theme.muted));
+            }
+            lines.push(Line::from(spans));
+            for variable in &server.variables {
+                let mut text = format!("    {{{}}} = {}", variable.name, variable.default);
+                if !variable.enum_values.is_empty() {
+                    text.push_str(&format!(" ({})", variable.enum_values.join(", ")));
+                }
+                lines.push(Line::styled(text, theme.subtle));
+            }
+        }
+        lines.push(Line::raw(""));
+    }
+
+    // Tags
+    if !spec.tags.is_empty() {
+        lines.push(Line::styled("Tags", theme.heading));
+        for tag in &spec.tags {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(tag.name.clone(), theme.accent),
+            ]));
+            if let Some(description) = &tag.description {
+                lines.extend(markdown::render(description, theme.muted, theme, 4));
+            }
+        }
+        lines.push(Line::raw(""));
+    }
+
+    // Security schemes
+    if !spec.security_schemes.is_empty() {
+        lines.push(Line::styled("Security Schemes", theme.heading));
+        for scheme in &spec.security_schemes {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(scheme.name.clone(), theme.accent),
+                Span::styled(format!(" - {}", scheme.kind), theme.muted),
+            ]));
+            match &scheme.kind {
+                SecuritySchemeKind::OAuth2 { flows } => {
+                    for flow in flows {
+                        let mut text = format!("    {}", flow.kind);
+                        if let Some(url) = &flow.authorization_url {
+                            text.push_str(&format!(" authorize: {}", url));
+                        }
+                        if let Some(url) = &flow.token_url {
+                            text.push_str(&format!(" token: {}", url));
+                        }
+                        lines.push(Line::styled(text, theme.subtle));
+                        for (name, description) in &flow.scopes {
+                            lines.push(Line::from(vec![
+                                Span::raw("      "),
+                                Span::styled(name.clone(), theme.text),
+                                Span::styled(format!(" - {}", description), theme.muted),
+                            ]));
+                        }
+                    }
+                }
+                SecuritySchemeKind::OpenIdConnect { url } => {
+                    lines.push(Line::styled(format!("    {}", url), theme.subtle));
+                }
+                _ => {}
+            }
+            if let Some(description) = &scheme.description {
+                lines.extend(markdown::render(description, theme.muted, theme, 4));
+            }
+        }
+        if !spec.security.is_empty() {
+            let defaults: Vec<String> = spec
+                .security
+                .iter()
+                .map(|req| {
+                    if req.is_empty() {
+                        return "anonymous".to_string();
+                    }
+                    req.iter()
+                        .map(|r| r.scheme.clone())
+                        .collect::<Vec<_>>()
+                        .join(" + ")
+                })
+                .collect();
+            lines.push(Line::from(vec![
+                Span::styled("  Default: ", theme.subtle),
+                Span::styled(defaults.join(" or "), theme.text),
+            ]));
+        }
+        lines.push(Line::raw(""));
+    }
+
+    if !spec.extensions.is_empty() {
+        lines.push(Line::styled("Extensions", theme.heading));
+        lines.extend(extension_lines(&spec.extensions, 2, theme));
+        lines.push(Line::raw(""));
+    }
+
+    // Statistics
+    let stats = spec.stats();
+    lines.push(Line::styled("Statistics", theme.heading));
+    let count_line = |label: String, style: Style, count: usize| {
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(format!("{:width$}", label, width = 12), style),
+            Span::styled(count.to_string(), theme.text),
+        ])
+    };
+    lines.push(count_line(
+        "Operations".to_string(),
+        theme.subtle,
+        stats.operations,
+    ));
+    for (method, count) in &stats.by_method {
+        lines.push(count_line(
+            format!("  {}", method),
+            theme.method(method),
+            *count,
+        ));
+    }
+    lines.push(count_line(
+        "Deprecated".to_string(),
+        theme.subtle,
+        stats.deprecated,
+    ));
+    lines.push(count_line(
+        "Schemas".to_string(),
+        theme.subtle,
+        stats.schemas,
+    ));
 
+    if !stats.by_tag.is_empty() {
+        let width = stats
+            .by_tag
+            .iter()
+            .map(|(tag, _)| tag.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("(untagged)".len());
+        lines.push(Line::raw(""));
+        lines.push(Line::styled("  Operations by tag", theme.subtle));
+        let mut rows: Vec<(String, usize)> = stats.by_tag.clone();
+        if stats.untagged > 0 {
+            rows.push(("(untagged)".to_string(), stats.untagged));
+        }
+        for (tag, count) in rows {
             lines.push(Line::from(vec![
-                Span::raw("  "),
-                Span::styled(status.clone(), Style::default().fg(status_color)),
-                Span::raw(" - "),
-                Span::styled(
-                    response.description.clone(),
-                    Style::default().fg(Color::White),
-                ),
+                Span::raw("    "),
+                Span::styled(format!("{:width$}  ", tag, width = width), theme.accent),
+                Span::styled(count.to_string(), theme.text),
             ]));
-
-            if !response.content_types.is_empty() {
-                lines.push(Line::styled(
-                    format!("    Content-Type: {}", response.content_types.join(", ")),
-                    Style::default().fg(Color::DarkGray),
-                ));
-            }
-
-            if let Some(schema) = &response.schema {
-                lines.push(Line::styled(
-                    format!("    Schema: {}", schema),
-                    Style::default().fg(Color::DarkGray),
-                ));
-            }
         }
     }
 
@@ -332,18 +2129,9 @@ fn build_detail_content(endpoint: &Endpoint) -> Text<'static> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{Parameter, RequestBody, Response};
+    use crate::model::{ApiSpec, HttpMethod, Parameter, RequestBody, Response};
     use std::collections::BTreeMap;
 
-    #[test]
-    fn test_method_color() {
-        assert_eq!(method_color(&HttpMethod::Get), Color::Green);
-        assert_eq!(method_color(&HttpMethod::Post), Color::Blue);
-        assert_eq!(method_color(&HttpMethod::Put), Color::Yellow);
-        assert_eq!(method_color(&HttpMethod::Delete), Color::Red);
-        assert_eq!(method_color(&HttpMethod::Patch), Color::Cyan);
-    }
-
     #[test]
     fn test_method_width() {
         // Ensure width accommodates all method names
@@ -353,6 +2141,131 @@ mod tests {
         assert!(method_width() >= "OPTIONS".len());
     }
 
+    fn create_app() -> App {
+        App::new(ApiSpec {
+            title: "Test".to_string(),
+            version: "1.0".to_string(),
+            description: None,
+            endpoints: vec![],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_pane_areas_side_by_side_on_wide_terminal() {
+        let app = create_app();
+        let (list, detail) = pane_areas(Rect::new(0, 0, 200, 50), &app);
+        let (list, detail) = (list.unwrap(), detail.unwrap());
+
+        assert_eq!(list.width, 80);
+        assert_eq!(detail.x, 80);
+        assert_eq!(list.height, 50);
+    }
+
+    #[test]
+    fn test_pane_areas_stacked_on_narrow_terminal() {
+        let app = create_app();
+        let (list, detail) = pane_areas(Rect::new(0, 0, 60, 50), &app);
+        let (list, detail) = (list.unwrap(), detail.unwrap());
+
+        assert_eq!(list.width, 60);
+        assert_eq!(list.height, 20);
+        assert_eq!(detail.y, 20);
+    }
+
+    #[test]
+    fn test_pane_areas_respects_split_percent() {
+        let mut app = create_app();
+        app.grow_list();
+        let (list, _) = pane_areas(Rect::new(0, 0, 200, 50), &app);
+
+        assert_eq!(list.unwrap().width, 90);
+    }
+
+    #[test]
+    fn test_pane_areas_zoomed_shows_focused_pane_only() {
+        let mut app = create_app();
+        let area = Rect::new(0, 0, 200, 50);
+        app.toggle_zoom();
+
+        assert_eq!(pane_areas(area, &app), (Some(area), None));
+
+        app.focus_detail();
+        assert_eq!(pane_areas(area, &app), (None, Some(area)));
+    }
+
+    #[test]
+    fn test_max_scroll() {
+        assert_eq!(max_scroll(10, 20), 0);
+        assert_eq!(max_scroll(30, 20), 10);
+        assert_eq!(max_scroll(100_000, 0), u16::MAX);
+    }
+
+    #[test]
+    fn test_render_clamps_scroll_after_resize() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = create_app();
+        app.spec.endpoints.push(Endpoint {
+            method: HttpMethod::Get,
+            path: "/users".to_string(),
+            summary: Some("Get all users".to_string()),
+            description: None,
+            operation_id: None,
+            tags: vec![],
+            parameters: vec![],
+            request_body: None,
+            responses: BTreeMap::new(),
+            ..Default::default()
+        });
+        app.filtered_indices = vec![0];
+        app.detail_scroll = 50;
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 10)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app)).unwrap();
+
+        // Three content lines fit in the 8-row pane, so nothing to scroll
+        assert_eq!(app.detail_max_scroll, 0);
+        assert_eq!(app.detail_scroll, 0);
+    }
+
+    #[test]
+    fn test_build_overview_content() {
+        let spec = crate::parser::parse_file("tests/fixtures/info-metadata.yaml").unwrap();
+        let text = build_overview_content(&spec, &Theme::dark()).to_string();
+
+        assert!(text.contains("Bookstore API v2.1.0"));
+        assert!(text.contains("Manage books and authors."));
+        assert!(text.contains("Terms of Service: https://example.com/terms"));
+        assert!(
+            text.contains("Contact: API Support <support@example.com> https://example.com/support")
+        );
+        assert!(text.contains("License: Apache 2.0"));
+        assert!(text.contains("https://api.example.com/v2 - Production"));
+        assert!(text.contains("Everything about books"));
+        assert!(text.contains("Operations  4"));
+        assert!(text.contains("  GET       2"));
+        assert!(text.contains("Deprecated  1"));
+        assert!(text.contains("Schemas     3"));
+        assert!(text.contains("books       3"));
+        assert!(text.contains("authors     0"));
+        assert!(text.contains("(untagged)  1"));
+    }
+
+    #[test]
+    fn test_build_overview_content_minimal_spec() {
+        let spec = ApiSpec {
+            title: "Tiny".to_string(),
+            version: "0.1".to_string(),
+            ..Default::default()
+        };
+        let text = build_overview_content(&spec, &Theme::dark()).to_string();
+
+        assert!(text.contains("Tiny v0.1"));
+        assert!(!text.contains("Servers"));
+        assert!(!text.contains("Operations by tag"));
+    }
+
     #[test]
     fn test_build_detail_content_basic() {
         let endpoint = Endpoint {
@@ -365,9 +2278,18 @@ mod tests {
             parameters: vec![],
             request_body: None,
             responses: BTreeMap::new(),
+            ..Default::default()
         };
 
-        let content = build_detail_content(&endpoint);
+        let content = build_detail_content(
+            &endpoint,
+            &ApiSpec::default(),
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        );
         let text = content.to_string();
 
         assert!(text.contains("GET"));
@@ -375,6 +2297,111 @@ mod tests {
         assert!(text.contains("Get all users"));
     }
 
+    #[test]
+    fn test_build_detail_content_shows_resolved_url() {
+        let endpoint = Endpoint {
+            method: HttpMethod::Get,
+            path: "/users".to_string(),
+            ..Default::default()
+        };
+
+        let text = build_detail_content(
+            &endpoint,
+            &ApiSpec::default(),
+            Some("https://api.example.com/v1/users"),
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        )
+        .to_string();
+
+        assert!(text.contains("GET /users\nhttps://api.example.com/v1/users\n"));
+    }
+
+    #[test]
+    fn test_build_detail_content_security() {
+        let spec = crate::parser::parse_file("tests/fixtures/security.yaml").unwrap();
+        let find = |path: &str, method: HttpMethod| {
+            spec.endpoints
+                .iter()
+                .find(|e| e.path == path && e.method == method)
+                .unwrap()
+        };
+
+        let create = find("/pets", HttpMethod::Post);
+        let text =
+            build_detail_content(create, &spec, None, 0, false, false, &Theme::dark()).to_string();
+        assert!(text.contains("Security\n  oauth (OAuth2) [admin:write]\n"));
+        assert!(text.contains("  or bearer (HTTP bearer (JWT))\n"));
+
+        let health = find("/health", HttpMethod::Get);
+        let text =
+            build_detail_content(health, &spec, None, 0, false, false, &Theme::dark()).to_string();
+        assert!(text.contains("Security\n  None (public)\n"));
+    }
+
+    #[test]
+    fn test_build_overview_content_security_schemes() {
+        let spec = crate::parser::parse_file("tests/fixtures/security.yaml").unwrap();
+        let text = build_overview_content(&spec, &Theme::dark()).to_string();
+
+        assert!(text.contains("Security Schemes"));
+        assert!(text.contains("api_key - API key in header \"X-API-Key\""));
+        assert!(text.contains("clientCredentials token: https://auth.example.com/token"));
+        assert!(text.contains("admin:write - Modify anything"));
+        assert!(text.contains("Token issued by the login endpoint."));
+        assert!(text.contains("Default: api_key"));
+    }
+
+    #[test]
+    fn test_centered_area() {
+        let area = Rect::new(0, 0, 100, 40);
+        assert_eq!(centered_area(area, 60, 10), Rect::new(20, 15, 60, 10));
+        assert_eq!(centered_area(area, 200, 50), area);
+    }
+
+    #[test]
+    fn test_render_server_picker_popup() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let spec = crate::parser::parse_file("tests/fixtures/servers.yaml").unwrap();
+        let mut app = App::new(spec);
+        let users = app
+            .spec
+            .endpoints
+            .iter()
+            .position(|e| e.path == "/users")
+            .unwrap();
+        app.selected_index = users;
+        app.open_server_picker();
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app)).unwrap();
+        let buffer = format!("{:?}", terminal.backend().buffer());
+
+        assert!(buffer.contains("https://api.example.com:443/v1"));
+        assert!(buffer.contains("http://localhost:8080"));
+        assert!(buffer.contains("Local development"));
+
+        // Endpoints with their own servers pick among those
+        let files = app
+            .spec
+            .endpoints
+            .iter()
+            .position(|e| e.path == "/files" && e.method == HttpMethod::Get)
+            .unwrap();
+        app.close_popup();
+        app.selected_index = files;
+        app.open_server_picker();
+        terminal.draw(|frame| render(frame, &mut app)).unwrap();
+        let buffer = format!("{:?}", terminal.backend().buffer());
+
+        assert!(buffer.contains("Servers for /files"));
+        assert!(buffer.contains("https://files.example.com"));
+        assert!(!buffer.contains("Local development"));
+    }
+
     #[test]
     fn test_build_detail_content_with_parameters() {
         let endpoint = Endpoint {
@@ -390,27 +2417,505 @@ mod tests {
                     location: ParameterLocation::Path,
                     description: Some("User ID".to_string()),
                     required: true,
+                    deprecated: false,
                     schema_type: Some("integer".to_string()),
+                    style: ParameterStyle::Simple,
+                    explode: false,
+                    ..Default::default()
                 },
                 Parameter {
                     name: "include".to_string(),
                     location: ParameterLocation::Query,
                     description: None,
                     required: false,
+                    deprecated: false,
                     schema_type: Some("string".to_string()),
+                    ..Default::default()
                 },
             ],
             request_body: None,
             responses: BTreeMap::new(),
+            ..Default::default()
         };
 
-        let content = build_detail_content(&endpoint);
+        let content = build_detail_content(
+            &endpoint,
+            &ApiSpec::default(),
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        );
         let text = content.to_string();
 
         assert!(text.contains("Parameters"));
         assert!(text.contains("id*"));
         assert!(text.contains("(integer)"));
         assert!(text.contains("include"));
+        assert!(!text.contains("explode="));
+    }
+
+    #[test]
+    fn test_build_detail_content_deprecated_parameter() {
+        let endpoint = Endpoint {
+            method: HttpMethod::Get,
+            path: "/users".to_string(),
+            parameters: vec![Parameter {
+                name: "include".to_string(),
+                location: ParameterLocation::Query,
+                description: None,
+                required: false,
+                deprecated: true,
+                schema_type: Some("string".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let text = build_detail_content(
+            &endpoint,
+            &ApiSpec::default(),
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        )
+        .to_string();
+
+        assert!(text.contains("include (string) deprecated"));
+        // Only the operation itself is flagged in capitals
+        assert!(!text.contains("DEPRECATED"));
+    }
+
+    #[test]
+    fn test_build_detail_content_parameter_serialization() {
+        let spec = crate::parser::parse_file("tests/fixtures/parameter-styles.yaml").unwrap();
+        let text = build_detail_content(
+            &spec.endpoints[0],
+            &spec,
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        )
+        .to_string();
+
+        assert!(text.contains("      matrix, explode=true → ;ids=blue;ids=black;ids=brown\n"));
+        assert!(text.contains("      form, explode=true → ?tags=blue&tags=black&tags=brown\n"));
+        assert!(text.contains("      pipeDelimited, explode=false → ?colors=blue|black|brown\n"));
+        assert!(text.contains(
+            "      deepObject, explode=false → ?filter[R]=100&filter[G]=200&filter[B]=150\n"
+        ));
+        assert!(
+            text.contains("      form, explode=true, allowReserved, allowEmptyValue → ?q=blue\n")
+        );
+        assert!(text.contains("    where (Filter)\n      application/json: Filter\n"));
+        // Plain parameters get no serialization line
+        assert!(text.contains("    X-Trace (string)\n"));
+        assert!(!text.contains("simple, explode=false"));
+    }
+
+    #[test]
+    fn test_build_detail_content_constraints() {
+        let spec = crate::parser::parse_file("tests/fixtures/constraints.yaml").unwrap();
+        let text = build_detail_content(
+            &spec.endpoints[0],
+            &spec,
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        )
+        .to_string();
+
+        assert!(text.contains("    limit (integer, int32)\n      1 ≤ x ≤ 100 · default: 20\n"));
+        assert!(text.contains("    sort (SortOrder)\n      one of: asc | desc · default: asc\n"));
+        assert!(text.contains("      length = 3 · pattern /^[A-Z]{3}$/ · nullable\n"));
+        assert!(text.contains("    price (number, double)\n      > 0 · multiple of 0.01\n"));
+        assert!(text.contains("      items ≤ 10 · unique items\n"));
+        assert!(text.contains("    since (string, date-time)\n"));
+        assert!(text.contains("      X-Page-Size (integer)\n        ≤ 100"));
+    }
+
+    #[test]
+    fn test_build_detail_content_examples() {
+        let spec = crate::parser::parse_file("tests/fixtures/examples.yaml").unwrap();
+        let endpoint = &spec.endpoints[0];
+
+        let text = build_detail_content(endpoint, &spec, None, 0, false, false, &Theme::dark())
+            .to_string();
+        assert!(text.contains("    limit (integer)\n      Example: 20\n"));
+        assert!(text.contains("      Example 1/2 available: available\n"));
+        assert!(text.contains(
+            "      Example 1/4 one - A single dog\n        [\n          {\n            \"id\": 1,"
+        ));
+        assert!(text.contains(
+            "    application/yaml: Pet\n      Example\n        id: 7\n        name: Fido\n"
+        ));
+        assert!(text.contains("    text/csv\n      Example\n        id,name\n        1,Rex"));
+
+        let text = build_detail_content(endpoint, &spec, None, 1, false, false, &Theme::dark())
+            .to_string();
+        assert!(text.contains("      Example 2/2 sold - Already sold: sold\n"));
+        assert!(text.contains("      Example 2/4 cat - A cat\n"));
+
+        let text = build_detail_content(endpoint, &spec, None, 3, false, false, &Theme::dark())
+            .to_string();
+        // Groups with fewer examples wrap around
+        assert!(text.contains("      Example 2/2 sold"));
+        assert!(text.contains("      Example 4/4 remote\n        https://example.com/pets.json\n"));
+    }
+
+    #[test]
+    fn test_build_detail_content_generated_example() {
+        let spec = crate::parser::parse_file("tests/fixtures/samples.yaml").unwrap();
+        let endpoint = &spec.endpoints[0];
+
+        let text = build_detail_content(endpoint, &spec, None, 0, false, false, &Theme::dark())
+            .to_string();
+        assert!(text.contains(
+            "  application/json: Pet\n    Generated example (all fields)\n      {\n        \"id\""
+        ));
+        assert!(text.contains("\"vaccinated\": true"));
+        assert!(!text.contains("Generated example (required fields)"));
+
+        let text =
+            build_detail_content(endpoint, &spec, None, 0, true, false, &Theme::dark()).to_string();
+        assert!(text.contains("    Generated example (required fields)\n"));
+        assert!(!text.contains("\"vaccinated\""));
+        // Given examples are shown instead
+        assert!(text.contains("      Example\n        {\n          \"code\": 400,"));
+    }
+
+    #[test]
+    fn test_build_detail_content_callback() {
+        let spec = crate::parser::parse_file("tests/fixtures/callbacks.yaml").unwrap();
+
+        let callback = &spec.endpoints[3];
+        let text = build_detail_content(callback, &spec, None, 0, false, false, &Theme::dark())
+            .to_string();
+        assert!(text.starts_with(
+            "POST {$request.body#/callbackUrl}\nCallback onEvent of POST /subscriptions\n\nEvent notification\n"
+        ));
+        assert!(text.contains("Request Body\n  application/json: Event\n"));
+
+        let webhook = &spec.endpoints[5];
+        let text =
+            build_detail_content(webhook, &spec, None, 0, false, false, &Theme::dark()).to_string();
+        assert!(text.starts_with("POST newPet\nWebhook\n"));
+    }
+
+    #[test]
+    fn test_build_detail_content_extensions() {
+        let spec = crate::parser::parse_file("tests/fixtures/extensions.yaml").unwrap();
+        let list = &spec.endpoints[1];
+
+        let text =
+            build_detail_content(list, &spec, None, 0, false, false, &Theme::dark()).to_string();
+        assert!(text.ends_with("\n\nExtensions (6) - press x to show"));
+
+        let text =
+            build_detail_content(list, &spec, None, 0, false, true, &Theme::dark()).to_string();
+        assert!(text.contains(
+            "Extensions (6)\n  x-rate-limit:\n    {\n      \"requests\": 100,\n      \"period\": \"minute\"\n    }\n"
+        ));
+        assert!(text.contains("  Path\n    x-gateway: edge\n"));
+        assert!(text.contains("  Parameter limit\n    x-example-hint: small\n"));
+        assert!(text.contains("  Parameter limit schema\n    x-max-page: 50\n"));
+        assert!(text.contains("  Response 200\n    x-cache-ttl: 60\n"));
+        assert!(text.ends_with("  Response 200 application/json schema\n    x-paginated: true"));
+
+        let create = &spec.endpoints[2];
+        let text =
+            build_detail_content(create, &spec, None, 0, false, true, &Theme::dark()).to_string();
+        assert!(text.contains("  Request body\n    x-body-name: user\n"));
+
+        let health = &spec.endpoints[0];
+        let text =
+            build_detail_content(health, &spec, None, 0, false, true, &Theme::dark()).to_string();
+        assert!(text.ends_with("Extensions (1)\n  x-internal: false"));
+    }
+
+    #[test]
+    fn test_build_component_content_extensions() {
+        let spec = crate::parser::parse_file("tests/fixtures/extensions.yaml").unwrap();
+        let user = spec.components.iter().find(|c| c.name == "User").unwrap();
+        let text = build_component_content(user, &spec, 0, false, &Theme::dark()).to_string();
+        assert!(text.contains("Type object\n  x-entity: user\n\nProperties\n"));
+        assert!(text.contains("  email (string)\n    x-pii: true\n  team (UserList)"));
+    }
+
+    #[test]
+    fn test_build_overview_content_extensions() {
+        let spec = crate::parser::parse_file("tests/fixtures/extensions.yaml").unwrap();
+        let text = build_overview_content(&spec, &Theme::dark()).to_string();
+        assert!(text.contains("Extensions\n  x-api-owner: platform\n  x-audience: internal\n\n"));
+    }
+
+    #[test]
+    fn test_build_component_content_schema() {
+        let spec = crate::parser::parse_file("tests/fixtures/components.yaml").unwrap();
+        let content = build_component_content(&spec.components[0], &spec, 0, false, &Theme::dark());
+        let text = content.to_string();
+        assert!(
+            text.starts_with("Schema Pet\n\nA pet in the store.\n\nType object\n\nProperties\n")
+        );
+        assert!(text
+            .contains("  id* (integer, int64)\n    ≥ 1\n  name* (string)\n    The pet's name.\n"));
+        assert!(text.contains("  owner (Owner)\n    Who takes care of a pet.\n  friends (Pet[])"));
+
+        let owner = content
+            .lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .find(|span| span.content == "Owner")
+            .unwrap();
+        assert_eq!(owner.style, Theme::dark().link);
+
+        let text = build_component_content(&spec.components[2], &spec, 0, false, &Theme::dark())
+            .to_string();
+        assert!(text.ends_with("One of\n  Pet\n  string\n    A free-form description."));
+    }
+
+    #[test]
+    fn test_build_component_content_parameter_and_response() {
+        let spec = crate::parser::parse_file("tests/fixtures/components.yaml").unwrap();
+
+        let text = build_component_content(&spec.components[4], &spec, 0, false, &Theme::dark())
+            .to_string();
+        assert!(text.starts_with(
+            "Parameter Limit\n\n  query\n    limit (integer)\n      ≤ 100\n      Page size."
+        ));
+
+        let text = build_component_content(&spec.components[5], &spec, 0, false, &Theme::dark())
+            .to_string();
+        assert!(text.starts_with(
+            "Response NotFound\n\n  NotFound - Not found\n    application/json: Error\n"
+        ));
+    }
+
+    #[test]
+    fn test_build_detail_content_references() {
+        let spec = crate::parser::parse_file("tests/fixtures/components.yaml").unwrap();
+        let text = build_detail_content(
+            &spec.endpoints[0],
+            &spec,
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        )
+        .to_string();
+        assert!(text.contains("    limit (integer) ↗ Limit\n"));
+        assert!(text.contains("    owner (Owner)\n"));
+        assert!(text.contains("  200 - OK\n    application/json: Pet[]\n"));
+        assert!(text.contains("  404 ↗ NotFound - Not found\n"));
+    }
+
+    #[test]
+    fn test_build_detail_content_deprecated_badge() {
+        let endpoint = Endpoint {
+            method: HttpMethod::Get,
+            path: "/v1/users".to_string(),
+            deprecated: true,
+            ..Default::default()
+        };
+
+        let text = build_detail_content(
+            &endpoint,
+            &ApiSpec::default(),
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        )
+        .to_string();
+        assert!(text.contains("GET /v1/users\nDEPRECATED\n"));
+    }
+
+    #[test]
+    fn test_render_list_title_when_hiding_deprecated() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let spec = crate::parser::parse_file("tests/fixtures/info-metadata.yaml").unwrap();
+        let mut app = App::new(spec);
+        app.toggle_hide_deprecated();
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app)).unwrap();
+        let buffer = format!("{:?}", terminal.backend().buffer());
+
+        assert!(buffer.contains("[no deprecated] (3/4)"));
+    }
+
+    #[test]
+    fn test_render_request_builder() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let spec = crate::parser::parse_file("tests/fixtures/builder.yaml").unwrap();
+        let mut app = App::new(spec);
+        app.open_request_builder();
+        let form = app.request_form.as_mut().unwrap();
+        form.pop_char();
+        form.pop_char();
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..20)
+            .map(|y| (0..120).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+
+        assert!(rows[0].contains("GET /pets"));
+        assert!(rows[1].starts_with("│ query  limit*  :  "));
+        assert!(rows[2].contains(" query  status  : ◂ (none) ▸"));
+        // The preview leaves out the empty parameter
+        assert!(rows[1].contains("│curl '/pets'"));
+        assert!(rows.iter().any(|row| row.contains(" limit is required")));
+    }
+
+    #[test]
+    fn test_render_mock() {
+        use crate::mock::{self, MockLog};
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let spec = crate::parser::parse_file("tests/fixtures/mock.yaml").unwrap();
+        let mut log = MockLog::new(
+            spec.title.clone(),
+            "http://127.0.0.1:4010".to_string(),
+            &crate::config::LayoutConfig {
+                split_percent: 50,
+                stack_below: 100,
+            },
+            Theme::dark(),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(120, 12)).unwrap();
+        let rows = |terminal: &Terminal<TestBackend>| -> Vec<String> {
+            let buffer = terminal.backend().buffer();
+            (0..12)
+                .map(|y| (0..120).map(|x| buffer[(x, y)].symbol()).collect())
+                .collect()
+        };
+        terminal.draw(|frame| render_mock(frame, &mut log)).unwrap();
+        assert!(rows(&terminal)[1].contains("Waiting for requests on http://127.0.0.1:4010…"));
+
+        let (response, endpoint) = mock::handle(&spec, "GET", "/pets/7", &[]);
+        log.push(mock::LogEntry {
+            method: "GET".to_string(),
+            url: "/pets/7".to_string(),
+            operation: endpoint.map(|_| "GET /pets/{petId}".to_string()),
+            request_headers: vec![("Accept".to_string(), "*/*".to_string())],
+            request_body: String::new(),
+            response,
+            elapsed: std::time::Duration::from_millis(3),
+        });
+        terminal.draw(|frame| render_mock(frame, &mut log)).unwrap();
+        let rows = rows(&terminal);
+        assert!(rows[0].contains("Mock Pets on http://127.0.0.1:4010 (1 request)"));
+        assert!(rows[1].contains("   1 200 GET     /pets/7  3 ms"));
+        assert!(rows[1].contains("Answered by GET /pets/{petId}"));
+        assert!(rows[3].contains("Request GET /pets/7"));
+
+        // The configured layout stacks the details below the log
+        log.stack_below = 130;
+        terminal.draw(|frame| render_mock(frame, &mut log)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..12)
+            .map(|y| (0..120).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+        assert!(!rows[1].contains("Answered by"));
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("Answered by GET /pets/{petId}")));
+    }
+
+    #[test]
+    fn test_build_response_content() {
+        use crate::http::Response;
+        use crate::snippet::Request;
+
+        let theme = Theme::dark();
+        let mut exchange = Exchange {
+            endpoint: 0,
+            request: Request::default(),
+            response: Ok(Response {
+                status: 404,
+                status_text: "Not Found".to_string(),
+                headers: vec![("content-type".to_string(), "application/json".to_string())],
+                body: r#"{"message":"No such pet"}"#.to_string(),
+                elapsed: std::time::Duration::from_millis(42),
+            }),
+            validation: None,
+        };
+        let text = build_response_content(&exchange, &theme);
+        let lines: Vec<String> = text.lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(lines[0..3], ["404 Not Found  42 ms  25 B", "", "Headers"]);
+        assert_eq!(lines[3], "  content-type: application/json");
+        assert_eq!(
+            lines[5..],
+            ["Body", "  {", "    \"message\": \"No such pet\"", "  }"]
+        );
+        assert_eq!(text.lines[0].spans[0].style, theme.status("404"));
+
+        exchange.validation = Some(Validation::default());
+        let text = build_response_content(&exchange, &theme);
+        assert_eq!(text.lines[2].to_string(), "Contract");
+        assert_eq!(
+            text.lines[3].to_string(),
+            "  ✓ Matches the declared responses"
+        );
+
+        exchange.validation = Some(Validation {
+            matched: Some("4XX".to_string()),
+            problems: vec![],
+            violations: vec![crate::validate::Violation {
+                pointer: "/message".to_string(),
+                message: "expected integer, got string".to_string(),
+            }],
+        });
+        let text = build_response_content(&exchange, &theme);
+        let lines: Vec<String> = text.lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            lines[2..5],
+            [
+                "Contract",
+                "  Checked against the 4XX response",
+                "  ✗ /message expected integer, got string",
+            ]
+        );
+
+        exchange.response = Err("Connection refused".to_string());
+        let text = build_response_content(&exchange, &theme);
+        assert_eq!(text.lines[2].to_string(), "Connection refused");
+    }
+
+    #[test]
+    fn test_render_status_line() {
+        use crate::app::StatusMessage;
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = create_app();
+        app.status = Some(StatusMessage {
+            text: "Copied path (OSC 52)".to_string(),
+            error: false,
+        });
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let last_row: String = (0..80)
+            .map(|x| buffer[(x, 19)].symbol().to_string())
+            .collect();
+        assert_eq!(last_row.trim_end(), " Copied path (OSC 52)");
+        assert_eq!(buffer[(1, 19)].fg, Theme::dark().status_2xx.fg.unwrap());
+        // The panes end one row higher
+        assert_eq!(buffer[(0, 18)].symbol(), "└");
     }
 
     #[test]
@@ -426,13 +2931,26 @@ mod tests {
             request_body: Some(RequestBody {
                 description: Some("User data".to_string()),
                 required: true,
-                content_types: vec!["application/json".to_string()],
-                schema: Some("User".to_string()),
+                content: vec![MediaType {
+                    content_type: "application/json".to_string(),
+                    schema: Some("User".to_string()),
+                    ..Default::default()
+                }],
+                extensions: vec![],
             }),
             responses: BTreeMap::new(),
+            ..Default::default()
         };
 
-        let content = build_detail_content(&endpoint);
+        let content = build_detail_content(
+            &endpoint,
+            &ApiSpec::default(),
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        );
         let text = content.to_string();
 
         assert!(text.contains("Request Body (required)"));
@@ -440,6 +2958,65 @@ mod tests {
         assert!(text.contains("User data"));
     }
 
+    #[test]
+    fn test_build_detail_content_renders_markdown_descriptions() {
+        let mut responses = BTreeMap::new();
+        responses.insert(
+            "200".to_string(),
+            Response {
+                description: "**Success**".to_string(),
+                ..Default::default()
+            },
+        );
+        responses.insert(
+            "400".to_string(),
+            Response {
+                description: "Bad request:\n\n- missing `name`\n- invalid `age`".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let endpoint = Endpoint {
+            method: HttpMethod::Get,
+            path: "/users".to_string(),
+            summary: None,
+            description: Some("Lists **all** users.\n\nSupports `limit`.".to_string()),
+            operation_id: None,
+            tags: vec![],
+            parameters: vec![Parameter {
+                name: "limit".to_string(),
+                location: ParameterLocation::Query,
+                description: Some("Max *items*".to_string()),
+                required: false,
+                deprecated: false,
+                schema_type: Some("integer".to_string()),
+                ..Default::default()
+            }],
+            request_body: None,
+            responses,
+            ..Default::default()
+        };
+
+        let text = build_detail_content(
+            &endpoint,
+            &ApiSpec::default(),
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        )
+        .to_string();
+
+        assert!(text.contains("Lists all users."));
+        assert!(text.contains("Supports limit."));
+        assert!(text.contains("      Max items"));
+        assert!(text.contains("200 - Success"));
+        assert!(text.contains("    • missing name"));
+        assert!(!text.contains("**"));
+        assert!(!text.contains('`'));
+    }
+
     #[test]
     fn test_build_detail_content_with_responses() {
         let mut responses = BTreeMap::new();
@@ -447,16 +3024,19 @@ mod tests {
             "200".to_string(),
             Response {
                 description: "Successful response".to_string(),
-                content_types: vec!["application/json".to_string()],
-                schema: Some("UserList".to_string()),
+                content: vec![MediaType {
+                    content_type: "application/json".to_string(),
+                    schema: Some("UserList".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
             },
         );
         responses.insert(
             "404".to_string(),
             Response {
                 description: "Not found".to_string(),
-                content_types: vec![],
-                schema: None,
+                ..Default::default()
             },
         );
 
@@ -470,9 +3050,18 @@ mod tests {
             parameters: vec![],
             request_body: None,
             responses,
+            ..Default::default()
         };
 
-        let content = build_detail_content(&endpoint);
+        let content = build_detail_content(
+            &endpoint,
+            &ApiSpec::default(),
+            None,
+            0,
+            false,
+            false,
+            &Theme::dark(),
+        );
         let text = content.to_string();
 
         assert!(text.contains("Responses"));
@@ -480,5 +3069,38 @@ mod tests {
         assert!(text.contains("Successful response"));
         assert!(text.contains("404"));
         assert!(text.contains("Not found"));
+        assert!(text.contains("application/json: UserList"));
+    }
+
+    #[test]
+    fn test_build_detail_content_response_headers_and_links() {
+        let spec = crate::parser::parse_file("tests/fixtures/responses.yaml").unwrap();
+        let find = |path: &str, method: HttpMethod| {
+            spec.endpoints
+                .iter()
+                .find(|e| e.path == path && e.method == method)
+                .unwrap()
+        };
+
+        let create = find("/reports", HttpMethod::Post);
+        let text =
+            build_detail_content(create, &spec, None, 0, false, false, &Theme::dark()).to_string();
+        assert!(text.contains("  application/x-www-form-urlencoded: object\n"));
+        assert!(text.contains("    Headers\n      Location* (string)\n"));
+        assert!(text.contains("        URL of the new report\n"));
+        assert!(text.contains("      X-Rate-Limit (integer)\n"));
+        assert!(text.contains("    Links\n      GetReport → GET /reports/{id}\n"));
+        assert!(text.contains("        id = $response.body#/id\n"));
+        assert!(text.contains("      DownloadReport → GET /reports/{id}/download\n"));
+        assert!(text.contains("      DeleteReport → DELETE /reports/{id}\n"));
+
+        let get = find("/reports/{id}", HttpMethod::Get);
+        let text =
+            build_detail_content(get, &spec, None, 0, false, false, &Theme::dark()).to_string();
+        assert!(text.contains("    application/json: Report\n"));
+        assert!(text.contains("    application/xml: Report\n"));
+        assert!(text.ends_with(
+            "    text/csv: string\n      Generated example (all fields)\n        string"
+        ));
     }
 }
@@ -0,0 +1,657 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::http::Response;
+use crate::model::{ApiSpec, Endpoint, MediaType};
+
+/// `$ref`s followed in a row before giving up, for schemas that refer to
+/// themselves without descending into the value. Items and properties start
+/// counting again, as the value they are checked against gets smaller.
+const MAX_DEPTH: usize = 64;
+
+/// A place where the body doesn't match its schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON pointer into the body, empty for the body itself.
+    pub pointer: String,
+    pub message: String,
+}
+
+/// How a response compares to what the spec declares for it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validation {
+    /// The `responses` key the status matched, e.g. `200`, `4XX` or `default`.
+    pub matched: Option<String>,
+    /// Problems with the response as a whole: an undeclared status or
+    /// content type, or a body that doesn't parse.
+    pub problems: Vec<String>,
+    pub violations: Vec<Violation>,
+}
+
+impl Validation {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty() && self.violations.is_empty()
+    }
+}
+
+/// Checks the status of `response` against the responses of `endpoint`, then
+/// its JSON body against the schema declared for its content type.
+pub fn check(endpoint: &Endpoint, spec: &ApiSpec, response: &Response) -> Validation {
+    let mut validation = Validation::default();
+    let Some(key) = match_status(endpoint, response.status) else {
+        let declared: Vec<&str> = endpoint.responses.keys().map(String::as_str).collect();
+        validation.problems.push(format!(
+            "Status {} is not declared (declared: {})",
+            response.status,
+            declared.join(", ")
+        ));
+        return validation;
+    };
+    let declared = &endpoint.responses[key];
+    validation.matched = Some(key.to_string());
+
+    // Bodies without content (HEAD, 204, ...) have nothing to check
+    if declared.content.is_empty() || response.body.is_empty() {
+        return validation;
+    }
+    let content_type = response
+        .content_type()
+        .and_then(|ct| ct.split(';').next())
+        .map(|ct| ct.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    let Some(media) = match_media_type(&declared.content, &content_type) else {
+        let declared: Vec<&str> = declared
+            .content
+            .iter()
+            .map(|media| media.content_type.as_str())
+            .collect();
+        validation.problems.push(format!(
+            "Content type {} is not declared (declared: {})",
+            if content_type.is_empty() {
+                "(none)"
+            } else {
+                &content_type
+            },
+            declared.join(", ")
+        ));
+        return validation;
+    };
+    let Some(schema) = &media.schema_json else {
+        return validation;
+    };
+    if !content_type.contains("json") {
+        return validation;
+    }
+    match serde_json::from_str::<Value>(&response.body) {
+        Ok(body) => validation.violations = validate(&body, schema, &spec.schemas),
+        Err(err) => validation
+            .problems
+            .push(format!("The body is not valid JSON: {}", err)),
+    }
+    validation
+}
+
+/// The `responses` key for `status`: the exact code, then its `NXX` range,
+/// then `default`.
+fn match_status(endpoint: &Endpoint, status: u16) -> Option<&str> {
+    let exact = status.to_string();
+    let range = format!("{}XX", status / 100);
+    endpoint
+        .responses
+        .keys()
+        .find(|key| **key == exact)
+        .or_else(|| {
+            endpoint
+                .responses
+                .keys()
+                .find(|key| key.eq_ignore_ascii_case(&range))
+        })
+        .or_else(|| endpoint.responses.keys().find(|key| *key == "default"))
+        .map(String::as_str)
+}
+
+/// The declared media type for `content_type`, trying wildcards like
+/// `application/*` and `*/*` after an exact match.
+fn match_media_type<'a>(content: &'a [MediaType], content_type: &str) -> Option<&'a MediaType> {
+    let base = |media: &MediaType| {
+        media
+            .content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+    };
+    let range = content_type
+        .split_once('/')
+        .map(|(kind, _)| format!("{}/*", kind))
+        .unwrap_or_default();
+    content
+        .iter()
+        .find(|media| base(media) == content_type)
+        .or_else(|| content.iter().find(|media| base(media) == range))
+        .or_else(|| content.iter().find(|media| base(media) == "*/*"))
+}
+
+/// Validates `value` against an OpenAPI schema, resolving `$ref`s into
+/// `schemas` (`components.schemas`).
+///
+/// Covers types (with `nullable`), `enum`, numeric ranges, lengths, item and
+/// property counts, `required`, `properties`, `additionalProperties`, `items`
+/// and the `allOf`/`anyOf`/`oneOf`/`not` compositions. Formats and patterns
+/// are not checked.
+pub fn validate(
+    value: &Value,
+    schema: &Value,
+    schemas: &BTreeMap<String, Value>,
+) -> Vec<Violation> {
+    let mut validator = Validator {
+        schemas,
+        violations: Vec::new(),
+    };
+    validator.validate(value, schema, "", 0);
+    validator.violations
+}
+
+struct Validator<'a> {
+    schemas: &'a BTreeMap<String, Value>,
+    violations: Vec<Violation>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, pointer: &str, message: String) {
+        self.violations.push(Violation {
+            pointer: pointer.to_string(),
+            message,
+        });
+    }
+
+    /// Whether `value` matches `schema`, without reporting anything.
+    fn matches(&self, value: &Value, schema: &Value, depth: usize) -> bool {
+        let mut validator = Validator {
+            schemas: self.schemas,
+            violations: Vec::new(),
+        };
+        validator.validate(value, schema, "", depth);
+        validator.violations.is_empty()
+    }
+
+    fn validate(&mut self, value: &Value, schema: &Value, pointer: &str, depth: usize) {
+        let schema = match schema {
+            Value::Object(schema) => schema,
+            Value::Bool(false) => return self.report(pointer, "no value is allowed here".into()),
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let target = reference
+                .strip_prefix("#/components/schemas/")
+                .and_then(|name| {
+                    self.schemas
+                        .get(&name.replace("~1", "/").replace("~0", "~"))
+                });
+            if let Some(target) = target.filter(|_| depth < MAX_DEPTH) {
+                self.validate(value, target, pointer, depth + 1);
+            }
+            return;
+        }
+
+        let nullable = schema.get("nullable") == Some(&Value::Bool(true));
+        if value.is_null() && nullable {
+            return;
+        }
+        if let Some(types) = schema.get("type") {
+            let types: Vec<&str> = match types {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+                let message = format!("expected {}, got {}", types.join(" or "), type_name(value));
+                return self.report(pointer, message);
+            }
+        }
+
+        if let Some(Value::Array(allowed)) = schema.get("enum") {
+            if !allowed.contains(value) {
+                let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+                let message = format!("{} is not one of {}", value, allowed.join(", "));
+                self.report(pointer, message);
+            }
+        }
+
+        match value {
+            Value::Number(n) => {
+                self.validate_number(n.as_f64().unwrap_or_default(), schema, pointer)
+            }
+            Value::String(s) => {
+                let length = s.chars().count();
+                if let Some(min) = limit(schema, "minLength").filter(|&min| length < min) {
+                    self.report(pointer, format!("shorter than {} characters", min));
+                }
+                if let Some(max) = limit(schema, "maxLength").filter(|&max| length > max) {
+                    self.report(pointer, format!("longer than {} characters", max));
+                }
+            }
+            Value::Array(items) => {
+                if let Some(min) = limit(schema, "minItems").filter(|&min| items.len() < min) {
+                    self.report(pointer, format!("fewer than {} items", min));
+                }
+                if let Some(max) = limit(schema, "maxItems").filter(|&max| items.len() > max) {
+                    self.report(pointer, format!("more than {} items", max));
+                }
+                let unique = schema.get("uniqueItems") == Some(&Value::Bool(true));
+                if unique
+                    && items
+                        .iter()
+                        .enumerate()
+                        .any(|(i, item)| items[..i].contains(item))
+                {
+                    self.report(pointer, "items are not unique".into());
+                }
+                if let Some(item_schema) = schema.get("items") {
+                    for (i, item) in items.iter().enumerate() {
+                        self.validate(item, item_schema, &format!("{}/{}", pointer, i), 0);
+                    }
+                }
+            }
+            Value::Object(object) => self.validate_object(object, schema, pointer),
+            Value::Null | Value::Bool(_) => {}
+        }
+
+        if let Some(Value::Array(all)) = schema.get("allOf") {
+            for sub in all {
+                self.validate(value, sub, pointer, depth);
+            }
+        }
+        if let Some(Value::Array(any)) = schema.get("anyOf") {
+            if !any.iter().any(|sub| self.matches(value, sub, depth)) {
+                self.report(pointer, "matches none of the anyOf schemas".into());
+            }
+        }
+        if let Some(Value::Array(one)) = schema.get("oneOf") {
+            match one
+                .iter()
+                .filter(|sub| self.matches(value, sub, depth))
+                .count()
+            {
+                1 => {}
+                0 => self.report(pointer, "matches none of the oneOf schemas".into()),
+                n => self.report(
+                    pointer,
+                    format!("matches {} of the oneOf schemas instead of one", n),
+                ),
+            }
+        }
+        if let Some(not) = schema.get("not") {
+            if self.matches(value, not, depth) {
+                self.report(pointer, "matches the schema it must not match".into());
+            }
+        }
+    }
+
+    fn validate_number(&mut self, n: f64, schema: &serde_json::Map<String, Value>, pointer: &str) {
+        let number = |key: &str| schema.get(key).and_then(Value::as_f64);
+        // OpenAPI 3.0 flags exclusive bounds, 3.1 gives them as numbers
+        let exclusive = |key: &str| schema.get(key) == Some(&Value::Bool(true));
+        if let Some(min) = number("minimum") {
+            if n < min || exclusive("exclusiveMinimum") && n == min {
+                let op = if exclusive("exclusiveMinimum") {
+                    ">"
+                } else {
+                    "≥"
+                };
+                self.report(pointer, format!("must be {} {}", op, min));
+            }
+        }
+        if let Some(min) = number("exclusiveMinimum").filter(|&min| n <= min) {
+            self.report(pointer, format!("must be > {}", min));
+        }
+        if let Some(max) = number("maximum") {
+            if n > max || exclusive("exclusiveMaximum") && n == max {
+                let op = if exclusive("exclusiveMaximum") {
+                    "<"
+                } else {
+                    "≤"
+                };
+                self.report(pointer, format!("must be {} {}", op, max));
+            }
+        }
+        if let Some(max) = number("exclusiveMaximum").filter(|&max| n >= max) {
+            self.report(pointer, format!("must be < {}", max));
+        }
+        if let Some(step) = number("multipleOf").filter(|&step| step > 0.0) {
+            // Decimal steps like 0.01 aren't exact in binary, so 19.99 / 0.01
+            // comes out as 1998.9999999999998
+            let quotient = n / step;
+            if (quotient - quotient.round()).abs() > 1e-9 * quotient.abs().max(1.0) {
+                self.report(pointer, format!("not a multiple of {}", step));
+            }
+        }
+    }
+
+    fn validate_object(
+        &mut self,
+        object: &serde_json::Map<String, Value>,
+        schema: &serde_json::Map<String, Value>,
+        pointer: &str,
+    ) {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    self.report(pointer, format!("missing required property \"{}\"", name));
+                }
+            }
+        }
+        if let Some(min) = limit(schema, "minProperties").filter(|&min| object.len() < min) {
+            self.report(pointer, format!("fewer than {} properties", min));
+        }
+        if let Some(max) = limit(schema, "maxProperties").filter(|&max| object.len() > max) {
+            self.report(pointer, format!("more than {} properties", max));
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let additional = schema.get("additionalProperties");
+        for (name, value) in object {
+            let pointer = format!("{}/{}", pointer, escape(name));
+            match properties.and_then(|properties| properties.get(name)) {
+                Some(property) => self.validate(value, property, &pointer, 0),
+                None => match additional {
+                    Some(Value::Bool(false)) => {
+                        self.report(&pointer, "property is not allowed".into());
+                    }
+                    Some(additional) => self.validate(value, additional, &pointer, 0),
+                    None => {}
+                },
+            }
+        }
+    }
+}
+
+fn limit(schema: &serde_json::Map<String, Value>, key: &str) -> Option<usize> {
+    schema.get(key)?.as_u64().map(|n| n as usize)
+}
+
+fn has_type(value: &Value, schema_type: &str) -> bool {
+    match schema_type {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Escapes a property name for use in a JSON pointer.
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::HttpMethod;
+    use crate::parser::parse_file;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn violations(value: Value, schema: Value) -> Vec<(String, String)> {
+        validate(&value, &schema, &BTreeMap::new())
+            .into_iter()
+            .map(|v| (v.pointer, v.message))
+            .collect()
+    }
+
+    fn violation(pointer: &str, message: &str) -> (String, String) {
+        (pointer.to_string(), message.to_string())
+    }
+
+    #[test]
+    fn test_validate_types() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "tag": {"type": "string", "nullable": true},
+                "score": {"type": "number"},
+            },
+        });
+        assert!(violations(json!({"id": 1, "tag": null, "score": 2}), schema.clone()).is_empty());
+        assert_eq!(
+            violations(json!({"id": 1.5, "tag": 3, "score": "high"}), schema),
+            vec![
+                violation("/id", "expected integer, got number"),
+                violation("/tag", "expected string, got integer"),
+                violation("/score", "expected number, got string"),
+            ]
+        );
+        assert_eq!(
+            violations(json!(null), json!({"type": ["string", "null"]})),
+            vec![]
+        );
+        assert_eq!(
+            violations(json!([]), json!({"type": "object"})),
+            vec![violation("", "expected object, got array")]
+        );
+    }
+
+    #[test]
+    fn test_validate_constraints() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name", "age"],
+            "additionalProperties": false,
+            "properties": {
+                "name": {"type": "string", "minLength": 2},
+                "status": {"enum": ["available", "sold"]},
+                "age": {"type": "integer", "minimum": 0, "exclusiveMaximum": true, "maximum": 30},
+                "weight": {"type": "number", "exclusiveMinimum": 0, "multipleOf": 0.5},
+                "photos": {"type": "array", "maxItems": 2, "uniqueItems": true, "items": {"type": "string"}},
+            },
+        });
+        assert_eq!(
+            violations(
+                json!({
+                    "name": "R",
+                    "status": "lost",
+                    "weight": 0.75,
+                    "photos": ["a", "a", 1],
+                    "a/b": true,
+                }),
+                schema,
+            ),
+            vec![
+                violation("", "missing required property \"age\""),
+                violation("/name", "shorter than 2 characters"),
+                violation("/status", "\"lost\" is not one of \"available\", \"sold\""),
+                violation("/weight", "not a multiple of 0.5"),
+                violation("/photos", "more than 2 items"),
+                violation("/photos", "items are not unique"),
+                violation("/photos/2", "expected string, got integer"),
+                violation("/a~1b", "property is not allowed"),
+            ]
+        );
+        assert_eq!(
+            violations(json!(30), json!({"maximum": 30, "exclusiveMaximum": true})),
+            vec![violation("", "must be < 30")]
+        );
+        assert_eq!(
+            violations(json!(0), json!({"exclusiveMinimum": 0})),
+            vec![violation("", "must be > 0")]
+        );
+        assert_eq!(
+            violations(json!(19.99), json!({"multipleOf": 0.01})),
+            vec![]
+        );
+        assert_eq!(
+            violations(json!(19.995), json!({"multipleOf": 0.01})),
+            vec![violation("", "not a multiple of 0.01")]
+        );
+    }
+
+    #[test]
+    fn test_validate_compositions() {
+        let cat = json!({"type": "object", "required": ["meows"]});
+        let dog = json!({"type": "object", "required": ["barks"]});
+        let one_of = json!({"oneOf": [cat, dog]});
+        assert!(violations(json!({"meows": true}), one_of.clone()).is_empty());
+        assert_eq!(
+            violations(json!({"meows": true, "barks": true}), one_of.clone()),
+            vec![violation(
+                "",
+                "matches 2 of the oneOf schemas instead of one"
+            )]
+        );
+        assert_eq!(
+            violations(json!({}), one_of),
+            vec![violation("", "matches none of the oneOf schemas")]
+        );
+        assert_eq!(
+            violations(json!({}), json!({"anyOf": [cat, dog]})),
+            vec![violation("", "matches none of the anyOf schemas")]
+        );
+        assert_eq!(
+            violations(json!({"barks": 1}), json!({"allOf": [cat, dog]})),
+            vec![violation("", "missing required property \"meows\"")]
+        );
+        assert_eq!(
+            violations(json!("x"), json!({"not": {"type": "string"}})),
+            vec![violation("", "matches the schema it must not match")]
+        );
+    }
+
+    #[test]
+    fn test_validate_references() {
+        let mut schemas = BTreeMap::new();
+        schemas.insert(
+            "Node".to_string(),
+            json!({
+                "type": "object",
+                "properties": {"children": {"type": "array", "items": {"$ref": "#/components/schemas/Node"}}},
+                "required": ["name"],
+            }),
+        );
+        // Refers to itself without ever reaching a value
+        schemas.insert(
+            "Loop".to_string(),
+            json!({"$ref": "#/components/schemas/Loop"}),
+        );
+
+        let tree = json!({"name": "root", "children": [{"name": "a"}, {"children": []}]});
+        let found = validate(
+            &tree,
+            &json!({"$ref": "#/components/schemas/Node"}),
+            &schemas,
+        );
+        assert_eq!(
+            found,
+            vec![Violation {
+                pointer: "/children/1".to_string(),
+                message: "missing required property \"name\"".to_string(),
+            }]
+        );
+        assert!(validate(
+            &tree,
+            &json!({"$ref": "#/components/schemas/Loop"}),
+            &schemas
+        )
+        .is_empty());
+    }
+
+    fn response(status: u16, content_type: &str, body: &str) -> Response {
+        Response {
+            status,
+            status_text: String::new(),
+            headers: vec![("content-type".to_string(), content_type.to_string())],
+            body: body.to_string(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let spec = parse_file("tests/fixtures/validation.yaml").unwrap();
+        let endpoint = spec
+            .endpoints
+            .iter()
+            .find(|e| e.method == HttpMethod::Get)
+            .unwrap();
+
+        let ok = response(200, "application/json", r#"{"id": 1, "name": "Rex"}"#);
+        let validation = check(endpoint, &spec, &ok);
+        assert_eq!(validation.matched.as_deref(), Some("200"));
+        assert!(validation.is_valid());
+
+        let wrong = response(
+            200,
+            "application/json; charset=utf-8",
+            r#"{"id": 0, "owner": {}}"#,
+        );
+        let pointers: Vec<_> = check(endpoint, &spec, &wrong)
+            .violations
+            .into_iter()
+            .map(|v| format!("{}: {}", v.pointer, v.message))
+            .collect();
+        assert_eq!(
+            pointers,
+            vec![
+                ": missing required property \"name\"",
+                "/id: must be ≥ 1",
+                "/owner: missing required property \"name\"",
+            ]
+        );
+
+        // Ranges, then the default response
+        let not_found = response(404, "application/json", r#"{"message": "No such pet"}"#);
+        let validation = check(endpoint, &spec, &not_found);
+        assert_eq!(validation.matched.as_deref(), Some("4XX"));
+        assert!(validation.is_valid());
+        let error = response(503, "text/plain", "Unavailable");
+        assert_eq!(
+            check(endpoint, &spec, &error).matched.as_deref(),
+            Some("default")
+        );
+
+        let html = response(200, "text/html", "<p>Rex</p>");
+        assert_eq!(
+            check(endpoint, &spec, &html).problems,
+            vec!["Content type text/html is not declared (declared: application/json)"]
+        );
+        let broken = response(200, "application/json", "{");
+        assert!(
+            check(endpoint, &spec, &broken).problems[0].starts_with("The body is not valid JSON")
+        );
+    }
+
+    #[test]
+    fn test_check_undeclared_status() {
+        let spec = parse_file("tests/fixtures/validation.yaml").unwrap();
+        let endpoint = spec
+            .endpoints
+            .iter()
+            .find(|e| e.method == HttpMethod::Delete)
+            .unwrap();
+        let validation = check(endpoint, &spec, &response(500, "text/plain", ""));
+        assert_eq!(validation.matched, None);
+        assert_eq!(
+            validation.problems,
+            vec!["Status 500 is not declared (declared: 204, 404)"]
+        );
+    }
+}